@@ -0,0 +1,127 @@
+//! Timings of the skinning and bounds hot path on synthetic tubes of 1k, 10k and 100k vertices,
+//! with 8 and 64 joints, to catch regressions in `skin_model`, `compute_aabb` and the
+//! per-joint bounds.
+//!
+//! Run with `cargo bench`, criterion compares each run against the last one on the same machine.
+
+use std::f32::consts::TAU;
+
+use bevy::{
+    prelude::*,
+    render::mesh::PrimitiveTopology,
+    tasks::{ComputeTaskPool, TaskPool},
+};
+use bevy_compute_skinned_aabb::{compute_aabb, skin_vertices, JointAabbs, SkinnedMeshCpuData};
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+const VERTEX_COUNTS: [u32; 3] = [1_000, 10_000, 100_000];
+const JOINT_COUNTS: [u32; 2] = [8, 64];
+const RING_SEGMENTS: u32 = 20;
+const HEIGHT: f32 = 4.0;
+
+/// One group per CPU path, with an input for each vertex and joint count.
+fn skinned_aabb(c: &mut Criterion) {
+    // `SkinnedMeshCpuData` splits larger meshes across the pool the plugin's app would set up.
+    ComputeTaskPool::init(TaskPool::default);
+    let inputs: Vec<_> = VERTEX_COUNTS
+        .into_iter()
+        .flat_map(|vertices| JOINT_COUNTS.map(|joints| (vertices, joints)))
+        .map(|(vertices, joints)| {
+            let (mesh, inverse_bindposes) = tube_mesh(vertices, joints);
+            let joint_matrices = bent_joints(&inverse_bindposes);
+            let id = BenchmarkId::from_parameter(format!("{vertices} vertices, {joints} joints"));
+            (id, mesh, inverse_bindposes, joint_matrices)
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("skin + compute_aabb");
+    for (id, mesh, _, joint_matrices) in &inputs {
+        group.throughput(Throughput::Elements(mesh.count_vertices() as u64));
+        group.bench_with_input(id.clone(), joint_matrices, |b, joint_matrices| {
+            b.iter(|| compute_aabb(&skin_vertices(black_box(mesh), joint_matrices).unwrap()))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("SkinnedMeshCpuData::skinned_aabb");
+    for (id, mesh, _, joint_matrices) in &inputs {
+        let cpu_data = SkinnedMeshCpuData::new(mesh).unwrap();
+        group.throughput(Throughput::Elements(mesh.count_vertices() as u64));
+        group.bench_with_input(id.clone(), joint_matrices, |b, joint_matrices| {
+            b.iter(|| black_box(&cpu_data).skinned_aabb(joint_matrices))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("JointAabbs");
+    for (id, mesh, inverse_bindposes, joint_matrices) in &inputs {
+        let joint_aabbs = JointAabbs::new(mesh, inverse_bindposes).unwrap();
+        group.throughput(Throughput::Elements(mesh.count_vertices() as u64));
+        group.bench_with_input(id.clone(), joint_matrices, |b, joint_matrices| {
+            b.iter(|| compute_aabb(&black_box(&joint_aabbs).skinned_corners(joint_matrices)))
+        });
+    }
+    group.finish();
+
+    let mut group = c.benchmark_group("compute_aabb");
+    for (id, mesh, _, joint_matrices) in &inputs {
+        let positions = skin_vertices(mesh, joint_matrices).unwrap();
+        group.throughput(Throughput::Elements(mesh.count_vertices() as u64));
+        group.bench_with_input(id.clone(), &positions, |b, positions| {
+            b.iter(|| compute_aabb(black_box(positions)))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, skinned_aabb);
+criterion_main!(benches);
+
+/// A tube of about `vertices` vertices standing on the origin, with a chain of `joints` joints
+/// running up it and each vertex weighted between the two joints around it, as in the `stress`
+/// example. Returns the inverse bindposes of the chain along with the mesh.
+fn tube_mesh(vertices: u32, joints: u32) -> (Mesh, Vec<Mat4>) {
+    let rings = (vertices / RING_SEGMENTS).max(2);
+    let joint_spacing = HEIGHT / (joints - 1) as f32;
+    let mut positions = Vec::new();
+    let mut joint_indices = Vec::new();
+    let mut joint_weights = Vec::new();
+    for ring in 0..rings {
+        let y = HEIGHT * ring as f32 / (rings - 1) as f32;
+        let joint = ((y / joint_spacing) as u16).min(joints as u16 - 2);
+        let blend = (y / joint_spacing - joint as f32).clamp(0.0, 1.0);
+        for segment in 0..RING_SEGMENTS {
+            let angle = TAU * segment as f32 / RING_SEGMENTS as f32;
+            positions.push([0.2 * angle.cos(), y, 0.2 * angle.sin()]);
+            joint_indices.push([joint, joint + 1, 0, 0]);
+            joint_weights.push([1.0 - blend, blend, 0.0, 0.0]);
+        }
+    }
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, joint_indices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, joint_weights);
+    let inverse_bindposes = (0..joints)
+        .map(|i| Mat4::from_translation(Vec3::new(0.0, -(i as f32) * joint_spacing, 0.0)))
+        .collect();
+    (mesh, inverse_bindposes)
+}
+
+/// The joint matrices of the chain with every joint bent a little, premultiplied by
+/// `inverse_bindposes` as [`build_joint_matrices`](bevy_compute_skinned_aabb::build_joint_matrices)
+/// does.
+fn bent_joints(inverse_bindposes: &[Mat4]) -> Vec<Mat4> {
+    let joint_spacing = HEIGHT / (inverse_bindposes.len() - 1) as f32;
+    let mut joint = Mat4::IDENTITY;
+    inverse_bindposes
+        .iter()
+        .enumerate()
+        .map(|(i, inverse_bindpose)| {
+            let offset = if i == 0 { 0.0 } else { joint_spacing };
+            joint = joint
+                * Mat4::from_translation(Vec3::Y * offset)
+                * Mat4::from_rotation_z(0.1 * (i as f32 * 0.7).sin());
+            joint * *inverse_bindpose
+        })
+        .collect()
+}
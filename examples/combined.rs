@@ -0,0 +1,175 @@
+//! A character made of two mesh primitives sharing one skeleton, as glTF exporters split them,
+//! with a single wireframe box around both from the `CombinedSkinnedAabb` on their root.
+//!
+//! Press space to hide the second primitive, the combined box then only fits the first one.
+
+use std::f32::consts::PI;
+
+use bevy::{
+    pbr::wireframe::{Wireframe, WireframePlugin},
+    prelude::*,
+    render::mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Indices, PrimitiveTopology,
+    },
+};
+use bevy_compute_skinned_aabb::{CombinedSkinnedAabb, ComputeSkinnedAabbPlugin};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(WireframePlugin)
+        .add_plugin(ComputeSkinnedAabbPlugin)
+        .add_startup_system(setup)
+        .add_system(joint_animation)
+        .add_system(toggle_second_primitive)
+        .add_system(update_combined_cube)
+        .run();
+}
+
+/// Used to mark a joint to be animated in the [`joint_animation`] system.
+#[derive(Component)]
+struct AnimatedJoint;
+
+/// The primitive hidden by pressing space.
+#[derive(Component)]
+struct SecondPrimitive;
+
+/// Wireframe cube drawing the [`CombinedSkinnedAabb`] of the character.
+#[derive(Component)]
+struct CombinedCube;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut skinned_mesh_inverse_bindposes_assets: ResMut<Assets<SkinnedMeshInverseBindposes>>,
+) {
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(0.8, 1.5, 6.0).looking_at(Vec3::new(0.8, 1.0, 0.0), Vec3::Y),
+        ..default()
+    });
+    commands.spawn_bundle(DirectionalLightBundle {
+        transform: Transform::from_xyz(1.0, 2.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    let inverse_bindposes =
+        skinned_mesh_inverse_bindposes_assets.add(SkinnedMeshInverseBindposes::from(vec![
+            Mat4::from_translation(Vec3::new(-0.5, -1.0, 0.0)),
+            Mat4::from_translation(Vec3::new(-0.5, -1.0, 0.0)),
+        ]));
+    let material = materials.add(Color::rgb(0.5, 0.5, 0.5).into());
+
+    // Both primitives are skinned with the same two joints.
+    let joint_0 = commands
+        .spawn_bundle((
+            Transform::from_xyz(0.5, 1.0, 0.0),
+            GlobalTransform::identity(),
+        ))
+        .id();
+    let joint_1 = commands
+        .spawn_bundle((
+            AnimatedJoint,
+            Transform::identity(),
+            GlobalTransform::identity(),
+        ))
+        .id();
+    commands.entity(joint_0).push_children(&[joint_1]);
+    let skinned_mesh = SkinnedMesh {
+        inverse_bindposes,
+        joints: vec![joint_0, joint_1],
+    };
+
+    commands
+        .spawn_bundle(SpatialBundle::default())
+        .insert(CombinedSkinnedAabb::new(false))
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(PbrBundle {
+                    mesh: meshes.add(strip_mesh(0.0)),
+                    material: material.clone(),
+                    ..default()
+                })
+                .insert(skinned_mesh.clone());
+            parent
+                .spawn_bundle(PbrBundle {
+                    mesh: meshes.add(strip_mesh(1.2)),
+                    material,
+                    ..default()
+                })
+                .insert(skinned_mesh)
+                .insert(SecondPrimitive);
+        });
+
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
+            material: materials.add(Color::rgba(0.0, 0.0, 0.0, 0.0).into()),
+            ..default()
+        })
+        .insert(Wireframe)
+        .insert(CombinedCube);
+}
+
+/// A vertical strip from `x` to `x + 1`, skinned like the mesh of
+/// `models/SimpleSkin/SimpleSkin.gltf`: its top half follows the second joint.
+fn strip_mesh(x: f32) -> Mesh {
+    let positions: Vec<[f32; 3]> = (0..10)
+        .map(|i| [x + (i % 2) as f32, (i / 2) as f32 * 0.5, 0.0])
+        .collect();
+    let weights: Vec<[f32; 4]> = (0..10)
+        .map(|i| {
+            let weight = ((i / 2) as f32 * 0.25).clamp(0.0, 1.0);
+            [1.0 - weight, weight, 0.0, 0.0]
+        })
+        .collect();
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 10]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; 10]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vec![[0u16, 1, 0, 0]; 10]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, weights);
+    mesh.set_indices(Some(Indices::U16(vec![
+        0, 1, 3, 0, 3, 2, 2, 3, 5, 2, 5, 4, 4, 5, 7, 4, 7, 6, 6, 7, 9, 6, 9, 8,
+    ])));
+    mesh
+}
+
+/// Animate the joints marked with [`AnimatedJoint`] component.
+fn joint_animation(time: Res<Time>, mut query: Query<&mut Transform, With<AnimatedJoint>>) {
+    for mut transform in &mut query {
+        transform.rotation = Quat::from_axis_angle(
+            Vec3::Z,
+            0.5 * PI * time.time_since_startup().as_secs_f32().sin(),
+        );
+    }
+}
+
+/// Show or hide the [`SecondPrimitive`] when space is pressed.
+fn toggle_second_primitive(
+    keys: Res<Input<KeyCode>>,
+    mut query: Query<&mut Visibility, With<SecondPrimitive>>,
+) {
+    if keys.just_pressed(KeyCode::Space) {
+        for mut visibility in &mut query {
+            visibility.is_visible = !visibility.is_visible;
+        }
+    }
+}
+
+/// Fit the wireframe cube to the combined bounds, in world space.
+fn update_combined_cube(
+    characters: Query<&CombinedSkinnedAabb, Changed<CombinedSkinnedAabb>>,
+    mut cubes: Query<&mut Transform, With<CombinedCube>>,
+) {
+    for aabb in characters
+        .iter()
+        .filter_map(|combined| combined.world.as_ref())
+    {
+        for mut transform in &mut cubes {
+            transform.translation = aabb.center.into();
+            transform.scale = (aabb.half_extents * 2.0).into();
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Loads an animated glTF character and draws the bounds of each of its skinned meshes.
+//!
+//! The scene is spawned asynchronously, so its skinned meshes only show up a few frames in,
+//! and their mesh assets may load later still. `ComputeSkinnedAabbPlugin` picks them up
+//! whenever they appear, nothing has to be inserted on them by hand.
+//!
+//! Run with `cargo run --example gltf -- path/to/character.glb`, the path is relative to the
+//! `assets` folder. Without it, loads `models/animated/Fox.glb` from bevy's assets.
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{
+    ComputeSkinnedAabbPlugin, SkinnedAabbDebugConfig, SkinnedAabbDebugPlugin,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ComputeSkinnedAabbPlugin)
+        .add_plugin(SkinnedAabbDebugPlugin)
+        .insert_resource(SkinnedAabbDebugConfig {
+            enabled: true,
+            show_aabb: true,
+            depth_test: true,
+            ..default()
+        })
+        .add_startup_system(setup)
+        .add_system(play_animation)
+        .run();
+}
+
+/// The first animation of the loaded file.
+struct Animation(Handle<AnimationClip>);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let path = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| "models/animated/Fox.glb".to_string());
+
+    commands.insert_resource(Animation(asset_server.load(&format!("{path}#Animation0"))));
+    commands.spawn_bundle(SceneBundle {
+        scene: asset_server.load(&format!("{path}#Scene0")),
+        ..default()
+    });
+
+    // The fox is about 100 units long, the camera is pulled back to fit it.
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(100.0, 100.0, 150.0)
+            .looking_at(Vec3::new(0.0, 20.0, 0.0), Vec3::Y),
+        ..default()
+    });
+    commands.spawn_bundle(DirectionalLightBundle {
+        transform: Transform::from_rotation(Quat::from_euler(EulerRot::ZYX, 0.0, 1.0, -0.8)),
+        ..default()
+    });
+}
+
+/// Loop the animation on every player spawned by the scene.
+fn play_animation(
+    animation: Res<Animation>,
+    mut players: Query<&mut AnimationPlayer, Added<AnimationPlayer>>,
+) {
+    for mut player in &mut players {
+        player.play(animation.0.clone_weak()).repeat();
+    }
+}
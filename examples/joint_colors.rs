@@ -0,0 +1,180 @@
+//! A skinned tube colored by `SkinnedAabbDebugConfig::joint_colors`, to check which joints its
+//! vertices follow.
+//!
+//! The tube starts colored by the joint with the most weight on each vertex. Press `J` and `K` to
+//! inspect the weights of the previous or next joint as a grayscale heatmap instead, and space
+//! to switch back to the dominant joints, or to turn the colors off.
+
+use std::f32::consts::{PI, TAU};
+
+use bevy::{
+    prelude::*,
+    render::mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Indices, PrimitiveTopology,
+    },
+};
+use bevy_compute_skinned_aabb::{
+    ComputeSkinnedAabbPlugin, JointColors, SkinnedAabbDebugConfig, SkinnedAabbDebugPlugin,
+};
+
+/// Number of vertex rings along the tube.
+const VERTEX_RINGS: u32 = 40;
+/// Number of vertices around each ring.
+const RING_SEGMENTS: u32 = 24;
+/// Number of joints in the chain running up the tube.
+const JOINTS: u32 = 6;
+const HEIGHT: f32 = 4.0;
+const RADIUS: f32 = 0.3;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ComputeSkinnedAabbPlugin)
+        .add_plugin(SkinnedAabbDebugPlugin)
+        .insert_resource(SkinnedAabbDebugConfig {
+            enabled: true,
+            joint_colors: JointColors::Dominant,
+            ..default()
+        })
+        .add_startup_system(setup)
+        .add_system(joint_animation)
+        .add_system(select_joint)
+        .run();
+}
+
+/// A joint of the chain, bending by its own phase offset.
+#[derive(Component)]
+struct AnimatedJoint {
+    phase: f32,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut skinned_mesh_inverse_bindposes_assets: ResMut<Assets<SkinnedMeshInverseBindposes>>,
+) {
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, 2.0, 8.0).looking_at(Vec3::new(0.0, 2.0, 0.0), Vec3::Y),
+        ..default()
+    });
+    commands.spawn_bundle(PointLightBundle {
+        transform: Transform::from_xyz(4.0, 6.0, 6.0),
+        ..default()
+    });
+
+    let joint_spacing = HEIGHT / (JOINTS - 1) as f32;
+    let inverse_bindposes =
+        skinned_mesh_inverse_bindposes_assets.add(SkinnedMeshInverseBindposes::from(
+            (0..JOINTS)
+                .map(|i| Mat4::from_translation(Vec3::new(0.0, -(i as f32) * joint_spacing, 0.0)))
+                .collect::<Vec<_>>(),
+        ));
+
+    // Each joint is a child of the previous one, `joint_spacing` above it.
+    let mut joints = Vec::new();
+    for i in 0..JOINTS {
+        let joint = commands
+            .spawn_bundle((
+                AnimatedJoint {
+                    phase: i as f32 * 0.7,
+                },
+                Transform::from_xyz(0.0, if i == 0 { 0.0 } else { joint_spacing }, 0.0),
+                GlobalTransform::identity(),
+            ))
+            .id();
+        if let Some(parent) = joints.last() {
+            commands.entity(*parent).push_children(&[joint]);
+        }
+        joints.push(joint);
+    }
+
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(tube_mesh(joint_spacing)),
+            material: materials.add(Color::WHITE.into()),
+            ..default()
+        })
+        .insert(SkinnedMesh {
+            inverse_bindposes,
+            joints,
+        });
+}
+
+/// A tube standing on the origin, each vertex weighted between the two joints around it.
+fn tube_mesh(joint_spacing: f32) -> Mesh {
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut joint_indices = Vec::new();
+    let mut joint_weights = Vec::new();
+    for ring in 0..VERTEX_RINGS {
+        let y = HEIGHT * ring as f32 / (VERTEX_RINGS - 1) as f32;
+        let joint = ((y / joint_spacing) as u16).min(JOINTS as u16 - 2);
+        let blend = (y / joint_spacing - joint as f32).clamp(0.0, 1.0);
+        for segment in 0..RING_SEGMENTS {
+            let angle = TAU * segment as f32 / RING_SEGMENTS as f32;
+            let normal = Vec3::new(angle.cos(), 0.0, angle.sin());
+            positions.push((normal * RADIUS + Vec3::Y * y).to_array());
+            normals.push(normal.to_array());
+            joint_indices.push([joint, joint + 1, 0, 0]);
+            joint_weights.push([1.0 - blend, blend, 0.0, 0.0]);
+        }
+    }
+
+    let mut indices = Vec::new();
+    for ring in 0..VERTEX_RINGS - 1 {
+        for segment in 0..RING_SEGMENTS {
+            let next = (segment + 1) % RING_SEGMENTS;
+            let a = ring * RING_SEGMENTS + segment;
+            let b = ring * RING_SEGMENTS + next;
+            let c = a + RING_SEGMENTS;
+            let d = b + RING_SEGMENTS;
+            indices.extend([a, c, b, b, c, d]);
+        }
+    }
+
+    let vertex_count = positions.len();
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; vertex_count]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, joint_indices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, joint_weights);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+/// Bend every joint back and forth, each a bit out of phase with its parent.
+fn joint_animation(time: Res<Time>, mut query: Query<(&mut Transform, &AnimatedJoint)>) {
+    let t = time.time_since_startup().as_secs_f32();
+    for (mut transform, joint) in &mut query {
+        transform.rotation = Quat::from_axis_angle(Vec3::Z, 0.15 * PI * (t + joint.phase).sin());
+    }
+}
+
+/// Cycle the inspected joint with `J` and `K`, and switch between the dominant joints and no
+/// colors with space.
+fn select_joint(keys: Res<Input<KeyCode>>, mut config: ResMut<SkinnedAabbDebugConfig>) {
+    let joint_colors = match config.joint_colors {
+        _ if keys.just_pressed(KeyCode::Space) => {
+            if config.joint_colors == JointColors::Dominant {
+                JointColors::Off
+            } else {
+                JointColors::Dominant
+            }
+        }
+        JointColors::Influence(joint) if keys.just_pressed(KeyCode::J) => {
+            JointColors::Influence((joint + JOINTS - 1) % JOINTS)
+        }
+        JointColors::Influence(joint) if keys.just_pressed(KeyCode::K) => {
+            JointColors::Influence((joint + 1) % JOINTS)
+        }
+        _ if keys.just_pressed(KeyCode::J) || keys.just_pressed(KeyCode::K) => {
+            JointColors::Influence(0)
+        }
+        _ => return,
+    };
+    info!("{:?}", joint_colors);
+    config.joint_colors = joint_colors;
+}
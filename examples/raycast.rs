@@ -0,0 +1,192 @@
+//! Raycasts from the cursor against the animated triangles of a skinned mesh, read from the
+//! `SkinnedVertexPositions` stored for it, and puts a sphere on the closest hit.
+
+use std::f32::consts::PI;
+
+use bevy::{
+    math::Vec3A,
+    prelude::*,
+    render::mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Indices, PrimitiveTopology,
+    },
+};
+use bevy_compute_skinned_aabb::{
+    ComputeSkinnedAabbPlugin, SkinnedVertexPositions, StoreSkinnedPositions,
+};
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ComputeSkinnedAabbPlugin)
+        .add_startup_system(setup)
+        .add_system(joint_animation)
+        .add_system(raycast_cursor)
+        .run();
+}
+
+/// Used to mark a joint to be animated in the [`joint_animation`] system.
+#[derive(Component)]
+struct AnimatedJoint;
+
+/// The sphere put on the point under the cursor.
+#[derive(Component)]
+struct HitMarker;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut skinned_mesh_inverse_bindposes_assets: ResMut<Assets<SkinnedMeshInverseBindposes>>,
+) {
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(0.5, 1.5, 5.0).looking_at(Vec3::new(0.5, 1.0, 0.0), Vec3::Y),
+        ..default()
+    });
+    commands.spawn_bundle(DirectionalLightBundle {
+        transform: Transform::from_xyz(1.0, 2.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    let inverse_bindposes =
+        skinned_mesh_inverse_bindposes_assets.add(SkinnedMeshInverseBindposes::from(vec![
+            Mat4::from_translation(Vec3::new(-0.5, -1.0, 0.0)),
+            Mat4::from_translation(Vec3::new(-0.5, -1.0, 0.0)),
+        ]));
+    let joint_0 = commands
+        .spawn_bundle((
+            Transform::from_xyz(0.5, 1.0, 0.0),
+            GlobalTransform::identity(),
+        ))
+        .id();
+    let joint_1 = commands
+        .spawn_bundle((
+            AnimatedJoint,
+            Transform::identity(),
+            GlobalTransform::identity(),
+        ))
+        .id();
+    commands.entity(joint_0).push_children(&[joint_1]);
+
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(strip_mesh()),
+            material: materials.add(Color::rgb(0.5, 0.5, 0.5).into()),
+            ..default()
+        })
+        .insert(SkinnedMesh {
+            inverse_bindposes,
+            joints: vec![joint_0, joint_1],
+        })
+        .insert(StoreSkinnedPositions);
+
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh: meshes.add(Mesh::from(shape::UVSphere {
+                radius: 0.05,
+                ..default()
+            })),
+            material: materials.add(StandardMaterial {
+                base_color: Color::RED,
+                unlit: true,
+                ..default()
+            }),
+            visibility: Visibility { is_visible: false },
+            ..default()
+        })
+        .insert(HitMarker);
+}
+
+/// A vertical strip skinned like the mesh of `models/SimpleSkin/SimpleSkin.gltf`:
+/// its top half follows the second joint.
+fn strip_mesh() -> Mesh {
+    let positions: Vec<[f32; 3]> = (0..10)
+        .map(|i| [(i % 2) as f32, (i / 2) as f32 * 0.5, 0.0])
+        .collect();
+    let weights: Vec<[f32; 4]> = (0..10)
+        .map(|i| {
+            let weight = ((i / 2) as f32 * 0.25).clamp(0.0, 1.0);
+            [1.0 - weight, weight, 0.0, 0.0]
+        })
+        .collect();
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 10]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; 10]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vec![[0u16, 1, 0, 0]; 10]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, weights);
+    mesh.set_indices(Some(Indices::U16(vec![
+        0, 1, 3, 0, 3, 2, 2, 3, 5, 2, 5, 4, 4, 5, 7, 4, 7, 6, 6, 7, 9, 6, 9, 8,
+    ])));
+    mesh
+}
+
+/// Animate the joints marked with [`AnimatedJoint`] component.
+fn joint_animation(time: Res<Time>, mut query: Query<&mut Transform, With<AnimatedJoint>>) {
+    for mut transform in &mut query {
+        transform.rotation = Quat::from_axis_angle(
+            Vec3::Z,
+            0.5 * PI * time.time_since_startup().as_secs_f32().sin(),
+        );
+    }
+}
+
+/// Cast a ray from the camera through the cursor against the skinned triangles of every mesh,
+/// and move the [`HitMarker`] to the closest hit.
+fn raycast_cursor(
+    windows: Res<Windows>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    skinned: Query<(&Handle<Mesh>, &SkinnedVertexPositions)>,
+    meshes: Res<Assets<Mesh>>,
+    mut markers: Query<(&mut Transform, &mut Visibility), With<HitMarker>>,
+) {
+    let cursor = windows.get_primary().and_then(|window| {
+        let size = Vec2::new(window.width(), window.height());
+        Some(window.cursor_position()? / size * 2.0 - Vec2::ONE)
+    });
+    let (camera, camera_transform) = cameras.single();
+    let hit = cursor.and_then(|ndc| {
+        // The near plane is at a depth of 1 with bevy's reversed depth.
+        let ndc_to_world = camera_transform.compute_matrix() * camera.projection_matrix().inverse();
+        let near = ndc_to_world.project_point3(ndc.extend(1.0));
+        let far = ndc_to_world.project_point3(ndc.extend(0.5));
+        let (origin, direction) = (Vec3A::from(near), Vec3A::from((far - near).normalize()));
+        skinned
+            .iter()
+            .filter_map(|(mesh_h, positions)| {
+                let indices = meshes.get(mesh_h)?.indices()?;
+                positions
+                    .triangles(indices)
+                    .filter_map(|triangle| ray_triangle(origin, direction, triangle))
+                    .reduce(f32::min)
+            })
+            .reduce(f32::min)
+            .map(|distance| origin + direction * distance)
+    });
+    for (mut transform, mut visibility) in &mut markers {
+        visibility.is_visible = hit.is_some();
+        if let Some(hit) = hit {
+            transform.translation = hit.into();
+        }
+    }
+}
+
+/// How far along the ray from `origin` towards `direction` it hits `triangle`, from either side,
+/// with the Möller-Trumbore algorithm.
+fn ray_triangle(origin: Vec3A, direction: Vec3A, [a, b, c]: [Vec3A; 3]) -> Option<f32> {
+    let (edge_1, edge_2) = (b - a, c - a);
+    let p = direction.cross(edge_2);
+    let determinant = edge_1.dot(p);
+    if determinant.abs() < f32::EPSILON {
+        return None;
+    }
+    let to_origin = origin - a;
+    let u = to_origin.dot(p) / determinant;
+    let q = to_origin.cross(edge_1);
+    let v = direction.dot(q) / determinant;
+    if u < 0.0 || v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let distance = edge_2.dot(q) / determinant;
+    (distance > 0.0).then_some(distance)
+}
@@ -0,0 +1,377 @@
+//! Two copies of the same animated skinned mesh, seen through a narrow "culling" camera (left)
+//! and from an overview camera that also shows the culling camera's frustum (right).
+//!
+//! The left copy keeps bevy's stock bind pose [`Aabb`], the right copy is managed by
+//! [`ComputeSkinnedAabbPlugin`]. Both bend into the culling frustum while their bind pose
+//! boxes stay outside of it: only the right copy shows up in the culling camera.
+
+use std::f32::consts::PI;
+
+use bevy::{
+    core_pipeline::clear_color::ClearColorConfig,
+    pbr::wireframe::{Wireframe, WireframePlugin},
+    prelude::*,
+    render::{
+        camera::{Projection, Viewport},
+        mesh::{
+            skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+            Indices, PrimitiveTopology,
+        },
+        primitives::Aabb,
+        view::RenderLayers,
+    },
+    window::{WindowId, WindowResized},
+};
+use bevy_compute_skinned_aabb::{ComputeSkinnedAabbPlugin, NoSkinnedAabb};
+
+/// Distance from the culling camera to the far side of its visualized frustum.
+const FRUSTUM_DEPTH: f32 = 12.0;
+
+/// Render layer only the overview camera sees, so the frustum lines don't get in the way of
+/// the culling camera's own view.
+const OVERVIEW_LAYER: u8 = 1;
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(WireframePlugin)
+        .add_plugin(ComputeSkinnedAabbPlugin)
+        .add_startup_system(setup)
+        .add_system(joint_animation)
+        .add_system(set_camera_viewports)
+        .add_system(update_frustum_lines)
+        .add_system(update_aabb_cubes)
+        .run();
+}
+
+/// Used to mark a joint to be animated in the [`joint_animation`] system.
+#[derive(Component)]
+struct AnimatedJoint;
+
+/// The narrow camera whose frustum is used to demonstrate culling.
+#[derive(Component)]
+struct CullingCamera;
+
+/// The wide camera looking at the whole scene from above.
+#[derive(Component)]
+struct OverviewCamera;
+
+/// Line mesh drawing the frustum of its parent [`CullingCamera`].
+#[derive(Component)]
+struct FrustumLines;
+
+/// Wireframe cube drawing the current [`Aabb`] of its parent skinned mesh.
+#[derive(Component)]
+struct AabbCube;
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut skinned_mesh_inverse_bindposes_assets: ResMut<Assets<SkinnedMeshInverseBindposes>>,
+) {
+    // The culling camera sees the gap between the two copies, but neither of their bind poses.
+    commands
+        .spawn_bundle(Camera3dBundle {
+            transform: Transform::from_xyz(0.0, 1.0, 8.0)
+                .looking_at(Vec3::new(0.0, 1.0, 0.0), Vec3::Y),
+            projection: Projection::Perspective(PerspectiveProjection {
+                fov: 16f32.to_radians(),
+                ..default()
+            }),
+            ..default()
+        })
+        .insert(CullingCamera)
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(PbrBundle {
+                    mesh: meshes.add(Mesh::new(PrimitiveTopology::LineList)),
+                    material: materials.add(StandardMaterial {
+                        base_color: Color::YELLOW,
+                        unlit: true,
+                        ..default()
+                    }),
+                    ..default()
+                })
+                .insert(RenderLayers::layer(OVERVIEW_LAYER))
+                .insert(FrustumLines);
+        });
+
+    commands
+        .spawn_bundle(Camera3dBundle {
+            transform: Transform::from_xyz(-6.0, 9.0, 14.0)
+                .looking_at(Vec3::new(0.0, 0.0, 3.0), Vec3::Y),
+            camera: Camera {
+                priority: 1,
+                ..default()
+            },
+            camera_3d: Camera3d {
+                // The culling camera already cleared the window.
+                clear_color: ClearColorConfig::None,
+                ..default()
+            },
+            ..default()
+        })
+        .insert(RenderLayers::default().with(OVERVIEW_LAYER))
+        .insert(OverviewCamera);
+
+    commands.spawn_bundle(DirectionalLightBundle {
+        transform: Transform::from_xyz(1.0, 2.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    let mesh = meshes.add(simple_skin_mesh());
+    let inverse_bindposes =
+        skinned_mesh_inverse_bindposes_assets.add(SkinnedMeshInverseBindposes::from(vec![
+            Mat4::from_translation(Vec3::new(-0.5, -1.0, 0.0)),
+            Mat4::from_translation(Vec3::new(-0.5, -1.0, 0.0)),
+        ]));
+    let mesh_material = materials.add(Color::rgb(0.5, 0.5, 0.5).into());
+    let cube_mesh = meshes.add(Mesh::from(shape::Cube { size: 1.0 }));
+    let cube_material = materials.add(Color::rgba(0.0, 0.0, 0.0, 0.0).into());
+
+    // Left copy: stock bevy bounds.
+    // Rotated half a turn so both copies bend toward the gap between them at the same time.
+    let left = spawn_simple_skin(
+        &mut commands,
+        Transform::from_xyz(-1.2, 0.0, 0.0).with_rotation(Quat::from_rotation_y(PI)),
+        mesh.clone(),
+        mesh_material.clone(),
+        inverse_bindposes.clone(),
+        cube_mesh.clone(),
+        cube_material.clone(),
+    );
+    commands.entity(left).insert(NoSkinnedAabb);
+
+    // Right copy: bounds managed by the plugin.
+    spawn_simple_skin(
+        &mut commands,
+        Transform::from_xyz(1.2, 0.0, 0.0),
+        mesh,
+        mesh_material,
+        inverse_bindposes,
+        cube_mesh,
+        cube_material,
+    );
+}
+
+/// Spawn the SimpleSkin skeleton and mesh with its bind pose placed at `transform`,
+/// along with a wireframe cube child showing the mesh's [`Aabb`].
+fn spawn_simple_skin(
+    commands: &mut Commands,
+    transform: Transform,
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+    inverse_bindposes: Handle<SkinnedMeshInverseBindposes>,
+    cube_mesh: Handle<Mesh>,
+    cube_material: Handle<StandardMaterial>,
+) -> Entity {
+    // The root joint sits on the bind pose joint position, so the skinned mesh
+    // matches its bind pose (and the bind pose Aabb) when the animation is at rest.
+    let joint_0 = commands
+        .spawn_bundle((
+            transform * Transform::from_xyz(0.5, 1.0, 0.0),
+            GlobalTransform::identity(),
+        ))
+        .id();
+    let joint_1 = commands
+        .spawn_bundle((
+            AnimatedJoint,
+            Transform::identity(),
+            GlobalTransform::identity(),
+        ))
+        .id();
+    commands.entity(joint_0).push_children(&[joint_1]);
+
+    commands
+        .spawn_bundle(PbrBundle {
+            mesh,
+            material,
+            transform,
+            ..default()
+        })
+        .insert(SkinnedMesh {
+            inverse_bindposes,
+            joints: vec![joint_0, joint_1],
+        })
+        .with_children(|parent| {
+            parent
+                .spawn_bundle(PbrBundle {
+                    mesh: cube_mesh,
+                    material: cube_material,
+                    ..default()
+                })
+                .insert(Wireframe)
+                .insert(AabbCube);
+        })
+        .id()
+}
+
+/// The mesh from `models/SimpleSkin/SimpleSkin.gltf`, see the `main` example for details.
+fn simple_skin_mesh() -> Mesh {
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 0.5, 0.0],
+            [1.0, 0.5, 0.0],
+            [0.0, 1.0, 0.0],
+            [1.0, 1.0, 0.0],
+            [0.0, 1.5, 0.0],
+            [1.0, 1.5, 0.0],
+            [0.0, 2.0, 0.0],
+            [1.0, 2.0, 0.0],
+        ],
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 10]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; 10]);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_JOINT_INDEX,
+        vec![
+            [0u16, 0, 0, 0],
+            [0, 0, 0, 0],
+            [0, 1, 0, 0],
+            [0, 1, 0, 0],
+            [0, 1, 0, 0],
+            [0, 1, 0, 0],
+            [0, 1, 0, 0],
+            [0, 1, 0, 0],
+            [0, 1, 0, 0],
+            [0, 1, 0, 0],
+        ],
+    );
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_JOINT_WEIGHT,
+        vec![
+            [1.00, 0.00, 0.0, 0.0],
+            [1.00, 0.00, 0.0, 0.0],
+            [0.75, 0.25, 0.0, 0.0],
+            [0.75, 0.25, 0.0, 0.0],
+            [0.50, 0.50, 0.0, 0.0],
+            [0.50, 0.50, 0.0, 0.0],
+            [0.25, 0.75, 0.0, 0.0],
+            [0.25, 0.75, 0.0, 0.0],
+            [0.00, 1.00, 0.0, 0.0],
+            [0.00, 1.00, 0.0, 0.0],
+        ],
+    );
+    mesh.set_indices(Some(Indices::U16(vec![
+        0, 1, 3, 0, 3, 2, 2, 3, 5, 2, 5, 4, 4, 5, 7, 4, 7, 6, 6, 7, 9, 6, 9, 8,
+    ])));
+    mesh
+}
+
+/// Animate the joints marked with [`AnimatedJoint`] component.
+fn joint_animation(time: Res<Time>, mut query: Query<&mut Transform, With<AnimatedJoint>>) {
+    for mut transform in &mut query {
+        transform.rotation = Quat::from_axis_angle(
+            Vec3::Z,
+            0.5 * PI * time.time_since_startup().as_secs_f32().sin(),
+        );
+    }
+}
+
+/// Split the window between the culling camera (left) and overview camera (right).
+/// A resize event is sent when the window is first created, so this also does the initial setup.
+fn set_camera_viewports(
+    windows: Res<Windows>,
+    mut resize_events: EventReader<WindowResized>,
+    mut culling_camera: Query<&mut Camera, (With<CullingCamera>, Without<OverviewCamera>)>,
+    mut overview_camera: Query<&mut Camera, (With<OverviewCamera>, Without<CullingCamera>)>,
+) {
+    for resize_event in resize_events.iter() {
+        if resize_event.id != WindowId::primary() {
+            continue;
+        }
+        let window = windows.primary();
+        let half_size = UVec2::new(window.physical_width() / 2, window.physical_height());
+        culling_camera.single_mut().viewport = Some(Viewport {
+            physical_position: UVec2::ZERO,
+            physical_size: half_size,
+            ..default()
+        });
+        overview_camera.single_mut().viewport = Some(Viewport {
+            physical_position: UVec2::new(half_size.x, 0),
+            physical_size: half_size,
+            ..default()
+        });
+    }
+}
+
+/// Rebuild the frustum lines whenever the culling camera's projection changes,
+/// which also happens when its viewport aspect ratio changes.
+#[allow(clippy::type_complexity)]
+fn update_frustum_lines(
+    cameras: Query<(&Projection, &Children), (With<CullingCamera>, Changed<Projection>)>,
+    lines: Query<&Handle<Mesh>, With<FrustumLines>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (projection, children) in &cameras {
+        let projection = match projection {
+            Projection::Perspective(projection) => projection,
+            Projection::Orthographic(_) => continue,
+        };
+        for child in children {
+            if let Some(mesh) = lines.get(*child).ok().and_then(|h| meshes.get_mut(h)) {
+                *mesh = frustum_lines_mesh(projection, FRUSTUM_DEPTH);
+            }
+        }
+    }
+}
+
+/// Build a line list outlining a perspective frustum in camera space,
+/// from the near plane to `depth` along the view direction.
+fn frustum_lines_mesh(projection: &PerspectiveProjection, depth: f32) -> Mesh {
+    let corners = |distance: f32| {
+        let half_height = distance * (0.5 * projection.fov).tan();
+        let half_width = half_height * projection.aspect_ratio;
+        [
+            Vec3::new(-half_width, -half_height, -distance),
+            Vec3::new(half_width, -half_height, -distance),
+            Vec3::new(half_width, half_height, -distance),
+            Vec3::new(-half_width, half_height, -distance),
+        ]
+    };
+    let near = corners(projection.near);
+    let far = corners(depth);
+
+    let mut positions = Vec::new();
+    for i in 0..4 {
+        let next = (i + 1) % 4;
+        // near rectangle
+        positions.push(near[i].to_array());
+        positions.push(near[next].to_array());
+        // far rectangle
+        positions.push(far[i].to_array());
+        positions.push(far[next].to_array());
+        // edge connecting both
+        positions.push(near[i].to_array());
+        positions.push(far[i].to_array());
+    }
+
+    let vertex_count = positions.len();
+    let mut mesh = Mesh::new(PrimitiveTopology::LineList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    // Not used by the unlit material, but required by the pbr pipeline.
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; vertex_count]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; vertex_count]);
+    mesh
+}
+
+/// Fit each wireframe cube to the current [`Aabb`] of its parent skinned mesh.
+/// The cube is a child of the mesh, and the [`Aabb`] is in the mesh's local space.
+fn update_aabb_cubes(
+    skinned_meshes: Query<(&Aabb, &Children), With<SkinnedMesh>>,
+    mut cubes: Query<&mut Transform, With<AabbCube>>,
+) {
+    for (aabb, children) in &skinned_meshes {
+        for child in children {
+            if let Ok(mut transform) = cubes.get_mut(*child) {
+                transform.translation = aabb.center.into();
+                transform.scale = (aabb.half_extents * 2.0).into();
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! The SimpleSkin scene of the main example, loaded from its glTF file instead of built in code,
+//! to check the bounds with joint indices and weights as an exporter writes them.
+//!
+//! Copy `models/SimpleSkin/SimpleSkin.gltf` from bevy's `assets` folder into `assets/` first.
+//! Each time the bounds move, the `SkinnedAabb` written by the plugin is logged next to the
+//! bounds recomputed from scratch by `compute_skinned_aabb`, they should match.
+
+use bevy::{
+    prelude::*,
+    render::mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+};
+use bevy_compute_skinned_aabb::{
+    compute_skinned_aabb, ComputeSkinnedAabbPlugin, SkinnedAabb, SkinnedAabbDebugConfig,
+    SkinnedAabbDebugPlugin,
+};
+
+const PATH: &str = "models/SimpleSkin/SimpleSkin.gltf";
+
+fn main() {
+    App::new()
+        .add_plugins(DefaultPlugins)
+        .add_plugin(ComputeSkinnedAabbPlugin)
+        .add_plugin(SkinnedAabbDebugPlugin)
+        .insert_resource(SkinnedAabbDebugConfig {
+            enabled: true,
+            show_aabb: true,
+            depth_test: true,
+            ..default()
+        })
+        .add_startup_system(setup)
+        .add_system(play_animation)
+        .add_system(compare_bounds)
+        .run();
+}
+
+/// The animation bending the second joint of the scene.
+struct Animation(Handle<AnimationClip>);
+
+fn setup(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Animation(asset_server.load(&format!("{PATH}#Animation0"))));
+    commands.spawn_bundle(SceneBundle {
+        scene: asset_server.load(&format!("{PATH}#Scene0")),
+        ..default()
+    });
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(-2.0, 2.5, 5.0)
+            .looking_at(Vec3::new(0.5, 1.0, 0.0), Vec3::Y),
+        ..default()
+    });
+}
+
+/// Loop the animation once the scene spawns its player.
+fn play_animation(
+    animation: Res<Animation>,
+    mut players: Query<&mut AnimationPlayer, Added<AnimationPlayer>>,
+) {
+    for mut player in &mut players {
+        player.play(animation.0.clone_weak()).repeat();
+    }
+}
+
+/// Log the bounds written by the plugin next to the same bounds computed directly.
+fn compare_bounds(
+    query: Query<(Entity, &Handle<Mesh>, &SkinnedMesh, &SkinnedAabb), Changed<SkinnedAabb>>,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    joints: Query<&GlobalTransform>,
+) {
+    for (entity, mesh_h, skinned_mesh, skinned_aabb) in &query {
+        let computed = meshes
+            .get(mesh_h)
+            .and_then(|mesh| compute_skinned_aabb(mesh, skinned_mesh, &inverse_bindposes, &joints));
+        if let Some(computed) = computed {
+            info!(
+                "{:?}: plugin {} / {}, computed {} / {}",
+                entity,
+                skinned_aabb.world.center,
+                skinned_aabb.world.half_extents,
+                computed.center,
+                computed.half_extents
+            );
+        }
+    }
+}
@@ -0,0 +1,295 @@
+//! Hundreds of animated skinned tubes, each with its own skeleton, to see how the CPU bounds
+//! scale with the number of meshes, vertices and joints.
+//!
+//! Run with `cargo run --release --example stress -- [meshes] [vertices per mesh] [joints]`,
+//! e.g. `-- 1 60000 8` for a single dense tube. The frame rate and the time spent between the
+//! start and the end of the bounds systems, averaged over the last frames, are shown in the
+//! window title and logged to the console. Press `1`, `2` and `3` to switch every mesh to
+//! exact, sampled or per joint bounds, and `D` to toggle drawing them.
+
+use std::{
+    f32::consts::{PI, TAU},
+    time::Instant,
+};
+
+use bevy::{
+    diagnostic::{
+        Diagnostic, DiagnosticId, Diagnostics, FrameTimeDiagnosticsPlugin, LogDiagnosticsPlugin,
+    },
+    prelude::*,
+    render::mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Indices, PrimitiveTopology,
+    },
+    transform::TransformSystem,
+};
+use bevy_compute_skinned_aabb::{
+    AabbQuality, ComputeSkinnedAabbPlugin, SkinnedAabbConfig, SkinnedAabbDebugConfig,
+    SkinnedAabbDebugPlugin, SkinnedAabbSystems,
+};
+
+/// Number of vertices around each ring of a tube.
+const RING_SEGMENTS: u32 = 16;
+const HEIGHT: f32 = 4.0;
+const RADIUS: f32 = 0.2;
+/// Distance between neighbouring tubes of the grid.
+const SPACING: f32 = 1.0;
+
+/// Milliseconds from the start to the end of [`SkinnedAabbSystems::UpdateAabbs`].
+const AABB_TIME: DiagnosticId = DiagnosticId::from_u128(0x5b6f_92c1_3d4e_4a7b_8c0d_1e2f_3a4b_5c6d);
+
+/// What to spawn, from the command line.
+#[derive(Clone, Copy, Debug)]
+struct StressSettings {
+    meshes: u32,
+    vertices: u32,
+    joints: u32,
+}
+
+impl StressSettings {
+    fn from_args() -> Self {
+        let mut args = std::env::args().skip(1).map(|arg| {
+            arg.parse::<u32>()
+                .expect("arguments should be positive integers")
+        });
+        Self {
+            meshes: args.next().unwrap_or(400),
+            vertices: args.next().unwrap_or(2000),
+            joints: args.next().unwrap_or(8).max(2),
+        }
+    }
+
+    fn rings(&self) -> u32 {
+        (self.vertices / RING_SEGMENTS).max(2)
+    }
+}
+
+/// When the bounds systems of this frame started.
+#[derive(Default)]
+struct AabbTimer(Option<Instant>);
+
+fn main() {
+    App::new()
+        .insert_resource(StressSettings::from_args())
+        .add_plugins(DefaultPlugins)
+        .add_plugin(FrameTimeDiagnosticsPlugin)
+        .add_plugin(LogDiagnosticsPlugin::default())
+        .add_plugin(ComputeSkinnedAabbPlugin)
+        .add_plugin(SkinnedAabbDebugPlugin)
+        .insert_resource(SkinnedAabbDebugConfig {
+            enabled: true,
+            show_aabb: true,
+            depth_test: true,
+            ..default()
+        })
+        .init_resource::<AabbTimer>()
+        .add_startup_system(setup)
+        .add_system(joint_animation)
+        .add_system(switch_quality)
+        .add_system(show_timings)
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            start_aabb_timer
+                .after(TransformSystem::TransformPropagate)
+                .before(SkinnedAabbSystems::UpdateAabbs),
+        )
+        .add_system_to_stage(
+            CoreStage::PostUpdate,
+            stop_aabb_timer.after(SkinnedAabbSystems::UpdateAabbs),
+        )
+        .run();
+}
+
+/// A joint of a chain, bending by its own phase offset.
+#[derive(Component)]
+struct AnimatedJoint {
+    phase: f32,
+}
+
+fn setup(
+    mut commands: Commands,
+    settings: Res<StressSettings>,
+    mut diagnostics: ResMut<Diagnostics>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut skinned_mesh_inverse_bindposes_assets: ResMut<Assets<SkinnedMeshInverseBindposes>>,
+) {
+    diagnostics.add(Diagnostic::new(AABB_TIME, "skinned_aabb_ms", 20));
+    info!(
+        "{} meshes of {} vertices with {} joints",
+        settings.meshes,
+        settings.rings() * RING_SEGMENTS,
+        settings.joints
+    );
+
+    let columns = (settings.meshes as f32).sqrt().ceil().max(1.0) as u32;
+    let width = (columns - 1) as f32 * SPACING;
+    commands.spawn_bundle(Camera3dBundle {
+        transform: Transform::from_xyz(0.0, width * 0.6 + 4.0, width * 0.8 + 8.0)
+            .looking_at(Vec3::Y * HEIGHT * 0.5, Vec3::Y),
+        ..default()
+    });
+    commands.spawn_bundle(DirectionalLightBundle {
+        transform: Transform::from_xyz(1.0, 2.0, 3.0).looking_at(Vec3::ZERO, Vec3::Y),
+        ..default()
+    });
+
+    // Every tube shares its mesh and bindposes, only the skeletons are separate.
+    let joint_spacing = HEIGHT / (settings.joints - 1) as f32;
+    let inverse_bindposes =
+        skinned_mesh_inverse_bindposes_assets.add(SkinnedMeshInverseBindposes::from(
+            (0..settings.joints)
+                .map(|i| Mat4::from_translation(Vec3::new(0.0, -(i as f32) * joint_spacing, 0.0)))
+                .collect::<Vec<_>>(),
+        ));
+    let mesh = meshes.add(tube_mesh(&settings, joint_spacing));
+    let material = materials.add(Color::WHITE.into());
+
+    for i in 0..settings.meshes {
+        let offset = Vec3::new(
+            (i % columns) as f32 * SPACING - width * 0.5,
+            0.0,
+            (i / columns) as f32 * SPACING - width * 0.5,
+        );
+        // Each joint is a child of the previous one, `joint_spacing` above it.
+        let mut joints = Vec::new();
+        for j in 0..settings.joints {
+            let joint = commands
+                .spawn_bundle((
+                    AnimatedJoint {
+                        phase: j as f32 * 0.7 + i as f32 * 0.37,
+                    },
+                    Transform::from_translation(if j == 0 {
+                        offset
+                    } else {
+                        Vec3::Y * joint_spacing
+                    }),
+                    GlobalTransform::identity(),
+                ))
+                .id();
+            if let Some(parent) = joints.last() {
+                commands.entity(*parent).push_children(&[joint]);
+            }
+            joints.push(joint);
+        }
+        commands
+            .spawn_bundle(PbrBundle {
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(offset),
+                ..default()
+            })
+            .insert(SkinnedMesh {
+                inverse_bindposes: inverse_bindposes.clone(),
+                joints,
+            });
+    }
+}
+
+/// A tube standing on the origin, each vertex weighted between the two joints around it.
+fn tube_mesh(settings: &StressSettings, joint_spacing: f32) -> Mesh {
+    let rings = settings.rings();
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut joint_indices = Vec::new();
+    let mut joint_weights = Vec::new();
+    for ring in 0..rings {
+        let y = HEIGHT * ring as f32 / (rings - 1) as f32;
+        let joint = ((y / joint_spacing) as u16).min(settings.joints as u16 - 2);
+        let blend = (y / joint_spacing - joint as f32).clamp(0.0, 1.0);
+        for segment in 0..RING_SEGMENTS {
+            let angle = TAU * segment as f32 / RING_SEGMENTS as f32;
+            let normal = Vec3::new(angle.cos(), 0.0, angle.sin());
+            positions.push((normal * RADIUS + Vec3::Y * y).to_array());
+            normals.push(normal.to_array());
+            joint_indices.push([joint, joint + 1, 0, 0]);
+            joint_weights.push([1.0 - blend, blend, 0.0, 0.0]);
+        }
+    }
+
+    let mut indices = Vec::new();
+    for ring in 0..rings - 1 {
+        for segment in 0..RING_SEGMENTS {
+            let next = (segment + 1) % RING_SEGMENTS;
+            let a = ring * RING_SEGMENTS + segment;
+            let b = ring * RING_SEGMENTS + next;
+            let c = a + RING_SEGMENTS;
+            let d = b + RING_SEGMENTS;
+            indices.extend([a, c, b, b, c, d]);
+        }
+    }
+
+    let vertex_count = positions.len();
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, normals);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0]; vertex_count]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, joint_indices);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, joint_weights);
+    mesh.set_indices(Some(Indices::U32(indices)));
+    mesh
+}
+
+/// Bend every joint back and forth, each a bit out of phase with its parent.
+fn joint_animation(time: Res<Time>, mut query: Query<(&mut Transform, &AnimatedJoint)>) {
+    let t = time.time_since_startup().as_secs_f32();
+    for (mut transform, joint) in &mut query {
+        transform.rotation = Quat::from_axis_angle(Vec3::Z, 0.15 * PI * (t + joint.phase).sin());
+    }
+}
+
+/// Press `1`, `2` and `3` for exact, sampled and per joint bounds, and `D` to toggle drawing them.
+fn switch_quality(
+    keys: Res<Input<KeyCode>>,
+    mut config: ResMut<SkinnedAabbConfig>,
+    mut debug_config: ResMut<SkinnedAabbDebugConfig>,
+) {
+    let quality = if keys.just_pressed(KeyCode::Key1) {
+        AabbQuality::Exact
+    } else if keys.just_pressed(KeyCode::Key2) {
+        AabbQuality::Sampled {
+            stride: 8,
+            padding: 0.05,
+        }
+    } else if keys.just_pressed(KeyCode::Key3) {
+        AabbQuality::PerJoint
+    } else {
+        if keys.just_pressed(KeyCode::D) {
+            debug_config.enabled = !debug_config.enabled;
+        }
+        return;
+    };
+    info!("{:?}", quality);
+    config.quality = quality;
+}
+
+fn start_aabb_timer(mut timer: ResMut<AabbTimer>) {
+    timer.0 = Some(Instant::now());
+}
+
+/// Record the time since [`start_aabb_timer`]. Systems running in parallel with the bounds
+/// systems are counted too, so it's an upper bound.
+fn stop_aabb_timer(mut timer: ResMut<AabbTimer>, mut diagnostics: ResMut<Diagnostics>) {
+    if let Some(start) = timer.0.take() {
+        diagnostics.add_measurement(AABB_TIME, || start.elapsed().as_secs_f64() * 1000.0);
+    }
+}
+
+/// Show the averaged frame rate and bounds time in the window title.
+fn show_timings(
+    diagnostics: Res<Diagnostics>,
+    config: Res<SkinnedAabbConfig>,
+    mut windows: ResMut<Windows>,
+) {
+    let average = |id| diagnostics.get(id).and_then(Diagnostic::average);
+    if let (Some(window), Some(fps), Some(aabb_ms)) = (
+        windows.get_primary_mut(),
+        average(FrameTimeDiagnosticsPlugin::FPS),
+        average(AABB_TIME),
+    ) {
+        window.set_title(format!(
+            "stress: {:.0} fps, {:.2} ms in bounds systems, {:?}",
+            fps, aabb_ms, config.quality
+        ));
+    }
+}
@@ -0,0 +1,281 @@
+//! One fixed [`Aabb`] covering whole animation clips, baked by sampling the clips once instead of
+//! skinning the mesh every frame.
+
+use bevy::{
+    asset::LoadState,
+    prelude::*,
+    render::{
+        mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        primitives::Aabb,
+    },
+    utils::HashMap,
+};
+
+use crate::{
+    aabb_union, compute_aabb, write_skinned_aabb, NoSkinnedAabb, SkinnedAabbConfig,
+    SkinnedAabbFrameCount, SkinnedAabbOutput, SkinnedMeshCpuData,
+};
+
+/// Bound a skinned mesh once over every pose of `clips` instead of every frame.
+///
+/// Each clip is sampled `sample_rate` times per second, from its start to its end included, and
+/// the mesh is skinned at each sample with the joints posed by the clip. The union of those
+/// bounds and of the bind pose is written to the mesh's [`Aabb`] and [`SkinnedAabb`](crate::SkinnedAabb),
+/// then [`NoSkinnedAabb`] is inserted so the mesh isn't processed again; remove it to bake again.
+///
+/// The samples are spread over several frames, see [`SkinnedAabbConfig::animation_samples_per_frame`].
+/// Until the bake is done the bounds are updated every frame as usual. Clips are resolved the way
+/// [`AnimationPlayer`] resolves them, from the ancestor of the first joint named after the first
+/// part of each path. Curves targeting entities the joints don't inherit from are ignored, and
+/// clips that failed to load are skipped.
+#[derive(Component, Clone, Debug)]
+pub struct AnimationAabb {
+    pub clips: Vec<Handle<AnimationClip>>,
+    /// Samples per second of each clip.
+    pub sample_rate: f32,
+}
+
+impl AnimationAabb {
+    /// Bake `clips` sampled at 15 Hz.
+    pub fn new(clips: Vec<Handle<AnimationClip>>) -> Self {
+        Self {
+            clips,
+            sample_rate: 15.0,
+        }
+    }
+}
+
+/// How far the bake of an [`AnimationAabb`] got.
+#[derive(Component)]
+pub(crate) struct AnimationAabbProgress {
+    /// The clip being sampled.
+    clip: usize,
+    /// The next sample of that clip.
+    sample: usize,
+    /// The union of the bind pose and every sample so far.
+    aabb: Aabb,
+}
+
+/// The hierarchy queries needed to pose joints without touching their transforms.
+#[derive(bevy::ecs::system::SystemParam)]
+pub(crate) struct Hierarchy<'w, 's> {
+    transforms: Query<'w, 's, (&'static Transform, &'static GlobalTransform)>,
+    parents: Query<'w, 's, &'static Parent>,
+    children: Query<'w, 's, &'static Children>,
+    names: Query<'w, 's, &'static Name>,
+}
+
+impl<'w, 's> Hierarchy<'w, 's> {
+    /// The entity `path` leads to, starting from the ancestor of `from` (or `from` itself) named
+    /// after its first part, as [`AnimationPlayer`] does from its own entity.
+    fn resolve(&self, from: Entity, path: &EntityPath) -> Option<Entity> {
+        let (first, rest) = path.parts.split_first()?;
+        let mut current = std::iter::successors(Some(from), |entity| {
+            self.parents.get(*entity).ok().map(|parent| parent.get())
+        })
+        .find(|entity| self.names.get(*entity).is_ok_and(|name| name == first))?;
+        for part in rest {
+            current = *self
+                .children
+                .get(current)
+                .ok()?
+                .iter()
+                .find(|child| self.names.get(**child).is_ok_and(|name| name == part))?;
+        }
+        Some(current)
+    }
+
+    /// The global matrix of `entity` with the local transforms in `posed` replacing its own and
+    /// its ancestors'. Entities without a parent keep their [`GlobalTransform`] unless posed.
+    fn posed_global(
+        &self,
+        entity: Entity,
+        posed: &HashMap<Entity, Transform>,
+        globals: &mut HashMap<Entity, Mat4>,
+    ) -> Option<Mat4> {
+        if let Some(global) = globals.get(&entity) {
+            return Some(*global);
+        }
+        let (transform, global) = self.transforms.get(entity).ok()?;
+        let local = posed.get(&entity);
+        let global = match self.parents.get(entity) {
+            Ok(parent) => {
+                self.posed_global(parent.get(), posed, globals)?
+                    * local.unwrap_or(transform).compute_matrix()
+            }
+            Err(_) => local.map_or_else(|| global.compute_matrix(), Transform::compute_matrix),
+        };
+        globals.insert(entity, global);
+        Some(global)
+    }
+}
+
+/// Apply `curves` at `time` to `transform`, interpolating between keyframes like
+/// [`AnimationPlayer`]. Curves that haven't started or are over leave it as is.
+fn sample_curves(curves: &[VariableCurve], time: f32, transform: &mut Transform) {
+    for curve in curves {
+        let timestamps = &curve.keyframe_timestamps;
+        let (start, lerp) = match timestamps.binary_search_by(|probe| probe.total_cmp(&time)) {
+            Ok(i) => (i, 0.0),
+            Err(i) if i == 0 || i >= timestamps.len() => continue,
+            Err(i) => {
+                let start = i - 1;
+                let lerp = (time - timestamps[start]) / (timestamps[i] - timestamps[start]);
+                (start, lerp)
+            }
+        };
+        let end = (start + 1).min(timestamps.len() - 1);
+        match &curve.keyframes {
+            Keyframes::Rotation(keyframes) => {
+                let rot_start = keyframes[start];
+                let mut rot_end = keyframes[end];
+                if rot_end.dot(rot_start) < 0.0 {
+                    rot_end = -rot_end;
+                }
+                transform.rotation = rot_start.normalize().slerp(rot_end.normalize(), lerp);
+            }
+            Keyframes::Translation(keyframes) => {
+                transform.translation = keyframes[start].lerp(keyframes[end], lerp);
+            }
+            Keyframes::Scale(keyframes) => {
+                transform.scale = keyframes[start].lerp(keyframes[end], lerp);
+            }
+        }
+    }
+}
+
+/// Sample the clips of every [`AnimationAabb`] within the frame's budget, and write the bounds of
+/// the meshes whose bake is done.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn bake_animation_aabbs(
+    mut commands: Commands,
+    config: Res<SkinnedAabbConfig>,
+    frame_count: Res<SkinnedAabbFrameCount>,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    clips: Option<Res<Assets<AnimationClip>>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    hierarchy: Hierarchy,
+    mut query: Query<
+        (
+            Entity,
+            &SkinnedMesh,
+            &SkinnedMeshCpuData,
+            &AnimationAabb,
+            Option<&mut AnimationAabbProgress>,
+            SkinnedAabbOutput,
+        ),
+        Without<NoSkinnedAabb>,
+    >,
+    mut joints: Local<Vec<Mat4>>,
+) {
+    // Without bevy's `AnimationPlugin` there is nothing to bake.
+    let clips = match clips {
+        Some(clips) => clips,
+        None => return,
+    };
+    let mut budget = config.animation_samples_per_frame.max(1);
+    for (entity, skinned_mesh, cpu_data, animation_aabb, progress, output) in &mut query {
+        if budget == 0 {
+            break;
+        }
+        let mut progress = match progress {
+            Some(progress) => progress,
+            None => {
+                let positions: Vec<Vec3> =
+                    cpu_data.positions.iter().map(|p| Vec3::from(*p)).collect();
+                if let Some(aabb) = compute_aabb(&positions) {
+                    commands.entity(entity).insert(AnimationAabbProgress {
+                        clip: 0,
+                        sample: 0,
+                        aabb,
+                    });
+                }
+                continue;
+            }
+        };
+        let inverse_bindposes = match inverse_bindposes.get(&skinned_mesh.inverse_bindposes) {
+            Some(inverse_bindposes) => inverse_bindposes,
+            None => continue,
+        };
+        let first_joint = match skinned_mesh.joints.first() {
+            Some(joint) => *joint,
+            None => continue,
+        };
+
+        while budget > 0 && progress.clip < animation_aabb.clips.len() {
+            let handle = &animation_aabb.clips[progress.clip];
+            let clip = match clips.get(handle) {
+                Some(clip) => clip,
+                None if asset_server.get_load_state(handle) == LoadState::Failed => {
+                    progress.clip += 1;
+                    progress.sample = 0;
+                    continue;
+                }
+                None => break,
+            };
+            let rate = animation_aabb.sample_rate.max(f32::EPSILON);
+            let samples = (clip.duration() * rate).ceil() as usize + 1;
+            let time = (progress.sample as f32 / rate).min(clip.duration());
+
+            let mut posed = HashMap::new();
+            for (path, curves) in clip.curves() {
+                if let Some(target) = hierarchy.resolve(first_joint, path) {
+                    if let Ok((transform, _)) = hierarchy.transforms.get(target) {
+                        let transform = posed.entry(target).or_insert(*transform);
+                        sample_curves(curves, time, transform);
+                    }
+                }
+            }
+            // The joints are skinned into the mesh entity's local space, as the mesh entity
+            // can itself be in the animated hierarchy.
+            let mut globals = HashMap::new();
+            joints.clear();
+            let mesh_inverse = hierarchy
+                .posed_global(entity, &posed, &mut globals)
+                .map(|global| global.inverse());
+            for (inverse_bindpose, joint) in inverse_bindposes.iter().zip(&skinned_mesh.joints) {
+                match (
+                    mesh_inverse,
+                    hierarchy.posed_global(*joint, &posed, &mut globals),
+                ) {
+                    (Some(mesh_inverse), Some(global)) => {
+                        joints.push(mesh_inverse * global * *inverse_bindpose);
+                    }
+                    _ => break,
+                }
+            }
+            if joints.len() == skinned_mesh.joints.len().min(inverse_bindposes.len()) {
+                if let Some(aabb) =
+                    cpu_data.sampled_skinned_aabb(&joints, 1, config.parallel_chunk_size)
+                {
+                    progress.aabb = aabb_union(&progress.aabb, &aabb);
+                }
+            }
+
+            budget -= 1;
+            progress.sample += 1;
+            if progress.sample >= samples {
+                progress.clip += 1;
+                progress.sample = 0;
+            }
+        }
+
+        if progress.clip >= animation_aabb.clips.len() {
+            write_skinned_aabb(
+                &mut commands,
+                entity,
+                output,
+                progress.aabb.clone(),
+                None,
+                frame_count.0,
+                &config,
+                &time,
+            );
+            commands
+                .entity(entity)
+                .remove::<AnimationAabbProgress>()
+                .insert(NoSkinnedAabb);
+        }
+    }
+}
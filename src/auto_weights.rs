@@ -0,0 +1,90 @@
+//! Skinning weights generated from the joint positions, for meshes that come without any.
+
+use bevy::{prelude::*, render::mesh::VertexAttributeValues};
+
+/// Bind each vertex of `mesh` to the four joints nearest to it, weighted by inverse squared
+/// distance and normalized to sum to 1. `joint_positions` are the bind pose positions of the
+/// joints in the mesh's space, in the order of the mesh's [`SkinnedMesh::joints`].
+///
+/// This is a rough stand-in for heat or geodesic weights, good enough to bound a mesh that
+/// wasn't weighted by an artist. Insert the result as [`Mesh::ATTRIBUTE_JOINT_INDEX`]
+/// (`Uint16x4`) and [`Mesh::ATTRIBUTE_JOINT_WEIGHT`] to skin and bound the mesh like any other.
+/// Returns no weights if the mesh has no `Float32x3` positions or there are no joints.
+///
+/// [`SkinnedMesh::joints`]: bevy::render::mesh::skinning::SkinnedMesh::joints
+pub fn compute_auto_weights(mesh: &Mesh, joint_positions: &[Vec3]) -> Vec<([u16; 4], Vec4)> {
+    let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions,
+        _ => return Vec::new(),
+    };
+    if joint_positions.is_empty() {
+        return Vec::new();
+    }
+
+    let mut nearest = Vec::with_capacity(joint_positions.len());
+    positions
+        .iter()
+        .map(|position| {
+            let position = Vec3::from_slice(position);
+            nearest.clear();
+            nearest.extend(
+                joint_positions
+                    .iter()
+                    .enumerate()
+                    .map(|(joint, joint_position)| {
+                        (joint, position.distance_squared(*joint_position))
+                    }),
+            );
+            nearest.sort_unstable_by(|a, b| a.1.total_cmp(&b.1));
+
+            let mut indices = [0; 4];
+            let mut weights = [0.0; 4];
+            for (slot, (joint, distance_squared)) in nearest.iter().take(4).enumerate() {
+                indices[slot] = *joint as u16;
+                // Keeps a vertex sitting on a joint from dividing by zero,
+                // it ends up almost entirely bound to that joint instead.
+                weights[slot] = 1.0 / distance_squared.max(1e-8);
+            }
+            let weights = Vec4::from(weights);
+            (indices, weights / weights.dot(Vec4::ONE))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::mesh::PrimitiveTopology;
+
+    use super::*;
+    use crate::{compute_aabb, skin_vertices};
+
+    #[test]
+    fn weights_sum_to_one_and_skin_the_column() {
+        // A column from (0, 0) to (0, 4) with a joint at each end.
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+        let positions: Vec<[f32; 3]> = (0..5).map(|i| [0.0, i as f32, 0.0]).collect();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        let weights = compute_auto_weights(&mesh, &[Vec3::ZERO, Vec3::Y * 4.0]);
+        assert_eq!(weights.len(), 5);
+        for (_, weights) in &weights {
+            assert!((weights.dot(Vec4::ONE) - 1.0).abs() < 1e-5);
+        }
+        // The ends follow their joint, the middle is split evenly between both.
+        assert!(weights[0].1.x > 0.999);
+        assert!((weights[2].1.x - 0.5).abs() < 1e-5);
+
+        let (indices, weights): (Vec<[u16; 4]>, Vec<[f32; 4]>) = weights
+            .into_iter()
+            .map(|(indices, weights)| (indices, weights.to_array()))
+            .unzip();
+        mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, indices);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, weights);
+        // Lifting the top joint by 2 stretches the column up to 6, the middle halfway up by 1.
+        let joints = [Mat4::IDENTITY, Mat4::from_translation(Vec3::Y * 2.0)];
+        let skinned = skin_vertices(&mesh, &joints).unwrap();
+        assert!((skinned[2].y - 3.0).abs() < 1e-5);
+        let aabb = compute_aabb(&skinned).unwrap();
+        assert!(Vec3::from(aabb.min()).abs_diff_eq(Vec3::ZERO, 1e-4));
+        assert!(Vec3::from(aabb.max()).abs_diff_eq(Vec3::Y * 6.0, 1e-4));
+    }
+}
@@ -0,0 +1,116 @@
+//! Bounds skinned with a simplified skeleton of their own, decoupled from the render skeleton.
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{skinning::SkinnedMeshInverseBindposes, VertexAttributeValues},
+        primitives::Aabb,
+    },
+};
+
+use crate::{
+    compute_aabb, compute_bounding_sphere, skin_model_normalized, write_skinned_aabb,
+    NoSkinnedAabb, SkinnedAabbConfig, SkinnedAabbFrameCount, SkinnedAabbOutput,
+};
+
+/// Bound a mesh by skinning it with these joints instead of those of its
+/// [`SkinnedMesh`](bevy::render::mesh::skinning::SkinnedMesh), e.g. a handful of capsule bones an
+/// artist set up for bounds, which is much cheaper to skin than the render skeleton.
+///
+/// Each vertex is skinned from its [`Mesh::ATTRIBUTE_POSITION`] with its entry of `mesh_weights`,
+/// the same way as with the render skeleton, into the mesh entity's local space. The mesh is
+/// bounded every frame, whatever its [`AabbUpdateMode`](crate::AabbUpdateMode) or
+/// [`AabbCompute`](crate::AabbCompute). Vertices without an entry, or with a joint index past
+/// `joints`, are left out.
+#[derive(Component, Clone, Debug)]
+pub struct BoundsSkeleton {
+    pub joints: Vec<Entity>,
+    /// The inverse bindpose of each of `joints`.
+    pub bindposes: Handle<SkinnedMeshInverseBindposes>,
+    /// The joints of `joints` influencing each vertex of the mesh, and their weights.
+    pub mesh_weights: Vec<([u32; 4], Vec4)>,
+}
+
+/// Same as `update_skinned_aabbs`, with the joint matrices and weights of a [`BoundsSkeleton`].
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn update_bounds_skeleton_aabbs(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &Handle<Mesh>,
+            &GlobalTransform,
+            &BoundsSkeleton,
+            SkinnedAabbOutput,
+        ),
+        Without<NoSkinnedAabb>,
+    >,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    joint_query: Query<&GlobalTransform>,
+    config: Res<SkinnedAabbConfig>,
+    frame_count: Res<SkinnedAabbFrameCount>,
+    time: Res<Time>,
+    mut joints: Local<Vec<Mat4>>,
+    mut ls_positions: Local<Vec<Vec3>>,
+) {
+    for (entity, mesh_h, transform, skeleton, output) in &mut query {
+        let mesh_positions = match meshes
+            .get(mesh_h)
+            .and_then(|mesh| mesh.attribute(Mesh::ATTRIBUTE_POSITION))
+        {
+            Some(VertexAttributeValues::Float32x3(positions)) => positions,
+            _ => continue,
+        };
+        let bindposes = match inverse_bindposes.get(&skeleton.bindposes) {
+            Some(bindposes) => bindposes,
+            None => continue,
+        };
+        let mut world_to_local = transform.compute_matrix().inverse();
+        if let Some(offset) = config.global_pose_offset {
+            world_to_local *= Mat4::from(offset);
+        }
+        joints.clear();
+        for (joint, bindpose) in skeleton.joints.iter().zip(bindposes.iter()) {
+            match joint_query.get(*joint) {
+                Ok(joint) => joints.push(world_to_local * joint.compute_matrix() * *bindpose),
+                Err(_) => break,
+            }
+        }
+        if joints.len() < skeleton.joints.len().min(bindposes.len()) {
+            continue;
+        }
+
+        ls_positions.clear();
+        ls_positions.extend(
+            mesh_positions
+                .iter()
+                .zip(&skeleton.mesh_weights)
+                .filter(|(_, (indices, weights))| {
+                    config.zero_weights.keep(*weights)
+                        && indices.iter().all(|index| (*index as usize) < joints.len())
+                })
+                .map(|(position, (indices, weights))| {
+                    skin_model_normalized(&joints, indices, *weights)
+                        .transform_point3(Vec3::from(*position))
+                }),
+        );
+        let ls_aabb: Option<Aabb> = compute_aabb(&ls_positions);
+        if let Some(ls_aabb) = ls_aabb {
+            let sphere = config
+                .bounding_sphere
+                .then(|| compute_bounding_sphere(&ls_positions))
+                .flatten();
+            write_skinned_aabb(
+                &mut commands,
+                entity,
+                output,
+                ls_aabb,
+                sphere,
+                frame_count.0,
+                &config,
+                &time,
+            );
+        }
+    }
+}
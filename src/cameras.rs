@@ -0,0 +1,65 @@
+//! Which cameras can see each skinned mesh, for split screen and other multi view setups.
+
+use bevy::{
+    prelude::*,
+    render::{
+        primitives::{Frustum, Sphere},
+        view::VisibilitySystems,
+    },
+};
+
+use crate::{SkinnedAabb, SkinnedAabbSystems};
+
+/// The active cameras whose [`Frustum`] contains the bounds of a skinned mesh, in no particular
+/// order. Insert it on a skinned mesh to have [`ComputeSkinnedAabbPlugin`](crate::ComputeSkinnedAabbPlugin)
+/// fill it in every frame, once its [`SkinnedAabb`] is computed.
+///
+/// The bounds are tested the way bevy culls: only against the frustum, so a mesh hidden behind
+/// another one or by [`RenderLayers`](bevy::render::view::RenderLayers) still counts as visible.
+#[derive(Component, Clone, Debug, Default, PartialEq, Eq)]
+pub struct VisibleToCameras(pub Vec<Entity>);
+
+pub(crate) struct VisibleToCamerasPlugin;
+
+impl Plugin for VisibleToCamerasPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            update_visible_to_cameras
+                .after(SkinnedAabbSystems::UpdateAabbs)
+                .after(VisibilitySystems::UpdateOrthographicFrusta)
+                .after(VisibilitySystems::UpdatePerspectiveFrusta)
+                .after(VisibilitySystems::UpdateProjectionFrusta),
+        );
+    }
+}
+
+/// Test the bounds of every skinned mesh with a [`VisibleToCameras`] against each active camera.
+fn update_visible_to_cameras(
+    cameras: Query<(Entity, &Camera, &Frustum)>,
+    mut query: Query<(&SkinnedAabb, &GlobalTransform, &mut VisibleToCameras)>,
+    mut visible: Local<Vec<Entity>>,
+) {
+    for (skinned_aabb, transform, mut visible_to_cameras) in &mut query {
+        let model = transform.compute_matrix();
+        let aabb = &skinned_aabb.local;
+        let sphere = Sphere {
+            center: model.transform_point3a(aabb.center),
+            radius: transform.radius_vec3a(aabb.half_extents),
+        };
+        visible.clear();
+        visible.extend(
+            cameras
+                .iter()
+                .filter(|(_, camera, frustum)| {
+                    camera.is_active
+                        && frustum.intersects_sphere(&sphere, false)
+                        && frustum.intersects_obb(aabb, &model, false)
+                })
+                .map(|(entity, _, _)| entity),
+        );
+        if visible_to_cameras.0 != *visible {
+            visible_to_cameras.0.clone_from(&visible);
+        }
+    }
+}
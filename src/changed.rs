@@ -0,0 +1,71 @@
+//! Events sent when the bounds of a skinned mesh move noticeably, for spatial structures that
+//! only want to be updated then.
+
+use bevy::{prelude::*, render::primitives::Aabb};
+
+use crate::{SkinnedAabb, SkinnedAabbConfig};
+
+/// Sent when the [`Aabb`] of a skinned mesh moved by more than its change threshold since the
+/// last event for it, see [`SkinnedAabbConfig::changed_event_threshold`].
+/// Not sent for the first bounds of a mesh, use `Added<SkinnedAabb>` for those.
+#[derive(Clone, Debug)]
+pub struct SkinnedAabbChanged {
+    pub entity: Entity,
+    /// The bounds of the last event, or the first bounds of the mesh.
+    pub previous: Aabb,
+    pub new: Aabb,
+}
+
+/// Overrides [`SkinnedAabbConfig::changed_event_threshold`] for one skinned mesh.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct SkinnedAabbChangeThreshold(pub f32);
+
+/// The bounds of the last [`SkinnedAabbChanged`] sent for a skinned mesh.
+#[derive(Component)]
+pub(crate) struct ReportedAabb(Aabb);
+
+/// Send a [`SkinnedAabbChanged`] for each skinned mesh whose bounds moved by more than its
+/// threshold, relative to the size of the bounds, since the last one.
+#[allow(clippy::type_complexity)]
+pub(crate) fn send_skinned_aabb_changed(
+    mut commands: Commands,
+    config: Res<SkinnedAabbConfig>,
+    mut events: EventWriter<SkinnedAabbChanged>,
+    mut query: Query<
+        (
+            Entity,
+            &Aabb,
+            Option<&mut ReportedAabb>,
+            Option<&SkinnedAabbChangeThreshold>,
+        ),
+        With<SkinnedAabb>,
+    >,
+) {
+    for (entity, aabb, reported, threshold) in &mut query {
+        let mut reported = match reported {
+            Some(reported) => reported,
+            None => {
+                commands.entity(entity).insert(ReportedAabb(aabb.clone()));
+                continue;
+            }
+        };
+        let threshold = threshold.map_or(config.changed_event_threshold, |threshold| threshold.0);
+        // Relative to the larger of the boxes, so idle animations of huge meshes stay quiet.
+        let size = aabb
+            .half_extents
+            .max_element()
+            .max(reported.0.half_extents.max_element());
+        let epsilon = threshold.max(0.0) * size;
+        let moved = !aabb.center.abs_diff_eq(reported.0.center, epsilon)
+            || !aabb
+                .half_extents
+                .abs_diff_eq(reported.0.half_extents, epsilon);
+        if moved {
+            events.send(SkinnedAabbChanged {
+                entity,
+                previous: std::mem::replace(&mut reported.0, aabb.clone()),
+                new: aabb.clone(),
+            });
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! The closest point of a skinned mesh's surface to a point, for snapping and magnetism.
+
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    render::mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Indices, PrimitiveTopology,
+    },
+};
+
+use crate::get_skinned_vertex_locations;
+
+/// Looks up the surface of skinned meshes in their current pose.
+/// Add it as a system parameter.
+#[derive(SystemParam)]
+pub struct SkinnedSurface<'w, 's> {
+    skinned_meshes: Query<'w, 's, (&'static Handle<Mesh>, &'static SkinnedMesh)>,
+    meshes: Res<'w, Assets<Mesh>>,
+    inverse_bindposes: Res<'w, Assets<SkinnedMeshInverseBindposes>>,
+    joints: Query<'w, 's, &'static GlobalTransform>,
+}
+
+impl<'w, 's> SkinnedSurface<'w, 's> {
+    /// The point of the skinned triangles of `entity` closest to the world space `point`, and its
+    /// distance to `point`, both in world space.
+    ///
+    /// The mesh is skinned on every call. Returns `None` if `entity` isn't a skinned mesh, its
+    /// mesh isn't loaded, isn't an indexed [`PrimitiveTopology::TriangleList`], or its joints
+    /// can't be resolved.
+    pub fn closest_point_on_skin(&self, entity: Entity, point: Vec3) -> Option<(Vec3, f32)> {
+        let (mesh_h, skinned_mesh) = self.skinned_meshes.get(entity).ok()?;
+        let mesh = self.meshes.get(mesh_h)?;
+        if mesh.primitive_topology() != PrimitiveTopology::TriangleList {
+            return None;
+        }
+        let ws_positions = get_skinned_vertex_locations(
+            mesh,
+            skinned_mesh,
+            &self.joints,
+            &self.inverse_bindposes,
+        )?;
+        closest_point_on_triangles(&ws_positions, mesh.indices()?, point)
+    }
+}
+
+/// The point of the triangle list `indices` over `positions` closest to `point`, and its
+/// distance to `point`. Triangles with an out of range index are skipped.
+/// Returns `None` if there is no triangle.
+///
+/// Each triangle is tested in turn, skipping those whose bounds are farther than the closest
+/// point found so far, so it is linear in the number of triangles.
+pub fn closest_point_on_triangles(
+    positions: &[Vec3],
+    indices: &Indices,
+    point: Vec3,
+) -> Option<(Vec3, f32)> {
+    let indices: Vec<usize> = indices.iter().collect();
+    let mut closest: Option<(Vec3, f32)> = None;
+    for triangle in indices.chunks_exact(3) {
+        if triangle.iter().any(|i| *i >= positions.len()) {
+            continue;
+        }
+        let [a, b, c] = [0, 1, 2].map(|i| positions[triangle[i]]);
+        if let Some((_, distance_squared)) = closest {
+            let outside = (a.min(b).min(c) - point)
+                .max(point - a.max(b).max(c))
+                .max(Vec3::ZERO);
+            if outside.length_squared() >= distance_squared {
+                continue;
+            }
+        }
+        let candidate = closest_point_on_triangle(point, a, b, c);
+        let distance_squared = candidate.distance_squared(point);
+        if closest.is_none_or(|(_, closest)| distance_squared < closest) {
+            closest = Some((candidate, distance_squared));
+        }
+    }
+    closest.map(|(closest, distance_squared)| (closest, distance_squared.sqrt()))
+}
+
+/// The point of triangle `a`, `b`, `c` closest to `p`, found by checking which vertex, edge or
+/// face region of the triangle `p` projects into (Ericson, Real-Time Collision Detection 5.1.5).
+fn closest_point_on_triangle(p: Vec3, a: Vec3, b: Vec3, c: Vec3) -> Vec3 {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = ab.dot(ap);
+    let d2 = ac.dot(ap);
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = ab.dot(bp);
+    let d4 = ac.dot(bp);
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = ab.dot(cp);
+    let d6 = ac.dot(cp);
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    // Degenerate triangles end up here with a zero denominator, fall back to a vertex.
+    let denominator = va + vb + vc;
+    if denominator.abs() <= f32::EPSILON {
+        return a;
+    }
+    let v = vb / denominator;
+    let w = vc / denominator;
+    a + ab * v + ac * w
+}
@@ -0,0 +1,89 @@
+//! One box around every skinned mesh of a character, for glTF characters imported as several mesh
+//! primitives sharing a skeleton.
+
+use bevy::{prelude::*, render::primitives::Aabb};
+
+use crate::{aabb_union, SkinnedAabb, SkinnedAabbConfig, SkinnedAabbSystems};
+
+/// The union of the [`SkinnedAabb::world`] bounds of every skinned mesh under the entity, itself
+/// included. Insert it on the root of a character to have
+/// [`ComputeSkinnedAabbPlugin`](crate::ComputeSkinnedAabbPlugin) fill it in every frame, once the
+/// bounds are updated.
+///
+/// Meshes added to or removed from the hierarchy, like equipped armor, are picked up on the next
+/// frame. Like [`SkinnedAabb`], it's only written when the union moves by more than
+/// [`SkinnedAabbConfig::change_epsilon`] or one of the meshes gets newer bounds.
+#[derive(Component, Clone, Debug, Default)]
+pub struct CombinedSkinnedAabb {
+    /// Whether meshes whose [`Visibility`] is hidden are part of the union.
+    pub include_hidden: bool,
+    /// The union in world space, `None` until one of the meshes has bounds.
+    pub world: Option<Aabb>,
+    /// The newest [`SkinnedAabb::frame`] of the meshes in the union.
+    pub frame: u32,
+}
+
+impl CombinedSkinnedAabb {
+    pub fn new(include_hidden: bool) -> Self {
+        Self {
+            include_hidden,
+            ..default()
+        }
+    }
+}
+
+pub(crate) struct CombinedSkinnedAabbPlugin;
+
+impl Plugin for CombinedSkinnedAabbPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            update_combined_skinned_aabbs.after(SkinnedAabbSystems::UpdateAabbs),
+        );
+    }
+}
+
+/// Bound the skinned meshes under each entity with a [`CombinedSkinnedAabb`] together.
+fn update_combined_skinned_aabbs(
+    config: Res<SkinnedAabbConfig>,
+    mut roots: Query<(Entity, &mut CombinedSkinnedAabb)>,
+    children: Query<&Children>,
+    meshes: Query<(&SkinnedAabb, Option<&Visibility>)>,
+    mut stack: Local<Vec<Entity>>,
+) {
+    for (root, mut combined) in &mut roots {
+        let mut world: Option<Aabb> = None;
+        let mut frame = 0;
+        stack.clear();
+        stack.push(root);
+        while let Some(entity) = stack.pop() {
+            if let Ok((skinned_aabb, visibility)) = meshes.get(entity) {
+                let hidden = visibility.is_some_and(|visibility| !visibility.is_visible);
+                if combined.include_hidden || !hidden {
+                    world = Some(match world {
+                        Some(world) => aabb_union(&world, &skinned_aabb.world),
+                        None => skinned_aabb.world.clone(),
+                    });
+                    frame = frame.max(skinned_aabb.frame);
+                }
+            }
+            if let Ok(children) = children.get(entity) {
+                stack.extend(children.iter().copied());
+            }
+        }
+
+        let moved = match (&combined.world, &world) {
+            (Some(a), Some(b)) => {
+                !a.center.abs_diff_eq(b.center, config.change_epsilon)
+                    || !a
+                        .half_extents
+                        .abs_diff_eq(b.half_extents, config.change_epsilon)
+            }
+            (a, b) => a.is_some() != b.is_some(),
+        };
+        if moved || combined.frame != frame {
+            combined.world = world;
+            combined.frame = frame;
+        }
+    }
+}
@@ -0,0 +1,175 @@
+//! Bounds for meshes skinned along a curve, for procedural creatures driven by a spline
+//! instead of joint entities.
+
+use bevy::{
+    ecs::schedule::ShouldRun,
+    prelude::*,
+    render::{mesh::skinning::SkinnedMesh, primitives::Aabb},
+    utils::HashSet,
+};
+
+use crate::{
+    compute_aabb, compute_bounding_sphere, skin_vertices_with, write_skinned_aabb, AabbUpdateMode,
+    NoSkinnedAabb, SkinnedAabbConfig, SkinnedAabbFrameCount, SkinnedAabbOutput,
+};
+
+/// Skin a mesh along a Catmull-Rom spline. Joint `i` of the mesh follows the `i`th of `samples`
+/// frames spread evenly over the curve's parameter, each pointing its Y axis along its tangent.
+///
+/// The mesh is modelled standing on the origin along +Y, with joint `i` at
+/// `i / (samples - 1) * rest_length` on the Y axis, and is bounded with the same skinning as
+/// joint driven meshes. Used instead of a [`SkinnedMesh`](bevy::render::mesh::skinning::SkinnedMesh),
+/// which takes precedence if both are present.
+#[derive(Component, Clone, Debug)]
+pub struct CurveSkin {
+    /// Control points of the spline in the mesh entity's local space.
+    /// The curve passes through all of them, at least two are needed.
+    pub curve: Vec<Vec3>,
+    pub samples: usize,
+    /// Length of the mesh's rest pose along the Y axis.
+    pub rest_length: f32,
+}
+
+impl CurveSkin {
+    /// The position and unit tangent of the curve at `t` from 0 to 1,
+    /// or `None` with less than two control points.
+    pub fn sample(&self, t: f32) -> Option<(Vec3, Vec3)> {
+        let last = self.curve.len().checked_sub(1).filter(|last| *last > 0)?;
+        let scaled = t.clamp(0.0, 1.0) * last as f32;
+        let segment = (scaled as usize).min(last - 1);
+        let t = scaled - segment as f32;
+        let point = |i: isize| self.curve[i.clamp(0, last as isize) as usize];
+        let i = segment as isize;
+        let (p0, p1, p2, p3) = (point(i - 1), point(i), point(i + 1), point(i + 2));
+
+        let position = 0.5
+            * (2.0 * p1
+                + (p2 - p0) * t
+                + (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t * t
+                + (3.0 * p1 - p0 - 3.0 * p2 + p3) * t * t * t);
+        let tangent = 0.5
+            * ((p2 - p0)
+                + 2.0 * (2.0 * p0 - 5.0 * p1 + 4.0 * p2 - p3) * t
+                + 3.0 * (3.0 * p1 - p0 - 3.0 * p2 + p3) * t * t);
+        Some((position, tangent.try_normalize().unwrap_or(Vec3::Y)))
+    }
+
+    /// The skinning matrices of the joints following the curve, as built by
+    /// [`build_joint_matrices`](crate::build_joint_matrices) for joint entities.
+    pub fn joint_matrices(&self) -> Option<Vec<Mat4>> {
+        let steps = self.samples.checked_sub(1).filter(|steps| *steps > 0)?;
+        (0..self.samples)
+            .map(|i| {
+                let t = i as f32 / steps as f32;
+                let (position, tangent) = self.sample(t)?;
+                let frame = Mat4::from_rotation_translation(
+                    Quat::from_rotation_arc(Vec3::Y, tangent),
+                    position,
+                );
+                Some(frame * Mat4::from_translation(Vec3::new(0.0, -t * self.rest_length, 0.0)))
+            })
+            .collect()
+    }
+}
+
+/// Run [`update_curve_skinned_aabbs`] only while there is a [`CurveSkin`] to bound, like
+/// [`any_skinned_meshes`](crate::any_skinned_meshes) for joint driven meshes.
+#[allow(clippy::type_complexity)]
+pub(crate) fn any_curve_skins(
+    query: Query<
+        (),
+        (
+            With<CurveSkin>,
+            Without<NoSkinnedAabb>,
+            Without<SkinnedMesh>,
+        ),
+    >,
+) -> ShouldRun {
+    if query.is_empty() {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+/// Same as `update_skinned_aabbs`, with the joint matrices derived from a [`CurveSkin`].
+/// The curve is already in local space, so the mesh entity's transform isn't involved.
+/// Under [`AabbUpdateMode::OnJointChange`] the bounds are updated when the [`CurveSkin`] changes,
+/// and always when the mesh or the [`SkinnedAabbConfig`] does.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn update_curve_skinned_aabbs(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &Handle<Mesh>,
+            &CurveSkin,
+            Option<&AabbUpdateMode>,
+            ChangeTrackers<Handle<Mesh>>,
+            ChangeTrackers<CurveSkin>,
+            SkinnedAabbOutput,
+        ),
+        (Without<NoSkinnedAabb>, Without<SkinnedMesh>),
+    >,
+    meshes: Res<Assets<Mesh>>,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    config: Res<SkinnedAabbConfig>,
+    frame_count: Res<SkinnedAabbFrameCount>,
+    time: Res<Time>,
+) {
+    let modified_meshes: HashSet<_> = mesh_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => Some(handle.id),
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+    for (entity, mesh_h, curve_skin, update_mode, mesh_tracker, curve_tracker, output) in &mut query
+    {
+        let inputs_changed = mesh_tracker.is_changed()
+            || modified_meshes.contains(&mesh_h.id)
+            || config.is_changed();
+        if output.skinned_aabb.is_some() && !inputs_changed {
+            let due = match update_mode.copied().unwrap_or(config.update_mode) {
+                AabbUpdateMode::EveryFrame => true,
+                AabbUpdateMode::Interval(interval) => {
+                    AabbUpdateMode::interval_due(interval, entity, frame_count.0)
+                }
+                AabbUpdateMode::FixedHz(hz) => AabbUpdateMode::fixed_hz_due(
+                    hz,
+                    entity,
+                    time.seconds_since_startup(),
+                    time.delta_seconds_f64(),
+                ),
+                // The curve is all that moves the joints.
+                AabbUpdateMode::OnJointChange => curve_tracker.is_changed(),
+            };
+            if !due {
+                continue;
+            }
+        }
+        let ls_positions = meshes
+            .get(mesh_h)
+            .zip(curve_skin.joint_matrices())
+            .and_then(|(mesh, joints)| skin_vertices_with(mesh, &joints, config.zero_weights));
+        let ls_aabb: Option<Aabb> = ls_positions
+            .as_ref()
+            .and_then(|ls_positions| compute_aabb(ls_positions));
+        if let (Some(ls_positions), Some(ls_aabb)) = (ls_positions, ls_aabb) {
+            let sphere = config
+                .bounding_sphere
+                .then(|| compute_bounding_sphere(&ls_positions))
+                .flatten();
+            write_skinned_aabb(
+                &mut commands,
+                entity,
+                output,
+                ls_aabb,
+                sphere,
+                frame_count.0,
+                &config,
+                &time,
+            );
+        }
+    }
+}
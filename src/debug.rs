@@ -0,0 +1,802 @@
+//! Line drawing of the bounds computed for each skinned mesh, and of whatever else other systems
+//! want to see.
+
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use bevy::{
+    asset::HandleId,
+    ecs::schedule::ShouldRun,
+    pbr::{MaterialPipeline, MaterialPipelineKey, NotShadowCaster},
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        mesh::{
+            skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+            MeshVertexBufferLayout, PrimitiveTopology, VertexAttributeValues,
+        },
+        primitives::Aabb,
+        render_resource::{
+            AsBindGroup, CompareFunction, RenderPipelineDescriptor, SpecializedMeshPipelineError,
+        },
+        view::NoFrustumCulling,
+    },
+    utils::HashMap,
+};
+
+use crate::{
+    any_skinned_meshes, get_skinned_vertex_locations_into, mesh_joint_indices, mesh_joint_weights,
+    normalize_weights, NoSkinnedAabb, SkinnedAabb, SkinnedAabbSystems, SkinnedObb,
+};
+
+/// Draws the [`SkinnedAabb`] of every skinned mesh as lines, and optionally its bounding sphere,
+/// its [`SkinnedObb`] and a cross on each of its skinned vertices, configured with
+/// [`SkinnedAabbDebugConfig`].
+///
+/// Every line is batched with those drawn through [`DebugLines`] by other systems into a single
+/// unlit line mesh, rebuilt each frame, so no entity is spawned per mesh.
+/// Add this plugin after `DefaultPlugins`, it creates its meshes when it's built.
+pub struct SkinnedAabbDebugPlugin;
+
+impl Plugin for SkinnedAabbDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(MaterialPlugin::<DebugLineMaterial>::default())
+            .init_resource::<SkinnedAabbDebugConfig>()
+            .init_resource::<DebugLines>()
+            .init_resource::<DebugAssets>()
+            .add_system(update_joint_colors.with_run_criteria(joint_colors_enabled))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                draw_skinned_bounds
+                    .with_run_criteria(debug_enabled)
+                    .label(DrawSkinnedBounds)
+                    .after(SkinnedAabbSystems::UpdateAabbs),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                flush_debug_lines.after(DrawSkinnedBounds),
+            );
+    }
+}
+
+/// What [`SkinnedAabbDebugPlugin`] draws. Nothing is drawn by default, turn on
+/// [`SkinnedAabbDebugConfig::enabled`] and what to draw.
+#[derive(Clone, Debug)]
+pub struct SkinnedAabbDebugConfig {
+    /// Draw anything at all, including the lines of [`DebugLines`]. Turning it off despawns the
+    /// line mesh.
+    pub enabled: bool,
+    /// Only draw the skinned meshes with a [`SkinnedAabbDebug`], to look at a single character
+    /// in a crowd. [`SkinnedAabbDebugConfig::joint_colors`] still applies to every mesh.
+    pub only_marked: bool,
+    /// Draw a cross on the skinned vertices of each mesh with a [`SkinnedMesh`],
+    /// tinted by the joint with the most weight on the vertex.
+    pub vertex_markers: bool,
+    /// Draw the edges of each [`SkinnedAabb`].
+    pub show_aabb: bool,
+    /// The most vertices marked for one mesh, the vertices past it aren't marked.
+    pub max_vertex_markers: usize,
+    /// The length of each arm of the vertex crosses.
+    pub vertex_marker_size: f32,
+    /// The color of the boxes and spheres, unless their mesh has a [`DebugColor`].
+    pub aabb_color: Color,
+    /// Draw [`SkinnedAabb::sphere`] as a latitude/longitude wire sphere, for meshes that have
+    /// one.
+    pub show_sphere: bool,
+    /// Draw the edges of each [`SkinnedObb`], to compare it with the [`SkinnedAabb`].
+    pub show_obb: bool,
+    /// The color of the oriented boxes.
+    pub obb_color: Color,
+    /// Hide the lines behind other geometry. Without it they are drawn over everything.
+    pub depth_test: bool,
+    /// Color the vertices of every skinned mesh by their joints, to spot mangled weights or
+    /// indices.
+    pub joint_colors: JointColors,
+    /// Draw the boxes and spheres into this [`DebugLines::group`] instead of this frame's lines.
+    /// They're then only drawn again when some bounds, transform or color changes, and can be
+    /// hidden through the group's [`DebugLineGroup::visible`] without turning debugging off.
+    /// The vertex markers are still drawn every frame.
+    pub group: Option<String>,
+}
+
+/// How [`SkinnedAabbDebugPlugin`] colors the vertices of skinned meshes, by writing their
+/// [`Mesh::ATTRIBUTE_COLOR`]. The colors a mesh had, or their absence, are put back once this is
+/// [`JointColors::Off`] again or debugging is turned off. The mesh asset itself is modified, so
+/// every entity sharing it is colored.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JointColors {
+    /// Leave the vertex colors alone.
+    #[default]
+    Off,
+    /// Color each vertex by the joint with the most weight on it, like the vertex markers.
+    Dominant,
+    /// Show the weight of this joint index on each vertex as a grayscale heatmap, white at 1.
+    Influence(u32),
+}
+
+impl Default for SkinnedAabbDebugConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            only_marked: false,
+            vertex_markers: false,
+            show_aabb: false,
+            max_vertex_markers: 256,
+            vertex_marker_size: 0.05,
+            aabb_color: Color::YELLOW,
+            show_sphere: false,
+            show_obb: false,
+            obb_color: Color::CYAN,
+            depth_test: false,
+            joint_colors: JointColors::default(),
+            group: None,
+        }
+    }
+}
+
+/// Draw the box and sphere of this skinned mesh in this color instead of
+/// [`SkinnedAabbDebugConfig::aabb_color`].
+#[derive(Component, Clone, Copy, Debug)]
+pub struct DebugColor(pub Color);
+
+/// Opts a skinned mesh into the drawing of [`SkinnedAabbDebugPlugin`] when
+/// [`SkinnedAabbDebugConfig::only_marked`] is set.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct SkinnedAabbDebug {
+    /// Mark every vertex of the mesh, however many it has, whether or not
+    /// [`SkinnedAabbDebugConfig::vertex_markers`] is set.
+    pub vertex_markers: bool,
+}
+
+/// Everything to insert on an existing skinned mesh to draw its bounds in `color`, e.g.
+/// `commands.entity(character).insert_bundle(SkinnedAabbDebugBundle::new(Color::GREEN))`.
+#[derive(Bundle, Clone, Copy, Debug)]
+pub struct SkinnedAabbDebugBundle {
+    pub debug: SkinnedAabbDebug,
+    pub color: DebugColor,
+}
+
+impl SkinnedAabbDebugBundle {
+    pub fn new(color: Color) -> Self {
+        Self {
+            debug: default(),
+            color: DebugColor(color),
+        }
+    }
+
+    /// Also mark every vertex of the mesh.
+    pub fn with_vertex_markers(mut self) -> Self {
+        self.debug.vertex_markers = true;
+        self
+    }
+}
+
+impl Default for SkinnedAabbDebugBundle {
+    fn default() -> Self {
+        Self::new(SkinnedAabbDebugConfig::default().aabb_color)
+    }
+}
+
+/// Lines drawn by [`SkinnedAabbDebugPlugin`] this frame, in world space.
+///
+/// Any system can draw into it; the lines are uploaded in [`CoreStage::PostUpdate`], after the
+/// bounds of the skinned meshes are drawn, and cleared for the next frame. Lines drawn later in
+/// the frame show up the frame after. Lines drawn into a [`DebugLines::group`] are kept instead,
+/// and drawn every frame until the group is cleared or removed.
+#[derive(Clone, Debug, Default)]
+pub struct DebugLines {
+    positions: Vec<[f32; 3]>,
+    colors: Vec<[f32; 4]>,
+    groups: HashMap<String, DebugLineGroup>,
+}
+
+/// Lines kept across frames in [`DebugLines`] under a name, e.g. the bounds drawn for
+/// [`SkinnedAabbDebugConfig::group`].
+#[derive(Clone, Debug, Default)]
+pub struct DebugLineGroup {
+    /// Whether the lines are drawn. A hidden group keeps its lines.
+    pub visible: bool,
+    /// The lines of the group. Only its own lines are drawn, not those of groups inside it.
+    pub lines: DebugLines,
+}
+
+/// Number of segments of each circle of [`DebugLines::draw_sphere`].
+const SPHERE_SEGMENTS: u32 = 24;
+/// Number of great circles through the poles of [`DebugLines::draw_sphere`].
+const SPHERE_MERIDIANS: u32 = 4;
+/// Number of circles of latitude of [`DebugLines::draw_sphere`], between the poles.
+const SPHERE_PARALLELS: u32 = 5;
+
+impl DebugLines {
+    pub fn line(&mut self, start: Vec3, end: Vec3, color: Color) {
+        self.positions.extend([start.to_array(), end.to_array()]);
+        self.colors.extend([color.as_linear_rgba_f32(); 2]);
+    }
+
+    /// The 12 edges of a box. Corner `i` is on the maximum side along x if bit 0 of `i` is set,
+    /// along y for bit 1 and along z for bit 2, like the corners of [`SkinnedObb::corners`].
+    pub fn draw_box(&mut self, corners: [Vec3; 8], color: Color) {
+        // Each edge joins two corners whose index differs by a single bit.
+        for i in 0..8 {
+            for bit in [1, 2, 4] {
+                if i & bit == 0 {
+                    self.line(corners[i], corners[i | bit], color);
+                }
+            }
+        }
+    }
+
+    /// The edges of a world space `aabb`.
+    pub fn draw_aabb(&mut self, aabb: &Aabb, color: Color) {
+        self.draw_aabb_transformed(aabb, &GlobalTransform::identity(), color);
+    }
+
+    /// The edges of `aabb`, in the local space of `transform`. Boxes with a zero extent are drawn
+    /// flat.
+    pub fn draw_aabb_transformed(
+        &mut self,
+        aabb: &Aabb,
+        transform: &GlobalTransform,
+        color: Color,
+    ) {
+        let center = Vec3::from(aabb.center);
+        let half_extents = Vec3::from(aabb.half_extents);
+        let corners = [0, 1, 2, 3, 4, 5, 6, 7].map(|i| {
+            let sign = Vec3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            );
+            transform.mul_vec3(center + sign * half_extents)
+        });
+        self.draw_box(corners, color);
+    }
+
+    /// A latitude/longitude wire sphere, with its poles along y.
+    pub fn draw_sphere(&mut self, center: Vec3, radius: f32, color: Color) {
+        let point = |latitude: f32, longitude: f32| {
+            center
+                + radius
+                    * Vec3::new(
+                        latitude.cos() * longitude.cos(),
+                        latitude.sin(),
+                        latitude.cos() * longitude.sin(),
+                    )
+        };
+        for segment in 0..SPHERE_SEGMENTS {
+            let start = TAU * segment as f32 / SPHERE_SEGMENTS as f32;
+            let end = TAU * (segment + 1) as f32 / SPHERE_SEGMENTS as f32;
+            for meridian in 0..SPHERE_MERIDIANS {
+                let longitude = PI * meridian as f32 / SPHERE_MERIDIANS as f32;
+                self.line(point(start, longitude), point(end, longitude), color);
+            }
+            for parallel in 1..=SPHERE_PARALLELS {
+                let latitude = PI * parallel as f32 / (SPHERE_PARALLELS + 1) as f32 - FRAC_PI_2;
+                self.line(point(latitude, start), point(latitude, end), color);
+            }
+        }
+    }
+
+    /// A cross of three lines along the axes, each `size` long on either side of `center`.
+    pub fn draw_cross(&mut self, center: Vec3, size: f32, color: Color) {
+        for axis in [Vec3::X, Vec3::Y, Vec3::Z] {
+            self.line(center - axis * size, center + axis * size, color);
+        }
+    }
+
+    /// Forget every line drawn so far this frame. The groups are kept.
+    pub fn clear(&mut self) {
+        self.positions.clear();
+        self.colors.clear();
+    }
+
+    /// The group of lines named `name`, added empty and visible if there isn't one yet.
+    pub fn group(&mut self, name: &str) -> &mut DebugLineGroup {
+        self.groups
+            .entry(name.to_string())
+            .or_insert_with(|| DebugLineGroup {
+                visible: true,
+                lines: default(),
+            })
+    }
+
+    /// The group of lines named `name`, if there is one.
+    pub fn get_group(&self, name: &str) -> Option<&DebugLineGroup> {
+        self.groups.get(name)
+    }
+
+    /// Stop drawing the group of lines named `name`, returning it.
+    pub fn remove_group(&mut self, name: &str) -> Option<DebugLineGroup> {
+        self.groups.remove(name)
+    }
+}
+
+/// Unlit material of the line mesh, drawn with bevy's default mesh shaders, which output the
+/// vertex colors.
+#[derive(AsBindGroup, TypeUuid, Clone, Debug)]
+#[uuid = "4c7a9e0b-52d1-4f3e-8b6a-1d3f9c2e7a58"]
+#[bind_group_data(DebugLineKey)]
+struct DebugLineMaterial {
+    depth_test: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct DebugLineKey {
+    depth_test: bool,
+}
+
+impl From<&DebugLineMaterial> for DebugLineKey {
+    fn from(material: &DebugLineMaterial) -> Self {
+        Self {
+            depth_test: material.depth_test,
+        }
+    }
+}
+
+impl Material for DebugLineMaterial {
+    fn alpha_mode(&self) -> AlphaMode {
+        // Lines over everything have to be drawn after the opaque meshes.
+        if self.depth_test {
+            AlphaMode::Opaque
+        } else {
+            AlphaMode::Blend
+        }
+    }
+
+    fn specialize(
+        _pipeline: &MaterialPipeline<Self>,
+        descriptor: &mut RenderPipelineDescriptor,
+        _layout: &MeshVertexBufferLayout,
+        key: MaterialPipelineKey<Self>,
+    ) -> Result<(), SpecializedMeshPipelineError> {
+        if !key.bind_group_data.depth_test {
+            if let Some(depth_stencil) = &mut descriptor.depth_stencil {
+                depth_stencil.depth_compare = CompareFunction::Always;
+                depth_stencil.depth_write_enabled = false;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Assets of the line mesh.
+struct DebugAssets {
+    lines: Handle<Mesh>,
+    depth_tested: Handle<DebugLineMaterial>,
+    on_top: Handle<DebugLineMaterial>,
+}
+
+impl FromWorld for DebugAssets {
+    fn from_world(world: &mut World) -> Self {
+        let lines = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Mesh::new(PrimitiveTopology::LineList));
+        let mut materials = world.resource_mut::<Assets<DebugLineMaterial>>();
+        Self {
+            lines,
+            depth_tested: materials.add(DebugLineMaterial { depth_test: true }),
+            on_top: materials.add(DebugLineMaterial { depth_test: false }),
+        }
+    }
+}
+
+/// Marks the line mesh drawing every [`DebugLines`].
+#[derive(Component)]
+struct DebugLinesMesh;
+
+#[derive(SystemLabel)]
+struct DrawSkinnedBounds;
+
+/// Run the debug systems only while [`SkinnedAabbDebugConfig::enabled`] is set
+/// and some mesh has a [`SkinnedAabb`] to draw.
+pub fn debug_enabled(
+    config: Res<SkinnedAabbDebugConfig>,
+    meshes: Query<(), With<SkinnedAabb>>,
+) -> ShouldRun {
+    if config.enabled && !meshes.is_empty() {
+        ShouldRun::Yes
+    } else {
+        ShouldRun::No
+    }
+}
+
+/// Run [`update_joint_colors`] while both [`any_skinned_meshes`] and [`debug_enabled`] do, and
+/// on the frame debugging is turned off, to put the colors back.
+#[allow(clippy::type_complexity)]
+fn joint_colors_enabled(
+    config: Res<SkinnedAabbDebugConfig>,
+    skinned: Query<(), (With<SkinnedMesh>, Without<NoSkinnedAabb>)>,
+    bounded: Query<(), With<SkinnedAabb>>,
+) -> ShouldRun {
+    match any_skinned_meshes(skinned) {
+        ShouldRun::Yes if config.is_changed() && !config.enabled => ShouldRun::Yes,
+        ShouldRun::Yes => debug_enabled(config, bounded),
+        _ => ShouldRun::No,
+    }
+}
+
+/// Draw the bounds of every skinned mesh into [`DebugLines`], or its
+/// [`SkinnedAabbDebugConfig::group`] when they changed, and the crosses on its vertices.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn draw_skinned_bounds(
+    config: Res<SkinnedAabbDebugConfig>,
+    mut lines: ResMut<DebugLines>,
+    owners: Query<(
+        &SkinnedAabb,
+        &GlobalTransform,
+        Option<&SkinnedObb>,
+        Option<&DebugColor>,
+        Option<&SkinnedAabbDebug>,
+    )>,
+    changed: Query<
+        (),
+        (
+            With<SkinnedAabb>,
+            Or<(
+                Changed<SkinnedAabb>,
+                Changed<GlobalTransform>,
+                Changed<SkinnedObb>,
+                Changed<DebugColor>,
+                Changed<SkinnedAabbDebug>,
+            )>,
+        ),
+    >,
+    removed_aabbs: RemovedComponents<SkinnedAabb>,
+    removed_obbs: RemovedComponents<SkinnedObb>,
+    mut drawn_group: Local<Option<String>>,
+    skinned: Query<(&Handle<Mesh>, &SkinnedMesh, Option<&SkinnedAabbDebug>), With<SkinnedAabb>>,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    joint_query: Query<&GlobalTransform>,
+    mut joints: Local<Vec<Mat4>>,
+    mut ws_positions: Local<Vec<Vec3>>,
+) {
+    if *drawn_group != config.group {
+        if let Some(name) = drawn_group.take() {
+            lines.remove_group(&name);
+        }
+    }
+    let redraw = match &config.group {
+        Some(name) => {
+            lines.get_group(name).is_none()
+                || config.is_changed()
+                || !changed.is_empty()
+                || removed_aabbs.iter().next().is_some()
+                || removed_obbs.iter().next().is_some()
+        }
+        None => true,
+    };
+    if redraw {
+        let bounds = match &config.group {
+            Some(name) => {
+                *drawn_group = Some(name.clone());
+                let group = &mut lines.group(name).lines;
+                group.clear();
+                group
+            }
+            None => &mut *lines,
+        };
+        for (skinned_aabb, transform, obb, color, debug) in &owners {
+            if config.only_marked && debug.is_none() {
+                continue;
+            }
+            let color = color.map_or(config.aabb_color, |color| color.0);
+            if config.show_aabb {
+                bounds.draw_aabb_transformed(&skinned_aabb.local, transform, color);
+            }
+            if let Some(sphere) = skinned_aabb.sphere.as_ref().filter(|_| config.show_sphere) {
+                let (scale, _, _) = transform.to_scale_rotation_translation();
+                bounds.draw_sphere(
+                    transform.mul_vec3(sphere.center.into()),
+                    sphere.radius * scale.abs().max_element(),
+                    color,
+                );
+            }
+            if let Some(obb) = obb.filter(|_| config.show_obb) {
+                bounds.draw_box(
+                    obb.corners().map(|corner| transform.mul_vec3(corner)),
+                    config.obb_color,
+                );
+            }
+        }
+    }
+
+    for (mesh_h, skinned_mesh, debug) in &skinned {
+        let max_markers = match debug {
+            Some(debug) if debug.vertex_markers => usize::MAX,
+            _ if config.vertex_markers && !(config.only_marked && debug.is_none()) => {
+                config.max_vertex_markers
+            }
+            _ => continue,
+        };
+        let mesh = match meshes.get(mesh_h) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let (indices, weights) = match (
+            get_skinned_vertex_locations_into(
+                mesh,
+                skinned_mesh,
+                &joint_query,
+                &inverse_bindposes,
+                &mut joints,
+                &mut ws_positions,
+            ),
+            mesh_joint_indices(mesh),
+            mesh_joint_weights(mesh),
+        ) {
+            (Some(()), Some(indices), Some(weights)) => (indices, weights),
+            _ => continue,
+        };
+        // Tint each cross by the joint with the most weight on its vertex, with a hue per joint
+        // and a lightness growing with that weight, so vertices bound to the wrong joint stand out.
+        for ((ws_pos, indices), weights) in ws_positions
+            .iter()
+            .zip(&indices)
+            .zip(&weights)
+            .take(max_markers)
+        {
+            let (joint, weight) = dominant_joint(indices, weights.to_array());
+            lines.draw_cross(
+                *ws_pos,
+                config.vertex_marker_size,
+                joint_color(joint, weight),
+            );
+        }
+    }
+}
+
+/// The joint with the most weight on a vertex, and its weight.
+fn dominant_joint(indices: &[u32; 4], weights: [f32; 4]) -> (u32, f32) {
+    indices
+        .iter()
+        .zip(weights)
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map_or((0, 0.0), |(joint, weight)| (*joint, weight))
+}
+
+/// The color of `joint` with a hue per joint and a lightness growing with `weight`, from 0 to 1,
+/// gray without any weight.
+fn joint_color(joint: u32, weight: f32) -> Color {
+    if weight <= 0.0 {
+        Color::GRAY
+    } else {
+        // Golden angle steps keep neighbouring joints far apart in hue.
+        let hue = (joint as f32 * 137.508) % 360.0;
+        Color::hsl(hue, 1.0, 0.15 + 0.45 * weight.min(1.0))
+    }
+}
+
+/// Write the [`JointColors`] of every skinned mesh to its vertex colors, keeping the colors it
+/// had to put them back once the mode is turned off. Meshes are only colored again when the mode
+/// changes, so coloring them doesn't feed back into itself through their `Modified` events.
+fn update_joint_colors(
+    config: Res<SkinnedAabbDebugConfig>,
+    query: Query<&Handle<Mesh>, With<SkinnedMesh>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut colored: Local<HashMap<HandleId, (JointColors, Option<VertexAttributeValues>)>>,
+) {
+    let mode = if config.enabled {
+        config.joint_colors
+    } else {
+        JointColors::Off
+    };
+    if mode == JointColors::Off {
+        for (id, (_, original)) in colored.drain() {
+            if let Some(mesh) = meshes.get_mut(&Handle::weak(id)) {
+                match original {
+                    Some(original) => mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, original),
+                    None => {
+                        mesh.remove_attribute(Mesh::ATTRIBUTE_COLOR);
+                    }
+                }
+            }
+        }
+        return;
+    }
+    for mesh_h in &query {
+        // A hot reloaded mesh no longer has the colors written to it, nor the original colors
+        // kept for it, so it's colored again from scratch.
+        let reloaded = meshes.get(mesh_h).is_some_and(|mesh| {
+            let count = |attribute| mesh.attribute(attribute).map(VertexAttributeValues::len);
+            count(Mesh::ATTRIBUTE_COLOR) != count(Mesh::ATTRIBUTE_POSITION)
+        });
+        match colored.get(&mesh_h.id) {
+            Some(_) if reloaded => {
+                colored.remove(&mesh_h.id);
+            }
+            Some((colored_mode, _)) if *colored_mode == mode => continue,
+            _ => {}
+        }
+        let mesh = match meshes.get_mut(mesh_h) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let (indices, weights) = match (mesh_joint_indices(mesh), mesh_joint_weights(mesh)) {
+            (Some(indices), Some(weights)) => (indices, weights),
+            _ => continue,
+        };
+        let colors: Vec<[f32; 4]> = indices
+            .iter()
+            .zip(&weights)
+            .map(|(indices, weights)| {
+                let (indices, weights) = normalize_weights(indices, *weights);
+                let color = match mode {
+                    JointColors::Influence(joint) => {
+                        let weight: f32 = indices
+                            .iter()
+                            .zip(weights.to_array())
+                            .filter(|(index, _)| **index == joint)
+                            .map(|(_, weight)| weight)
+                            .sum();
+                        Color::rgb(weight, weight, weight)
+                    }
+                    _ => {
+                        let (joint, weight) = dominant_joint(&indices, weights.to_array());
+                        joint_color(joint, weight)
+                    }
+                };
+                color.as_linear_rgba_f32()
+            })
+            .collect();
+        let original = match colored.remove(&mesh_h.id) {
+            Some((_, original)) => original,
+            None => mesh.attribute(Mesh::ATTRIBUTE_COLOR).cloned(),
+        };
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        colored.insert(mesh_h.id, (mode, original));
+    }
+}
+
+/// Upload this frame's [`DebugLines`] and its visible groups into the line mesh and clear this
+/// frame's lines, spawning its entity when needed and despawning it once debugging is turned off.
+fn flush_debug_lines(
+    mut commands: Commands,
+    config: Res<SkinnedAabbDebugConfig>,
+    assets: Res<DebugAssets>,
+    mut lines: ResMut<DebugLines>,
+    skinned: Query<(), With<SkinnedAabb>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut line_meshes: Query<
+        (Entity, &mut Handle<DebugLineMaterial>, &mut Visibility),
+        With<DebugLinesMesh>,
+    >,
+) {
+    // Bounds drawn into a group go with the last skinned mesh, `draw_skinned_bounds` no longer
+    // runs to clear them.
+    if let Some(name) = config.group.as_ref().filter(|_| skinned.is_empty()) {
+        lines.remove_group(name);
+    }
+    if !config.enabled {
+        lines.clear();
+        for (entity, _, _) in &line_meshes {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    let material = if config.depth_test {
+        &assets.depth_tested
+    } else {
+        &assets.on_top
+    };
+    let groups = || lines.groups.values().filter(|group| group.visible);
+    let visible =
+        !lines.positions.is_empty() || groups().any(|group| !group.lines.positions.is_empty());
+    if line_meshes.is_empty() {
+        commands
+            .spawn_bundle(MaterialMeshBundle {
+                mesh: assets.lines.clone(),
+                material: material.clone(),
+                visibility: Visibility {
+                    is_visible: visible,
+                },
+                ..default()
+            })
+            // The lines are in world space and rebuilt every frame, their `Aabb` would go stale
+            // right away.
+            .insert_bundle((DebugLinesMesh, NoFrustumCulling, NotShadowCaster));
+    }
+    for (_, mut line_material, mut visibility) in &mut line_meshes {
+        if *line_material != *material {
+            *line_material = material.clone();
+        }
+        if visibility.is_visible != visible {
+            visibility.is_visible = visible;
+        }
+    }
+
+    if let Some(mesh) = meshes.get_mut(&assets.lines) {
+        let mut positions = std::mem::take(&mut lines.positions);
+        let mut colors = std::mem::take(&mut lines.colors);
+        for group in lines.groups.values().filter(|group| group.visible) {
+            positions.extend_from_slice(&group.lines.positions);
+            colors.extend_from_slice(&group.lines.colors);
+        }
+        // bevy's mesh pipeline expects normals even on unlit lines.
+        let normals = vec![[0.0, 1.0, 0.0]; positions.len()];
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_NORMAL,
+            VertexAttributeValues::Float32x3(normals),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_COLOR,
+            VertexAttributeValues::Float32x4(colors),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            VertexAttributeValues::Float32x3(positions),
+        );
+    } else {
+        lines.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::asset::AssetPlugin;
+
+    use super::*;
+
+    #[test]
+    fn bounds_are_kept_in_their_group() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<SkinnedMeshInverseBindposes>()
+            .insert_resource(SkinnedAabbDebugConfig {
+                enabled: true,
+                show_aabb: true,
+                group: Some("bounds".to_string()),
+                ..default()
+            })
+            .init_resource::<DebugLines>()
+            .add_system(draw_skinned_bounds);
+        let aabb = Aabb::from_min_max(Vec3::ZERO, Vec3::ONE);
+        let entity = app
+            .world
+            .spawn()
+            .insert(GlobalTransform::identity())
+            .insert(SkinnedAabb {
+                local: aabb.clone(),
+                world: aabb,
+                sphere: None,
+                frame: 0,
+            })
+            .id();
+
+        app.update();
+        let lines = app.world.resource::<DebugLines>();
+        assert!(lines.positions.is_empty());
+        let group = lines.get_group("bounds").unwrap();
+        assert!(group.visible);
+        assert_eq!(group.lines.positions.len(), 24);
+        assert!(group.lines.positions.contains(&[1.0, 1.0, 1.0]));
+
+        // Nothing moved, the group is left as it was rather than drawn again.
+        app.update();
+        let group = app
+            .world
+            .resource::<DebugLines>()
+            .get_group("bounds")
+            .unwrap();
+        assert_eq!(group.lines.positions.len(), 24);
+
+        app.world.get_mut::<SkinnedAabb>(entity).unwrap().local =
+            Aabb::from_min_max(Vec3::ZERO, Vec3::splat(2.0));
+        app.update();
+        let group = app
+            .world
+            .resource::<DebugLines>()
+            .get_group("bounds")
+            .unwrap();
+        assert_eq!(group.lines.positions.len(), 24);
+        assert!(group.lines.positions.contains(&[2.0, 2.0, 2.0]));
+        assert!(!group.lines.positions.contains(&[1.0, 1.0, 1.0]));
+
+        app.world.resource_mut::<SkinnedAabbDebugConfig>().group = None;
+        app.update();
+        let lines = app.world.resource::<DebugLines>();
+        assert!(lines.get_group("bounds").is_none());
+        assert_eq!(lines.positions.len(), 24);
+    }
+}
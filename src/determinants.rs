@@ -0,0 +1,112 @@
+//! Flag the vertices whose blended skinning matrix collapses or flips them, for skinning QA.
+
+use bevy::prelude::*;
+
+use crate::{mesh_joint_indices, mesh_joint_weights, skin_model_normalized};
+
+/// The vertices of a skinned mesh whose blended skinning matrix has a determinant of at most
+/// `threshold` in its current pose: near zero they are collapsed onto a plane, a line or a point,
+/// and below zero they are turned inside out, usually from joints blended across a seam with
+/// opposite rotations.
+///
+/// Insert it on a skinned mesh with the `threshold` to check against, and
+/// [`ComputeSkinnedAabbPlugin`](crate::ComputeSkinnedAabbPlugin) fills in the rest each time the
+/// bounds are computed on the CPU, with the joints in the mesh entity's local space so the
+/// mesh's own scale cancels out. This skins every vertex again.
+#[derive(Component, Clone, Debug, PartialEq)]
+pub struct CollapsedSkin {
+    pub threshold: f32,
+    /// The index of every flagged vertex in the mesh's [`Mesh::ATTRIBUTE_POSITION`].
+    pub vertices: Vec<usize>,
+    /// The smallest determinant of any vertex, `f32::INFINITY` before the mesh is checked.
+    pub min_determinant: f32,
+}
+
+impl CollapsedSkin {
+    pub fn new(threshold: f32) -> Self {
+        Self {
+            threshold,
+            vertices: Vec::new(),
+            min_determinant: f32::INFINITY,
+        }
+    }
+
+    /// Flag the vertices of `determinants` at or below the threshold. Returns `None` if that's
+    /// what is already flagged, so unchanged results don't trigger change detection.
+    pub(crate) fn checked(&self, determinants: &[f32]) -> Option<Self> {
+        let vertices: Vec<usize> = determinants
+            .iter()
+            .enumerate()
+            .filter(|(_, determinant)| **determinant <= self.threshold)
+            .map(|(i, _)| i)
+            .collect();
+        let min_determinant = determinants.iter().copied().fold(f32::INFINITY, f32::min);
+        let changed = vertices != self.vertices || min_determinant != self.min_determinant;
+        changed.then_some(Self {
+            threshold: self.threshold,
+            vertices,
+            min_determinant,
+        })
+    }
+}
+
+impl Default for CollapsedSkin {
+    fn default() -> Self {
+        Self::new(1e-3)
+    }
+}
+
+/// The determinant of the upper 3x3 of the blended skinning matrix of every vertex of `mesh`, in
+/// the order of its positions. Vertices whose weights are all zero are bound to joint 0, as
+/// [`skin_model_normalized`] does. Returns `None` if the mesh is missing skinning attributes.
+pub fn skin_determinants(mesh: &Mesh, joints: &[Mat4]) -> Option<Vec<f32>> {
+    let indices = mesh_joint_indices(mesh)?;
+    let weights = mesh_joint_weights(mesh)?;
+    Some(
+        indices
+            .iter()
+            .zip(weights)
+            .map(|(indices, weights)| {
+                Mat3::from_mat4(skin_model_normalized(joints, indices, weights)).determinant()
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::mesh::PrimitiveTopology;
+
+    use super::*;
+
+    #[test]
+    fn collapsed_and_flipped_vertices() {
+        // On joint 0, halfway between joints 0 and 1, and on joint 1.
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0f32, 0.0, 0.0]; 3]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vec![[0u16, 1, 0, 0]; 3]);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            vec![
+                [1.0f32, 0.0, 0.0, 0.0],
+                [0.5, 0.5, 0.0, 0.0],
+                [0.0, 1.0, 0.0, 0.0],
+            ],
+        );
+        // Joint 1 mirrored along X and scaled up twice along Y: the blend of the two collapses X.
+        let joints = [Mat4::IDENTITY, Mat4::from_scale(Vec3::new(-1.0, 2.0, 1.0))];
+        let determinants = skin_determinants(&mesh, &joints).unwrap();
+        assert_eq!(determinants, vec![1.0, 0.0, -2.0]);
+
+        let checked = CollapsedSkin::default().checked(&determinants).unwrap();
+        assert_eq!(checked.vertices, vec![1, 2]);
+        assert_eq!(checked.min_determinant, -2.0);
+        // Checked again in the same pose, nothing changed.
+        assert!(checked.checked(&determinants).is_none());
+        assert!(checked
+            .checked(&[1.0, 1.0, 1.0])
+            .unwrap()
+            .vertices
+            .is_empty());
+    }
+}
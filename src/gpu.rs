@@ -0,0 +1,950 @@
+//! Skin the vertices and reduce them to an [`Aabb`] in a compute shader.
+//!
+//! The joint matrices are built on the CPU during extraction (it's only one matrix per joint),
+//! the vertices are uploaded once per mesh. Every frame a compute pass writes the bounds of
+//! each skinned mesh into a small buffer that is copied to one of a few readback buffers and
+//! mapped asynchronously once the frame is submitted, so the main world receives the [`Aabb`]
+//! a frame or two after the pose it was computed from, tagged with that frame in
+//! [`SkinnedAabb::frame`](crate::SkinnedAabb::frame). While all the readback buffers of a mesh are
+//! still waiting on the GPU, its bounds aren't recomputed. Until its first readback lands, a mesh
+//! keeps the bind pose [`Aabb`] bevy gave it.
+//!
+//! Each mesh keeps its joints, bounds and readback buffers across frames, they are only created
+//! again when its number of joints changes, or when it's bound to other vertices. The vertices of
+//! a mesh asset that is modified, as hot reloading does, or removed are dropped and uploaded again
+//! the next time they are used.
+
+use std::{
+    num::NonZeroU64,
+    sync::{Arc, Mutex},
+};
+
+use bevy::{
+    asset::HandleId,
+    prelude::*,
+    reflect::TypeUuid,
+    render::{
+        mesh::skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        primitives::Aabb,
+        render_graph::{self, RenderGraph},
+        render_resource::*,
+        renderer::{RenderContext, RenderDevice, RenderQueue},
+        view::VisibilitySystems,
+        Extract, RenderApp, RenderStage,
+    },
+    utils::HashMap,
+};
+use bytemuck::{Pod, Zeroable};
+
+use crate::{
+    build_joint_matrices, joint_indices_in_range, mesh_joint_indices, mesh_joint_weights,
+    mesh_positions, normalize_weights, referenced_joint_count, referenced_vertices,
+    warn_over_max_joints, warn_unbuildable_joints, warn_unreadable_attributes, write_skinned_aabb,
+    AabbCompute, BoundsSkeleton, NoSkinnedAabb, SkinnedAabbConfig, SkinnedAabbFrameCount,
+    SkinnedAabbOutput, Warned, ZeroWeights,
+};
+
+const SKINNED_AABB_SHADER_HANDLE: HandleUntyped =
+    HandleUntyped::weak_from_u64(Shader::TYPE_UUID, 7249043187367315612);
+
+/// The workgroup size the device must support for the GPU path to be used at all, and the
+/// fallback when [`SkinnedAabbConfig::gpu_workgroup_size`] isn't supported.
+const DEFAULT_WORKGROUP_SIZE: u32 = 64;
+
+/// The shared memory each invocation of `skinned_aabb.wgsl` uses, a min and a max `vec3<f32>`
+/// padded to 16 bytes each.
+const WORKGROUP_STORAGE_PER_INVOCATION: u32 = 2 * 16;
+
+/// The workgroup sizes `skinned_aabb.wgsl` can be compiled with.
+const WORKGROUP_SIZES: std::ops::RangeInclusive<u32> = 32..=1024;
+
+/// How the compute shader is dispatched, from [`SkinnedAabbConfig::gpu_workgroup_size`] and
+/// [`SkinnedAabbConfig::gpu_reduction_depth`] checked against the device.
+#[derive(Clone, Copy, Debug)]
+struct GpuTuning {
+    workgroup_size: u32,
+    /// How many values each workgroup merges into the bounds with atomics.
+    atomic_lanes: u32,
+}
+
+impl GpuTuning {
+    fn new(config: &SkinnedAabbConfig, limits: &WgpuLimits) -> Self {
+        let requested = config.gpu_workgroup_size;
+        let workgroup_size = if requested.is_power_of_two()
+            && WORKGROUP_SIZES.contains(&requested)
+            && requested <= limits.max_compute_invocations_per_workgroup
+            && requested <= limits.max_compute_workgroup_size_x
+            && requested * WORKGROUP_STORAGE_PER_INVOCATION
+                <= limits.max_compute_workgroup_storage_size
+        {
+            requested
+        } else {
+            warn!(
+                "SkinnedAabbConfig::gpu_workgroup_size {} isn't a power of two from {} to {} \
+                within the device's limits, using {}",
+                requested,
+                WORKGROUP_SIZES.start(),
+                WORKGROUP_SIZES.end(),
+                DEFAULT_WORKGROUP_SIZE
+            );
+            DEFAULT_WORKGROUP_SIZE
+        };
+        let full_depth = workgroup_size.trailing_zeros();
+        let depth = match config.gpu_reduction_depth {
+            Some(depth) if depth > full_depth => {
+                warn!(
+                    "SkinnedAabbConfig::gpu_reduction_depth {} is deeper than the {} levels of \
+                    a workgroup of {}, using {}",
+                    depth, full_depth, workgroup_size, full_depth
+                );
+                full_depth
+            }
+            Some(depth) => depth,
+            None => full_depth,
+        };
+        GpuTuning {
+            workgroup_size,
+            atomic_lanes: workgroup_size >> depth,
+        }
+    }
+
+    /// `skinned_aabb.wgsl` specialized for these settings.
+    fn shader(&self) -> Shader {
+        Shader::from_wgsl(self.source())
+    }
+
+    /// The source of [`GpuTuning::shader`].
+    fn source(&self) -> String {
+        include_str!("skinned_aabb.wgsl")
+            .replace("{{WORKGROUP_SIZE}}", &self.workgroup_size.to_string())
+            .replace("{{ATOMIC_LANES}}", &self.atomic_lanes.to_string())
+    }
+}
+
+/// min x, y, z followed by max x, y, z, encoded with `to_ordered` in the shader.
+const BOUNDS_SIZE: u64 = 6 * std::mem::size_of::<u32>() as u64;
+const BOUNDS_INIT: [u32; 6] = [u32::MAX, u32::MAX, u32::MAX, 0, 0, 0];
+
+/// How many frames of bounds each mesh can have in flight before it skips a frame.
+const READBACK_COUNT: usize = 3;
+
+/// Computes skinned [`Aabb`]s on the GPU, added by [`crate::ComputeSkinnedAabbPlugin`]
+/// when the `gpu` feature is enabled and the device supports compute shaders.
+pub(crate) struct GpuSkinnedAabbPlugin;
+
+impl Plugin for GpuSkinnedAabbPlugin {
+    fn build(&self, app: &mut App) {
+        let tuning = GpuTuning::new(
+            app.world.resource::<SkinnedAabbConfig>(),
+            &app.world.resource::<RenderDevice>().limits(),
+        );
+        app.world
+            .resource_mut::<Assets<Shader>>()
+            .set_untracked(SKINNED_AABB_SHADER_HANDLE, tuning.shader());
+
+        let results = GpuAabbResults::default();
+        app.insert_resource(results.clone()).add_system_to_stage(
+            CoreStage::PostUpdate,
+            apply_gpu_aabbs
+                .label(crate::SkinnedAabbSystems::UpdateAabbs)
+                .after(VisibilitySystems::CalculateBounds)
+                .before(VisibilitySystems::CheckVisibility),
+        );
+
+        let render_app = match app.get_sub_app_mut(RenderApp) {
+            Ok(render_app) => render_app,
+            Err(_) => return,
+        };
+        render_app
+            .insert_resource(results)
+            .insert_resource(tuning)
+            .init_resource::<SkinnedAabbPipeline>()
+            .init_resource::<ExtractedSkinnedAabbs>()
+            .init_resource::<SkinnedAabbGpuBuffers>()
+            .add_system_to_stage(RenderStage::Extract, extract_skinned_aabbs)
+            .add_system_to_stage(RenderStage::Prepare, prepare_skinned_aabbs)
+            .add_system_to_stage(RenderStage::Cleanup, read_back_skinned_aabbs);
+
+        let mut render_graph = render_app.world.resource_mut::<RenderGraph>();
+        render_graph.add_node(SKINNED_AABB_NODE, SkinnedAabbNode);
+        render_graph
+            .add_node_edge(
+                SKINNED_AABB_NODE,
+                bevy::render::main_graph::node::CAMERA_DRIVER,
+            )
+            .unwrap();
+    }
+}
+
+/// Whether the device can run the skinning compute shader.
+pub(crate) fn supports_compute(render_device: &RenderDevice) -> bool {
+    render_device.limits().max_compute_invocations_per_workgroup >= DEFAULT_WORKGROUP_SIZE
+}
+
+/// Bounds read back from the GPU and the frame of the pose they were computed from,
+/// waiting to be written to their entities in the main world.
+#[derive(Clone, Default)]
+struct GpuAabbResults(Arc<Mutex<Vec<(Entity, Aabb, u32)>>>);
+
+#[repr(C)]
+#[derive(Clone, Copy, Pod, Zeroable)]
+struct GpuSkinVertex {
+    position: [f32; 4],
+    weights: [f32; 4],
+    joints: [u32; 4],
+}
+
+/// One skinned mesh to compute the bounds of this frame.
+struct SkinnedAabbJob {
+    entity: Entity,
+    mesh: HandleId,
+    /// Joint matrices premultiplied by the inverse of the mesh entity's transform
+    /// (and the global pose offset), so the shader outputs bounds in the mesh's local space.
+    joints: Vec<Mat4>,
+    /// The [`SkinnedAabbFrameCount`] of the pose.
+    frame: u32,
+}
+
+#[derive(Default)]
+struct ExtractedSkinnedAabbs {
+    jobs: Vec<SkinnedAabbJob>,
+    /// Vertex data for meshes that don't have a vertex buffer yet, or whose asset was modified.
+    new_meshes: Vec<(HandleId, Vec<GpuSkinVertex>)>,
+    /// Meshes modified or removed since the last extraction, whose vertex buffers are stale.
+    evicted: Vec<HandleId>,
+}
+
+struct GpuSkinVertices {
+    buffer: Buffer,
+    vertex_count: u32,
+}
+
+struct GpuSkinnedAabb {
+    /// The mesh and vertex buffer the bind group was built for, it's built again when the entity's
+    /// mesh changes or its vertices are uploaded again.
+    mesh: HandleId,
+    vertices: BufferId,
+    joints: Buffer,
+    joints_len: usize,
+    bounds: Buffer,
+    readbacks: Vec<Readback>,
+    /// The readback buffer the bounds are copied to this frame,
+    /// `None` if the mesh isn't dispatched.
+    current: Option<usize>,
+    bind_group: BindGroup,
+    workgroups: u32,
+}
+
+struct Readback {
+    buffer: Buffer,
+    /// Set from the copy until the buffer is unmapped.
+    pending: bool,
+    /// Set by the map callback once mapping finished, to whether the buffer can be read.
+    mapped: Arc<Mutex<Option<bool>>>,
+    frame: u32,
+}
+
+impl Readback {
+    fn new(render_device: &RenderDevice) -> Self {
+        Readback {
+            buffer: render_device.create_buffer(&BufferDescriptor {
+                label: Some("skinned_aabb_readback"),
+                size: BOUNDS_SIZE,
+                usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            pending: false,
+            mapped: Arc::new(Mutex::new(None)),
+            frame: 0,
+        }
+    }
+}
+
+#[derive(Default)]
+struct SkinnedAabbGpuBuffers {
+    meshes: HashMap<HandleId, GpuSkinVertices>,
+    entities: HashMap<Entity, GpuSkinnedAabb>,
+}
+
+struct SkinnedAabbPipeline {
+    layout: BindGroupLayout,
+    pipeline: CachedComputePipelineId,
+}
+
+impl FromWorld for SkinnedAabbPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let storage = |binding, read_only| BindGroupLayoutEntry {
+            binding,
+            visibility: ShaderStages::COMPUTE,
+            ty: BindingType::Buffer {
+                ty: BufferBindingType::Storage { read_only },
+                has_dynamic_offset: false,
+                min_binding_size: None,
+            },
+            count: None,
+        };
+        let layout =
+            world
+                .resource::<RenderDevice>()
+                .create_bind_group_layout(&BindGroupLayoutDescriptor {
+                    label: Some("skinned_aabb_bind_group_layout"),
+                    entries: &[storage(0, true), storage(1, true), storage(2, false)],
+                });
+        let pipeline = world
+            .resource_mut::<PipelineCache>()
+            .queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("skinned_aabb_pipeline".into()),
+                layout: Some(vec![layout.clone()]),
+                shader: SKINNED_AABB_SHADER_HANDLE.typed(),
+                shader_defs: vec![],
+                entry_point: "main".into(),
+            });
+        SkinnedAabbPipeline { layout, pipeline }
+    }
+}
+
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn extract_skinned_aabbs(
+    query: Extract<
+        Query<
+            (
+                Entity,
+                &Handle<Mesh>,
+                &SkinnedMesh,
+                &GlobalTransform,
+                Option<&AabbCompute>,
+            ),
+            (Without<NoSkinnedAabb>, Without<BoundsSkeleton>),
+        >,
+    >,
+    meshes: Extract<Res<Assets<Mesh>>>,
+    mut mesh_events: Extract<EventReader<AssetEvent<Mesh>>>,
+    inverse_bindposes: Extract<Res<Assets<SkinnedMeshInverseBindposes>>>,
+    joint_query: Extract<Query<&GlobalTransform>>,
+    config: Extract<Res<SkinnedAabbConfig>>,
+    frame_count: Extract<Res<SkinnedAabbFrameCount>>,
+    buffers: Res<SkinnedAabbGpuBuffers>,
+    mut extracted: ResMut<ExtractedSkinnedAabbs>,
+    mut warned: Local<Warned>,
+    mut joints: Local<Vec<Mat4>>,
+    mut joint_counts: Local<HashMap<HandleId, usize>>,
+) {
+    extracted.jobs.clear();
+    extracted.new_meshes.clear();
+    extracted.evicted.clear();
+    for event in mesh_events.iter() {
+        if let AssetEvent::Modified { handle } | AssetEvent::Removed { handle } = event {
+            extracted.evicted.push(handle.id);
+            joint_counts.remove(&handle.id);
+        }
+    }
+    for (entity, mesh_h, skinned_mesh, transform, mode) in query.iter() {
+        if AabbCompute::resolve(mode, &config, true) != AabbCompute::Gpu {
+            continue;
+        }
+        let mesh = match meshes.get(mesh_h) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let uploaded =
+            buffers.meshes.contains_key(&mesh_h.id) && !extracted.evicted.contains(&mesh_h.id);
+        if !uploaded && !extracted.new_meshes.iter().any(|(id, _)| *id == mesh_h.id) {
+            match gpu_skin_vertices(mesh, config.zero_weights, config.skip_unreferenced_vertices) {
+                Some(vertices) if !vertices.is_empty() => {
+                    extracted.new_meshes.push((mesh_h.id, vertices));
+                }
+                _ => {
+                    warn_unreadable_attributes(entity, mesh, &mut warned.attributes);
+                    continue;
+                }
+            }
+        }
+
+        if build_joint_matrices(skinned_mesh, &inverse_bindposes, &joint_query, &mut joints)
+            .is_none()
+        {
+            warn_unbuildable_joints(
+                entity,
+                skinned_mesh,
+                &inverse_bindposes,
+                &joint_query,
+                &mut warned.unbuildable_joints,
+            );
+            continue;
+        }
+        warned.unbuildable_joints.remove(&entity);
+        warn_over_max_joints(entity, skinned_mesh, &mut warned.max_joints);
+        let joint_count = *joint_counts.entry(mesh_h.id).or_insert_with(|| {
+            mesh_joint_indices(mesh).map_or(0, |indices| referenced_joint_count(&indices))
+        });
+        if !joint_indices_in_range(
+            entity,
+            mesh,
+            &joints,
+            joint_count,
+            &mut warned.out_of_range_joints,
+        ) {
+            continue;
+        }
+        let mut world_to_local = transform.compute_matrix().inverse();
+        if let Some(offset) = config.global_pose_offset {
+            world_to_local *= Mat4::from(offset);
+        }
+        extracted.jobs.push(SkinnedAabbJob {
+            entity,
+            mesh: mesh_h.id,
+            joints: joints.iter().map(|joint| world_to_local * *joint).collect(),
+            frame: frame_count.0,
+        });
+    }
+}
+
+/// Pack the skinning attributes of `mesh` for the compute shader, leaving out the vertices
+/// `zero_weights` doesn't bound, and those no triangle references if `skip_unreferenced` is set.
+/// Read once when the mesh is uploaded.
+fn gpu_skin_vertices(
+    mesh: &Mesh,
+    zero_weights: ZeroWeights,
+    skip_unreferenced: bool,
+) -> Option<Vec<GpuSkinVertex>> {
+    let positions = mesh_positions(mesh)?;
+    let indices = mesh_joint_indices(mesh)?;
+    let weights = mesh_joint_weights(mesh)?;
+    let referenced = referenced_vertices(mesh).filter(|_| skip_unreferenced);
+    Some(
+        positions
+            .iter()
+            .zip(&indices)
+            .zip(weights)
+            .enumerate()
+            .filter(|(i, (_, weights))| {
+                zero_weights.keep(*weights)
+                    && referenced
+                        .as_ref()
+                        .is_none_or(|referenced| referenced.get(*i) != Some(&false))
+            })
+            .map(|(_, ((position, indices), weights))| {
+                let (indices, weights) = normalize_weights(indices, weights);
+                GpuSkinVertex {
+                    position: [position[0], position[1], position[2], 1.0],
+                    weights: weights.to_array(),
+                    joints: indices,
+                }
+            })
+            .collect(),
+    )
+}
+
+fn prepare_skinned_aabbs(
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    pipeline_cache: Res<PipelineCache>,
+    pipeline: Res<SkinnedAabbPipeline>,
+    tuning: Res<GpuTuning>,
+    mut extracted: ResMut<ExtractedSkinnedAabbs>,
+    mut buffers: ResMut<SkinnedAabbGpuBuffers>,
+) {
+    let SkinnedAabbGpuBuffers { meshes, entities } = &mut *buffers;
+
+    for id in extracted.evicted.drain(..) {
+        meshes.remove(&id);
+    }
+    for (id, vertices) in extracted.new_meshes.drain(..) {
+        let buffer = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("skinned_aabb_vertices"),
+            contents: bytemuck::cast_slice(&vertices),
+            usage: BufferUsages::STORAGE,
+        });
+        meshes.insert(
+            id,
+            GpuSkinVertices {
+                buffer,
+                vertex_count: vertices.len() as u32,
+            },
+        );
+    }
+
+    entities.retain(|entity, _| extracted.jobs.iter().any(|job| job.entity == *entity));
+    for gpu in entities.values_mut() {
+        gpu.current = None;
+    }
+    // Nothing is dispatched while the shader is still compiling.
+    if pipeline_cache
+        .get_compute_pipeline(pipeline.pipeline)
+        .is_none()
+    {
+        return;
+    }
+
+    for job in &extracted.jobs {
+        let vertices = match meshes.get(&job.mesh) {
+            Some(vertices) => vertices,
+            None => continue,
+        };
+        let joints_bytes: &[u8] = bytemuck::cast_slice(&job.joints);
+        if let Some(gpu) = entities.get_mut(&job.entity).filter(|gpu| {
+            gpu.mesh == job.mesh
+                && gpu.vertices == vertices.buffer.id()
+                && gpu.joints_len == job.joints.len()
+        }) {
+            let free = match gpu.readbacks.iter().position(|readback| !readback.pending) {
+                Some(free) => free,
+                None if gpu.readbacks.len() < READBACK_COUNT => {
+                    gpu.readbacks.push(Readback::new(&render_device));
+                    gpu.readbacks.len() - 1
+                }
+                // Every readback is still in flight, skip this frame.
+                None => continue,
+            };
+            gpu.readbacks[free].frame = job.frame;
+            gpu.current = Some(free);
+            render_queue.write_buffer(&gpu.joints, 0, joints_bytes);
+            render_queue.write_buffer(&gpu.bounds, 0, bytemuck::cast_slice(&BOUNDS_INIT));
+            continue;
+        }
+
+        let joints = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("skinned_aabb_joints"),
+            contents: joints_bytes,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_DST,
+        });
+        let bounds = render_device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("skinned_aabb_bounds"),
+            contents: bytemuck::cast_slice(&BOUNDS_INIT),
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC | BufferUsages::COPY_DST,
+        });
+        let mut readback = Readback::new(&render_device);
+        readback.frame = job.frame;
+        let bind_group = render_device.create_bind_group(&BindGroupDescriptor {
+            label: Some("skinned_aabb_bind_group"),
+            layout: &pipeline.layout,
+            entries: &[
+                BindGroupEntry {
+                    binding: 0,
+                    resource: vertices.buffer.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 1,
+                    resource: joints.as_entire_binding(),
+                },
+                BindGroupEntry {
+                    binding: 2,
+                    resource: BindingResource::Buffer(BufferBinding {
+                        buffer: &bounds,
+                        offset: 0,
+                        size: NonZeroU64::new(BOUNDS_SIZE),
+                    }),
+                },
+            ],
+        });
+        entities.insert(
+            job.entity,
+            GpuSkinnedAabb {
+                mesh: job.mesh,
+                vertices: vertices.buffer.id(),
+                joints,
+                joints_len: job.joints.len(),
+                bounds,
+                readbacks: vec![readback],
+                current: Some(0),
+                bind_group,
+                workgroups: vertices.vertex_count.div_ceil(tuning.workgroup_size),
+            },
+        );
+    }
+}
+
+const SKINNED_AABB_NODE: &str = "skinned_aabb";
+
+struct SkinnedAabbNode;
+
+impl render_graph::Node for SkinnedAabbNode {
+    fn run(
+        &self,
+        _graph: &mut render_graph::RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), render_graph::NodeRunError> {
+        let pipeline = world.resource::<SkinnedAabbPipeline>();
+        let pipeline = match world
+            .resource::<PipelineCache>()
+            .get_compute_pipeline(pipeline.pipeline)
+        {
+            Some(pipeline) => pipeline,
+            None => return Ok(()),
+        };
+        let buffers = world.resource::<SkinnedAabbGpuBuffers>();
+
+        {
+            let mut pass =
+                render_context
+                    .command_encoder
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("skinned_aabb_pass"),
+                    });
+            pass.set_pipeline(pipeline);
+            for gpu in buffers.entities.values() {
+                if gpu.current.is_some() {
+                    pass.set_bind_group(0, &gpu.bind_group, &[]);
+                    pass.dispatch_workgroups(gpu.workgroups, 1, 1);
+                }
+            }
+        }
+
+        for gpu in buffers.entities.values() {
+            if let Some(current) = gpu.current {
+                render_context.command_encoder.copy_buffer_to_buffer(
+                    &gpu.bounds,
+                    0,
+                    &gpu.readbacks[current].buffer,
+                    0,
+                    BOUNDS_SIZE,
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Start mapping the readback buffers copied to this frame once it has been submitted,
+/// and hand the bounds of every buffer that finished mapping over to the main world.
+/// This never waits on the GPU.
+fn read_back_skinned_aabbs(
+    render_device: Res<RenderDevice>,
+    mut buffers: ResMut<SkinnedAabbGpuBuffers>,
+    results: Res<GpuAabbResults>,
+) {
+    if buffers.entities.is_empty() {
+        return;
+    }
+    for gpu in buffers.entities.values_mut() {
+        if let Some(current) = gpu.current.take() {
+            let readback = &mut gpu.readbacks[current];
+            readback.pending = true;
+            let mapped = readback.mapped.clone();
+            render_device.map_buffer(&readback.buffer.slice(..), MapMode::Read, move |result| {
+                *mapped.lock().unwrap() = Some(result.is_ok());
+            });
+        }
+    }
+    render_device.poll(wgpu::Maintain::Poll);
+
+    let mut results = results.0.lock().unwrap();
+    for (entity, gpu) in &mut buffers.entities {
+        // Oldest first, so the newest bounds are the ones that stick.
+        gpu.readbacks.sort_by_key(|readback| readback.frame);
+        for readback in &mut gpu.readbacks {
+            if !readback.pending {
+                continue;
+            }
+            match readback.mapped.lock().unwrap().take() {
+                Some(true) => {}
+                // The buffer was never mapped, free it for a later frame instead of waiting on it
+                // forever, the mesh keeps its last bounds.
+                Some(false) => {
+                    readback.pending = false;
+                    continue;
+                }
+                None => continue,
+            }
+            {
+                let mapped = readback.buffer.slice(..).get_mapped_range();
+                let bounds: &[u32] = bytemuck::cast_slice(&mapped);
+                let minimum = Vec3::new(
+                    from_ordered(bounds[0]),
+                    from_ordered(bounds[1]),
+                    from_ordered(bounds[2]),
+                );
+                let maximum = Vec3::new(
+                    from_ordered(bounds[3]),
+                    from_ordered(bounds[4]),
+                    from_ordered(bounds[5]),
+                );
+                results.push((
+                    *entity,
+                    Aabb::from_min_max(minimum, maximum),
+                    readback.frame,
+                ));
+            }
+            readback.buffer.unmap();
+            readback.pending = false;
+        }
+    }
+}
+
+/// Inverse of `to_ordered` in the shader.
+fn from_ordered(bits: u32) -> f32 {
+    if bits & 0x8000_0000 != 0 {
+        f32::from_bits(bits & !0x8000_0000)
+    } else {
+        f32::from_bits(!bits)
+    }
+}
+
+/// Write the bounds read back from the GPU to their entities, skipping any that were despawned
+/// or opted out in the meantime, or already got bounds of a later pose, e.g. from the CPU after
+/// switching away from [`AabbCompute::Gpu`](crate::AabbCompute::Gpu).
+#[allow(clippy::type_complexity)]
+fn apply_gpu_aabbs(
+    mut commands: Commands,
+    results: Res<GpuAabbResults>,
+    config: Res<SkinnedAabbConfig>,
+    time: Res<Time>,
+    mut query: Query<SkinnedAabbOutput, (With<SkinnedMesh>, Without<NoSkinnedAabb>)>,
+) {
+    for (entity, new_aabb, frame) in results.0.lock().unwrap().drain(..) {
+        if let Ok(output) = query.get_mut(entity) {
+            if output
+                .skinned_aabb
+                .as_ref()
+                .is_some_and(|skinned_aabb| skinned_aabb.frame > frame)
+            {
+                continue;
+            }
+            write_skinned_aabb(
+                &mut commands,
+                entity,
+                output,
+                new_aabb,
+                None,
+                frame,
+                &config,
+                &time,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        asset::AssetPlugin,
+        render::{mesh::PrimitiveTopology, MainWorld},
+        transform::TransformPlugin,
+    };
+
+    use super::*;
+    use crate::ComputeSkinnedAabbPlugin;
+
+    /// A column of `vertices` points one apart along Y, all bound to joint 0.
+    fn column(vertices: usize) -> Mesh {
+        let positions: Vec<[f32; 3]> = (0..vertices).map(|i| [0.0, i as f32, 0.0]).collect();
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vec![[0u16; 4]; vertices]);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            vec![[1.0f32, 0.0, 0.0, 0.0]; vertices],
+        );
+        mesh
+    }
+
+    /// Run `stage` on `render_world` against the world of `app`, as the render app does.
+    fn extract(app: &mut App, render_world: &mut World, stage: &mut SystemStage) {
+        std::mem::swap(
+            &mut app.world,
+            &mut render_world.resource_mut::<MainWorld>(),
+        );
+        stage.run(render_world);
+        std::mem::swap(
+            &mut app.world,
+            &mut render_world.resource_mut::<MainWorld>(),
+        );
+    }
+
+    #[test]
+    fn modified_mesh_is_extracted_again() {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugin(AssetPlugin)
+            .add_plugin(TransformPlugin)
+            .add_asset::<Mesh>()
+            .add_asset::<SkinnedMeshInverseBindposes>()
+            .add_plugin(ComputeSkinnedAabbPlugin);
+        app.world.resource_mut::<SkinnedAabbConfig>().compute = AabbCompute::Gpu;
+        let mesh = app.world.resource_mut::<Assets<Mesh>>().add(column(10));
+        let inverse_bindposes = app
+            .world
+            .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+            .add(SkinnedMeshInverseBindposes::from(vec![Mat4::IDENTITY]));
+        let joint = app
+            .world
+            .spawn()
+            .insert_bundle(TransformBundle::default())
+            .id();
+        app.world
+            .spawn()
+            .insert_bundle(TransformBundle::default())
+            .insert(mesh.clone())
+            .insert(SkinnedMesh {
+                inverse_bindposes,
+                joints: vec![joint],
+            });
+        app.update();
+
+        let mut render_world = World::new();
+        render_world.init_resource::<MainWorld>();
+        render_world.init_resource::<SkinnedAabbGpuBuffers>();
+        render_world.init_resource::<ExtractedSkinnedAabbs>();
+        let mut stage = SystemStage::single(extract_skinned_aabbs);
+        extract(&mut app, &mut render_world, &mut stage);
+        let extracted = render_world.resource::<ExtractedSkinnedAabbs>();
+        assert!(extracted.evicted.is_empty());
+        assert_eq!(extracted.new_meshes.len(), 1);
+        assert_eq!(extracted.new_meshes[0].1.len(), 10);
+
+        // Replaced in place, as hot reloading does.
+        *app.world
+            .resource_mut::<Assets<Mesh>>()
+            .get_mut(&mesh)
+            .unwrap() = column(4);
+        app.update();
+        extract(&mut app, &mut render_world, &mut stage);
+        let extracted = render_world.resource::<ExtractedSkinnedAabbs>();
+        assert_eq!(extracted.evicted, vec![mesh.id]);
+        assert_eq!(extracted.new_meshes.len(), 1);
+        assert_eq!(extracted.new_meshes[0].0, mesh.id);
+        assert_eq!(extracted.new_meshes[0].1.len(), 4);
+        assert_eq!(extracted.jobs.len(), 1);
+    }
+
+    /// The tuning of a workgroup of `size` reduced `depth` levels in shared memory.
+    fn tuning(size: u32, depth: Option<u32>, limits: &WgpuLimits) -> GpuTuning {
+        let config = SkinnedAabbConfig {
+            gpu_workgroup_size: size,
+            gpu_reduction_depth: depth,
+            ..default()
+        };
+        GpuTuning::new(&config, limits)
+    }
+
+    #[test]
+    fn tuning_is_validated() {
+        // 256 invocations per workgroup at most.
+        let limits = WgpuLimits::default();
+        let lanes = |tuning: GpuTuning| (tuning.workgroup_size, tuning.atomic_lanes);
+        assert_eq!(lanes(tuning(128, None, &limits)), (128, 1));
+        assert_eq!(lanes(tuning(128, Some(2), &limits)), (128, 32));
+        assert_eq!(lanes(tuning(128, Some(0), &limits)), (128, 128));
+        // Deeper than the workgroup, reduced all the way.
+        assert_eq!(lanes(tuning(128, Some(10), &limits)), (128, 1));
+        // Not a power of two, too small, or past the device's limits.
+        for size in [100, 16, 512] {
+            assert_eq!(
+                lanes(tuning(size, None, &limits)),
+                (DEFAULT_WORKGROUP_SIZE, 1)
+            );
+        }
+    }
+
+    #[test]
+    fn every_tuning_compiles() {
+        let limits = WgpuLimits {
+            max_compute_invocations_per_workgroup: 1024,
+            max_compute_workgroup_size_x: 1024,
+            max_compute_workgroup_storage_size: 32768,
+            ..default()
+        };
+        for size in WORKGROUP_SIZES.filter(|size| size.is_power_of_two()) {
+            for depth in 0..=size.trailing_zeros() {
+                let tuning = tuning(size, Some(depth), &limits);
+                assert_eq!(tuning.workgroup_size, size);
+                if let Err(error) = ProcessedShader::Wgsl(tuning.source().into())
+                    .reflect(bevy::render::settings::WgpuFeatures::empty())
+                {
+                    panic!("workgroup of {} at depth {}: {:?}", size, depth, error);
+                }
+            }
+        }
+    }
+
+    /// `to_ordered` in the shader.
+    fn to_ordered(value: f32) -> u32 {
+        let bits = value.to_bits();
+        if bits & 0x8000_0000 != 0 {
+            !bits
+        } else {
+            bits | 0x8000_0000
+        }
+    }
+
+    /// The shader's reduction of `positions` run on the CPU with `tuning`, one workgroup at a
+    /// time: each invocation's vertex, or the empty bounds past the last one, halved in shared
+    /// memory down to `atomic_lanes` values merged into the bounds.
+    fn reduce_like_the_shader(positions: &[Vec3], tuning: &GpuTuning) -> Aabb {
+        let size = tuning.workgroup_size as usize;
+        let lanes = tuning.atomic_lanes as usize;
+        let mut bounds = BOUNDS_INIT;
+        for workgroup in 0..positions.len().div_ceil(size) {
+            let (mut local_min, mut local_max): (Vec<Vec3>, Vec<Vec3>) = (0..size)
+                .map(
+                    |local_index| match positions.get(workgroup * size + local_index) {
+                        Some(position) => (*position, *position),
+                        None => (Vec3::splat(f32::MAX), Vec3::splat(-f32::MAX)),
+                    },
+                )
+                .unzip();
+            let mut stride = size / 2;
+            while stride >= lanes {
+                for local_index in 0..stride {
+                    local_min[local_index] =
+                        local_min[local_index].min(local_min[local_index + stride]);
+                    local_max[local_index] =
+                        local_max[local_index].max(local_max[local_index + stride]);
+                }
+                stride /= 2;
+            }
+            for local_index in 0..lanes {
+                for axis in 0..3 {
+                    bounds[axis] = bounds[axis].min(to_ordered(local_min[local_index][axis]));
+                    bounds[axis + 3] =
+                        bounds[axis + 3].max(to_ordered(local_max[local_index][axis]));
+                }
+            }
+        }
+        let corner = |bits: &[u32]| {
+            Vec3::new(
+                from_ordered(bits[0]),
+                from_ordered(bits[1]),
+                from_ordered(bits[2]),
+            )
+        };
+        Aabb::from_min_max(corner(&bounds[..3]), corner(&bounds[3..]))
+    }
+
+    #[test]
+    fn every_tuning_reduces_to_the_same_bounds() {
+        let limits = WgpuLimits {
+            max_compute_invocations_per_workgroup: 1024,
+            max_compute_workgroup_size_x: 1024,
+            max_compute_workgroup_storage_size: 32768,
+            ..default()
+        };
+        // Not a multiple of any workgroup size, so the last workgroup is partly empty, and
+        // spread on both sides of zero on every axis.
+        let positions: Vec<Vec3> = (0..1500)
+            .map(|i| {
+                let i = i as f32;
+                Vec3::new(
+                    (i * 0.37).sin() * 5.0,
+                    (i * 0.11).cos() * 3.0 - 1.0,
+                    i * 0.01 - 7.0,
+                )
+            })
+            .collect();
+        let expected = crate::compute_aabb(&positions).unwrap();
+        for size in WORKGROUP_SIZES.filter(|size| size.is_power_of_two()) {
+            for depth in 0..=size.trailing_zeros() {
+                let tuning = tuning(size, Some(depth), &limits);
+                let aabb = reduce_like_the_shader(&positions, &tuning);
+                assert_eq!(
+                    (aabb.min(), aabb.max()),
+                    (expected.min(), expected.max()),
+                    "workgroup of {} at depth {}",
+                    size,
+                    depth
+                );
+            }
+        }
+    }
+}
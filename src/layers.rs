@@ -0,0 +1,135 @@
+//! Bounds per [`RenderLayers`] layer, for multi view setups where each layer shows a different part
+//! of the same skinned mesh.
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{
+            skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+            MeshVertexAttribute, VertexAttributeValues,
+        },
+        primitives::Aabb,
+        render_resource::VertexFormat,
+        view::RenderLayers,
+    },
+    utils::{HashMap, HashSet},
+};
+
+use crate::{
+    build_joint_matrices, compute_aabb, joint_indices_in_range, mesh_joint_indices,
+    referenced_joint_count, skin_vertices, SkinnedAabb, SkinnedAabbConfig, SkinnedAabbSystems,
+};
+
+/// The bounds of a skinned mesh for each layer of its [`RenderLayers`] (layer 0 without one),
+/// in its local space like its [`Aabb`]. Insert it on a skinned mesh to have
+/// [`ComputeSkinnedAabbPlugin`](crate::ComputeSkinnedAabbPlugin) fill it in whenever its
+/// [`SkinnedAabb`] moves.
+///
+/// Each layer is bounded over the vertices whose [`ATTRIBUTE_RENDER_LAYERS`] mask has it, every
+/// vertex if the mesh doesn't have the attribute. Layers with no vertex are left out.
+/// Computed on the CPU, skinning every vertex again.
+#[derive(Component, Clone, Debug, Default)]
+pub struct PerLayerAabb(pub HashMap<u8, Aabb>);
+
+/// The [`RenderLayers`] each vertex belongs to, as a bit mask with bit `n` for layer `n`.
+pub const ATTRIBUTE_RENDER_LAYERS: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_RenderLayers", 2_843_115_010, VertexFormat::Uint32);
+
+pub(crate) struct PerLayerAabbPlugin;
+
+impl Plugin for PerLayerAabbPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            update_per_layer_aabbs.after(SkinnedAabbSystems::UpdateAabbs),
+        );
+    }
+}
+
+/// Bound the vertices of each layer of every skinned mesh with a [`PerLayerAabb`].
+#[allow(clippy::type_complexity)]
+fn update_per_layer_aabbs(
+    mut query: Query<
+        (
+            Entity,
+            &Handle<Mesh>,
+            &SkinnedMesh,
+            &GlobalTransform,
+            Option<&RenderLayers>,
+            &mut PerLayerAabb,
+        ),
+        (
+            With<SkinnedAabb>,
+            Or<(
+                Changed<SkinnedAabb>,
+                Added<PerLayerAabb>,
+                Changed<RenderLayers>,
+            )>,
+        ),
+    >,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    joint_query: Query<&GlobalTransform>,
+    config: Res<SkinnedAabbConfig>,
+    mut joints: Local<Vec<Mat4>>,
+    mut warned: Local<HashSet<Entity>>,
+) {
+    for (entity, mesh_h, skinned_mesh, transform, render_layers, mut per_layer_aabb) in &mut query {
+        let mesh = match meshes.get(mesh_h) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        if build_joint_matrices(skinned_mesh, &inverse_bindposes, &joint_query, &mut joints)
+            .is_none()
+        {
+            continue;
+        }
+        let joint_count =
+            mesh_joint_indices(mesh).map_or(0, |indices| referenced_joint_count(&indices));
+        if !joint_indices_in_range(entity, mesh, &joints, joint_count, &mut warned) {
+            continue;
+        }
+        let mut world_to_local = transform.compute_matrix().inverse();
+        if let Some(offset) = config.global_pose_offset {
+            world_to_local *= Mat4::from(offset);
+        }
+        for joint in joints.iter_mut() {
+            *joint = world_to_local * *joint;
+        }
+        let (ls_positions, weights) = match (
+            skin_vertices(mesh, &joints),
+            mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT),
+        ) {
+            (Some(ls_positions), Some(VertexAttributeValues::Float32x4(weights))) => {
+                (ls_positions, weights)
+            }
+            _ => continue,
+        };
+        let masks = match mesh.attribute(ATTRIBUTE_RENDER_LAYERS) {
+            Some(VertexAttributeValues::Uint32(masks)) => Some(masks),
+            _ => None,
+        };
+
+        per_layer_aabb.0.clear();
+        let mut layer_positions = Vec::new();
+        for layer in render_layers.copied().unwrap_or_default().iter() {
+            layer_positions.clear();
+            layer_positions.extend(
+                ls_positions
+                    .iter()
+                    .zip(weights)
+                    .enumerate()
+                    .filter(|(i, (_, weights))| {
+                        config.zero_weights.keep(Vec4::from(**weights))
+                            && masks.is_none_or(|masks| {
+                                masks.get(*i).is_some_and(|mask| mask & (1 << layer) != 0)
+                            })
+                    })
+                    .map(|(_, (position, _))| *position),
+            );
+            if let Some(aabb) = compute_aabb(&layer_positions) {
+                per_layer_aabb.0.insert(layer, aabb);
+            }
+        }
+    }
+}
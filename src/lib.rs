@@ -0,0 +1,3384 @@
+//! Compute the Axis-Aligned Bounding Box of skinned meshes from their current pose,
+//! so they are frustum culled against where they actually are instead of their bind pose.
+
+use std::sync::Arc;
+
+use bevy::{
+    asset::HandleId,
+    ecs::{query::WorldQuery, schedule::ShouldRun, system::SystemParam},
+    math::{Affine3A, Vec3A},
+    prelude::*,
+    render::{
+        mesh::{
+            skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+            MeshVertexAttribute, VertexAttributeValues,
+        },
+        primitives::{Aabb, Sphere},
+        render_resource::VertexFormat,
+        view::VisibilitySystems,
+    },
+    tasks::ComputeTaskPool,
+    transform::TransformSystem,
+    utils::{HashMap, HashSet},
+};
+
+use tasks::{AabbJob, AabbTasks};
+
+mod animation_aabb;
+mod auto_weights;
+mod bounds_skeleton;
+mod cameras;
+mod changed;
+mod closest_point;
+mod combined;
+mod curve;
+mod debug;
+mod determinants;
+#[cfg(feature = "gpu")]
+mod gpu;
+mod layers;
+mod metrics;
+mod morph;
+mod obb;
+mod positions;
+mod proxy;
+mod self_intersection;
+mod sphere;
+mod stream;
+mod tasks;
+
+pub use animation_aabb::AnimationAabb;
+pub use auto_weights::compute_auto_weights;
+pub use bounds_skeleton::BoundsSkeleton;
+pub use cameras::VisibleToCameras;
+pub use changed::{SkinnedAabbChangeThreshold, SkinnedAabbChanged};
+pub use closest_point::{closest_point_on_triangles, SkinnedSurface};
+pub use combined::CombinedSkinnedAabb;
+pub use curve::CurveSkin;
+pub use debug::{
+    debug_enabled, DebugColor, DebugLineGroup, DebugLines, JointColors, SkinnedAabbDebug,
+    SkinnedAabbDebugBundle, SkinnedAabbDebugConfig, SkinnedAabbDebugPlugin,
+};
+pub use determinants::{skin_determinants, CollapsedSkin};
+pub use layers::{PerLayerAabb, ATTRIBUTE_RENDER_LAYERS};
+pub use metrics::{
+    bounding_volume_tightness, centered_bounding_sphere, SkinnedAabbMetrics, VolumeKind,
+};
+pub use morph::{MorphTargets, MorphWeights};
+pub use obb::{compute_obb, compute_obb_in_frame, ObbAxes, SkinnedObb};
+pub use positions::{SkinnedVertexPositions, StoreSkinnedPositions};
+pub use proxy::generate_skin_proxy;
+pub use self_intersection::{mesh_self_intersects, SelfIntersecting};
+pub use sphere::compute_bounding_sphere;
+pub use stream::{
+    SkinnedAabbFrame, SkinnedAabbSink, SkinnedAabbStream, SkinnedAabbStreamPlugin, StreamedAabb,
+};
+
+/// Keeps the [`Aabb`] of every skinned mesh in sync with its animated pose.
+///
+/// The bounds are updated in [`CoreStage::PostUpdate`], once the joints' [`GlobalTransform`]s
+/// have been propagated and before [`VisibilitySystems::CheckVisibility`],
+/// so frustum culling uses the pose of the current frame.
+///
+/// With the `gpu` feature, meshes using [`AabbCompute::Gpu`] are skinned in a compute shader
+/// instead, and their bounds lag a frame or more behind the pose while they are read back.
+/// The CPU path is used when the feature is disabled, or when there is no render device
+/// or it doesn't support compute shaders.
+///
+/// Every entity with a [`SkinnedMesh`] is handled, including those spawned later by a glTF scene.
+/// A mesh whose asset isn't loaded yet is retried every frame until it is.
+///
+/// On the CPU it only needs the [`Assets`] of [`Mesh`] and [`SkinnedMeshInverseBindposes`], the
+/// [`GlobalTransform`]s of the joints and [`Time`], so it also runs headless with
+/// `MinimalPlugins`, `AssetPlugin` and `TransformPlugin`, as in the crate's `tests/simple_skin.rs`.
+///
+/// The vertices are skinned from their [`Mesh::ATTRIBUTE_POSITION`]. Bevy 0.8 doesn't load or
+/// render morph targets, so blend shapes are applied on the CPU with [`MorphTargets`] and
+/// [`MorphWeights`], which rewrite those positions before the mesh is bounded. A mesh whose
+/// weights changed is bounded that same frame, whatever its [`AabbUpdateMode`].
+/// Shapes blended by rewriting the positions of the mesh asset some other way are picked up too,
+/// but only on the next frame once the modified mesh is cached again.
+pub struct ComputeSkinnedAabbPlugin;
+
+/// Labels for the systems added by [`ComputeSkinnedAabbPlugin`].
+#[derive(Debug, Hash, PartialEq, Eq, Clone, SystemLabel)]
+pub enum SkinnedAabbSystems {
+    /// Writes the skinned [`Aabb`] of each skinned mesh.
+    UpdateAabbs,
+    /// Sends [`SkinnedAabbChanged`] events, after [`SkinnedAabbSystems::UpdateAabbs`].
+    SendChangedEvents,
+}
+
+impl Plugin for ComputeSkinnedAabbPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkinnedAabbConfig>()
+            .init_resource::<SkinnedAabbFrameCount>()
+            .add_system_to_stage(CoreStage::First, count_frames)
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                cache_skinned_mesh_data.with_run_criteria(any_skinned_meshes),
+            );
+
+        #[cfg(feature = "gpu")]
+        if app
+            .world
+            .get_resource::<bevy::render::renderer::RenderDevice>()
+            .is_some_and(gpu::supports_compute)
+        {
+            app.insert_resource(GpuAabbEnabled)
+                .add_plugin(gpu::GpuSkinnedAabbPlugin);
+        }
+
+        app.init_resource::<AabbTasks>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                tasks::apply_aabb_tasks
+                    .label(SkinnedAabbSystems::UpdateAabbs)
+                    .after(VisibilitySystems::CalculateBounds)
+                    .before(update_skinned_aabbs),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                update_skinned_aabbs
+                    .with_run_criteria(any_skinned_meshes)
+                    .label(SkinnedAabbSystems::UpdateAabbs)
+                    .after(TransformSystem::TransformPropagate)
+                    // Runs after bevy inserts the bind pose `Aabb` so ours is applied last.
+                    .after(VisibilitySystems::CalculateBounds)
+                    .before(VisibilitySystems::CheckVisibility),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                curve::update_curve_skinned_aabbs
+                    .with_run_criteria(curve::any_curve_skins)
+                    .label(SkinnedAabbSystems::UpdateAabbs)
+                    .after(VisibilitySystems::CalculateBounds)
+                    .before(VisibilitySystems::CheckVisibility),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                bounds_skeleton::update_bounds_skeleton_aabbs
+                    .with_run_criteria(any_skinned_meshes)
+                    .label(SkinnedAabbSystems::UpdateAabbs)
+                    .after(TransformSystem::TransformPropagate)
+                    .after(VisibilitySystems::CalculateBounds)
+                    .before(VisibilitySystems::CheckVisibility),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                animation_aabb::bake_animation_aabbs
+                    .with_run_criteria(any_skinned_meshes)
+                    .label(SkinnedAabbSystems::UpdateAabbs)
+                    // The last bounds of a finished bake win over the ones computed this frame.
+                    .after(update_skinned_aabbs)
+                    .before(VisibilitySystems::CheckVisibility),
+            )
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                positions::store_skinned_positions
+                    .with_run_criteria(any_skinned_meshes)
+                    .after(TransformSystem::TransformPropagate),
+            )
+            .add_plugin(cameras::VisibleToCamerasPlugin)
+            .add_plugin(combined::CombinedSkinnedAabbPlugin)
+            .add_plugin(layers::PerLayerAabbPlugin)
+            .add_plugin(morph::MorphTargetsPlugin)
+            .add_event::<SkinnedAabbChanged>()
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                changed::send_skinned_aabb_changed
+                    .label(SkinnedAabbSystems::SendChangedEvents)
+                    .after(SkinnedAabbSystems::UpdateAabbs),
+            );
+    }
+}
+
+/// Run a system only while some [`SkinnedMesh`] doesn't opt out with [`NoSkinnedAabb`], so the
+/// plugin costs nothing in apps without skinned meshes, or before they are spawned.
+pub fn any_skinned_meshes(
+    query: Query<(), (With<SkinnedMesh>, Without<NoSkinnedAabb>)>,
+) -> ShouldRun {
+    if query.is_empty() {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+/// Settings for [`ComputeSkinnedAabbPlugin`], shared by every skinned mesh.
+#[derive(Clone, Debug)]
+pub struct SkinnedAabbConfig {
+    /// Where the bounds are computed, unless overridden by an [`AabbCompute`] component.
+    /// Defaults to [`AabbCompute::Gpu`] with the `gpu` feature, [`AabbCompute::Cpu`] otherwise.
+    pub compute: AabbCompute,
+    /// How the bounds are computed on the CPU, unless overridden by an [`AabbQuality`] component.
+    pub quality: AabbQuality,
+    /// An extra transform applied on top of every joint matrix before skinning,
+    /// e.g. to preview a whole character somewhere else without moving its entity.
+    /// It is applied in world space, after the joints' own transforms.
+    pub global_pose_offset: Option<Affine3A>,
+    /// Only ever grow the bounds of each mesh, so a stale update can never leave it under-bounded.
+    /// The union of every bound computed so far is kept in [`MonotonicAabb`]
+    /// until it is cleared with [`reset_bounds`].
+    pub monotonic: bool,
+    /// Keep the bounds of each mesh from shrinking right away as it animates, so it doesn't pop
+    /// at the edges of the screen and `Changed<Aabb>` settles. The default never holds them back.
+    pub hysteresis: AabbHysteresis,
+    /// Test the skinned triangles of each mesh against each other and write the result to
+    /// [`SelfIntersecting`]. This is expensive, and only done on the CPU.
+    pub detect_self_intersection: bool,
+    /// How often the bounds are recomputed, unless overridden by an [`AabbUpdateMode`] component.
+    pub update_mode: AabbUpdateMode,
+    /// Don't recompute the bounds of meshes that are hidden or were culled on the last frame,
+    /// unless overridden by an [`AabbSkipHidden`] component. Disabled by default, as culling
+    /// relies on those bounds, see [`AabbSkipHidden`].
+    pub skip_hidden: bool,
+    /// Measure how tightly a bounding volume of this kind fits the skinned vertices of each
+    /// mesh, written to [`SkinnedAabbMetrics`]. This skins every vertex again on the CPU.
+    pub metrics: Option<VolumeKind>,
+    /// How far the center or half extents of a mesh's local or world bounds have to move before
+    /// its [`SkinnedAabb`] is written again, so `Changed<SkinnedAabb>` only fires on real changes.
+    pub change_epsilon: f32,
+    /// How far any element of the transform of a joint, or of the mesh entity, has to move
+    /// before an [`AabbUpdateMode::OnJointChange`] mesh is updated, compared against the pose
+    /// its bounds were last computed from.
+    pub joint_epsilon: f32,
+    /// Meshes with more (sampled) vertices than this are bounded in parallel on the
+    /// [`ComputeTaskPool`], in chunks of this many vertices. Smaller meshes are bounded on the
+    /// calling thread, where spawning tasks would cost more than it saves.
+    pub parallel_chunk_size: usize,
+    /// The workgroup size of the `gpu` compute shader, a power of two from 32 to 1024 within the
+    /// device's limits (usually at most 256 or 512 for the shared memory each invocation needs),
+    /// 64 otherwise. Larger workgroups issue fewer atomics but synchronize more
+    /// invocations, the best size depends on the GPU. Read when the plugin is built.
+    pub gpu_workgroup_size: u32,
+    /// How many halving steps each workgroup of the `gpu` compute shader reduces its vertices by
+    /// in shared memory before merging the rest into the bounds with atomics, from 0 (one atomic
+    /// per vertex) to the log2 of [`SkinnedAabbConfig::gpu_workgroup_size`] (one per workgroup),
+    /// which `None` defaults to. Read when the plugin is built.
+    pub gpu_reduction_depth: Option<u32>,
+    /// Also compute a bounding sphere of the skinned vertices, written to
+    /// [`SkinnedAabb::sphere`]. This skins every vertex again on the CPU.
+    pub bounding_sphere: bool,
+    /// How far the center or half extents of a mesh's [`Aabb`] have to move, as a fraction of
+    /// its largest half extent, before a [`SkinnedAabbChanged`] is sent for it. Overridden by a
+    /// [`SkinnedAabbChangeThreshold`] component.
+    pub changed_event_threshold: f32,
+    /// What to do with vertices whose joint weights are all zero.
+    pub zero_weights: ZeroWeights,
+    /// How the joints of each vertex are blended, unless overridden by a [`SkinningMethod`]
+    /// component. Defaults to [`SkinningMethod::LinearBlend`], like bevy's renderer.
+    pub skinning: SkinningMethod,
+    /// Quantize the joint weights of the [`SkinnedMeshCpuData`] cached for each mesh, to measure
+    /// how much a compact runtime weight format would change the bounds. Defaults to
+    /// [`WeightPrecision::Full`]. The meshes skinned without the cache, or on the GPU, keep their
+    /// weights as stored.
+    pub weight_precision: WeightPrecision,
+    /// Leave the vertices no triangle of an indexed mesh references out of the bounds, as exports
+    /// often leave orphaned vertices at the origin or far away. They are left out when the
+    /// [`SkinnedMeshCpuData`], or the vertices for [`AabbCompute::Gpu`], are cached, so it costs
+    /// nothing per frame, but the meshes skinned without the cache still bound every vertex.
+    /// Meshes without indices are unaffected. Defaults to `true`.
+    pub skip_unreferenced_vertices: bool,
+    /// Snap the local bounds of every mesh outwards to multiples of `1 / fixed_point_scale`, as
+    /// [`fixed_point_bounds`] computes them, so lockstep simulations get the same bounds on every
+    /// platform as long as the skinned positions match. Disabled with `None`, the default.
+    ///
+    /// The scale trades precision for range: the bounds are within `1 / scale` of the vertices
+    /// and clamped to `i32::MAX / scale` from the origin, and are only exactly representable as
+    /// `f32` up to `2^24 / scale`. A scale of 1000 gives millimetres up to about 16 km.
+    pub fixed_point_scale: Option<f32>,
+    /// Only bound this fraction of the skinned vertices, from 0 to 1, leaving out those farthest
+    /// from their centroid, see [`compute_aabb_percentile`]. Disabled with `None`, the default.
+    ///
+    /// This rejects stray vertices and broken weights that would balloon the bounds, but the
+    /// bounds no longer contain the whole mesh, so it can be culled while some of it is on screen.
+    /// Not applied to [`AabbQuality::PerJoint`] or on the GPU. Other meshes are bounded on the
+    /// calling thread, not in parallel or with [`AabbCompute::Async`].
+    pub percentile: Option<f32>,
+    /// When a joint of a mesh is despawned or loses its [`GlobalTransform`], its bounds stop
+    /// being updated. With `Some`, the half extents of its last bounds are scaled by this factor
+    /// once, before its [`SkinnedAabbMargin`] and [`AabbPadding`] are added back, so it's less
+    /// likely to be culled while on screen. Disabled with `None`, the default.
+    /// Not applied to [`AabbCompute::Gpu`] meshes.
+    pub broken_joints_inflation: Option<f32>,
+    /// The most vertices skinned on the CPU per frame, over every mesh, or `None` for no limit.
+    /// Meshes that don't fit wait for a later frame, keeping their previous bounds (with the
+    /// [`SkinnedAabb::frame`] they were computed on) and staying due until they are computed.
+    /// Meshes that were never computed go first, then those computed the longest ago. The first
+    /// mesh of each frame is computed whatever its size.
+    pub vertex_budget: Option<usize>,
+    /// How many clip samples are skinned per frame, over every [`AnimationAabb`] being baked.
+    pub animation_samples_per_frame: usize,
+    /// Grow every half extent of the bounds to at least this much, so a mesh collapsed to a
+    /// point or a plane (e.g. a single vertex) doesn't get a zero sized [`Aabb`] that padding or
+    /// anything dividing by its size chokes on. Disabled with `None`, the default.
+    pub min_half_extent: Option<f32>,
+}
+
+impl Default for SkinnedAabbConfig {
+    fn default() -> Self {
+        Self {
+            compute: if cfg!(feature = "gpu") {
+                AabbCompute::Gpu
+            } else {
+                AabbCompute::Cpu
+            },
+            quality: AabbQuality::default(),
+            global_pose_offset: None,
+            monotonic: false,
+            hysteresis: AabbHysteresis::default(),
+            detect_self_intersection: false,
+            update_mode: AabbUpdateMode::default(),
+            skip_hidden: false,
+            metrics: None,
+            change_epsilon: 1e-4,
+            joint_epsilon: 1e-5,
+            parallel_chunk_size: DEFAULT_PARALLEL_CHUNK_SIZE,
+            gpu_workgroup_size: 64,
+            gpu_reduction_depth: None,
+            bounding_sphere: false,
+            changed_event_threshold: 0.05,
+            zero_weights: ZeroWeights::default(),
+            skinning: SkinningMethod::default(),
+            weight_precision: WeightPrecision::default(),
+            skip_unreferenced_vertices: true,
+            animation_samples_per_frame: 32,
+            vertex_budget: None,
+            fixed_point_scale: None,
+            percentile: None,
+            broken_joints_inflation: None,
+            min_half_extent: None,
+        }
+    }
+}
+
+/// How vertices whose joint weights sum to zero are bounded. Weights that don't sum to zero are
+/// always scaled to sum to 1, see [`normalize_weights`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ZeroWeights {
+    /// Leave them out of the bounds, bevy's shader collapses them to the origin.
+    #[default]
+    Skip,
+    /// Bound them as if rigidly bound to joint 0.
+    BindToJointZero,
+}
+
+impl ZeroWeights {
+    /// Whether a vertex with these weights is bounded.
+    pub(crate) fn keep(self, weights: Vec4) -> bool {
+        self == ZeroWeights::BindToJointZero || !is_unweighted(weights)
+    }
+}
+
+/// The precision the joint weights are skinned with, see [`SkinnedAabbConfig::weight_precision`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum WeightPrecision {
+    /// The weights as read from the mesh.
+    #[default]
+    Full,
+    /// Weights normalized and rounded to multiples of 1/255, as stored in `Unorm8x4`.
+    Quantized8,
+    /// Weights normalized and rounded to multiples of 1/65535, as stored in `Unorm16x4`.
+    Quantized16,
+}
+
+impl WeightPrecision {
+    /// Round `weights` to this precision. They are normalized first, and the rounding error is
+    /// given to the largest weight so they still sum to exactly 1, as quantizing exporters do.
+    /// Weights summing to zero are left as they are.
+    pub fn quantize(self, weights: Vec4) -> Vec4 {
+        let steps = match self {
+            WeightPrecision::Full => return weights,
+            WeightPrecision::Quantized8 => u8::MAX as f32,
+            WeightPrecision::Quantized16 => u16::MAX as f32,
+        };
+        if is_unweighted(weights) {
+            return weights;
+        }
+        let mut quantized = (weights / weights.dot(Vec4::ONE) * steps).round();
+        let largest = (0..4)
+            .max_by(|a, b| quantized[*a].total_cmp(&quantized[*b]))
+            .unwrap_or(0);
+        quantized[largest] += steps - quantized.dot(Vec4::ONE);
+        quantized / steps
+    }
+}
+
+/// Whether joint weights sum to zero, see [`normalize_weights`].
+fn is_unweighted(weights: Vec4) -> bool {
+    weights.dot(Vec4::ONE).abs() <= f32::EPSILON
+}
+
+/// How often the bounds of a skinned mesh are recomputed on the CPU.
+/// Insert it on a skinned mesh to override [`SkinnedAabbConfig::update_mode`].
+/// Every mode computes the bounds on the first frame a mesh is seen, and again whenever its mesh
+/// or the [`SkinnedAabbConfig`] changes. The GPU path always updates every frame.
+///
+/// Between updates the last bounds are kept, so with [`AabbUpdateMode::Interval`] and
+/// [`AabbUpdateMode::FixedHz`] they lag the pose by up to the interval, and a fast moving mesh
+/// can be culled while on screen.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub enum AabbUpdateMode {
+    EveryFrame,
+    /// Update once every this many frames. Meshes are spread over the interval by their
+    /// entity id, so they don't all update on the same frame.
+    Interval(u32),
+    /// Update this many times per second of [`Time`], at most once per frame, so the rate
+    /// doesn't depend on the frame rate. Meshes are spread over the period by their entity id.
+    FixedHz(f32),
+    /// Only update when a joint, or the mesh entity itself, has moved by more than
+    /// [`SkinnedAabbConfig::joint_epsilon`] since the bounds were last computed, its inverse
+    /// bindposes were modified or a component setting how it's bounded, such as an
+    /// [`AabbQuality`], changed, so idle meshes cost nothing.
+    #[default]
+    OnJointChange,
+}
+
+impl AabbUpdateMode {
+    /// Whether the bounds of `entity` are due this frame, before accounting for joint changes.
+    pub(crate) fn interval_due(interval: u32, entity: Entity, frame: u32) -> bool {
+        let offset = entity.id().wrapping_mul(0x9E37_79B9);
+        interval <= 1 || frame.wrapping_add(offset).is_multiple_of(interval)
+    }
+
+    /// Whether the bounds of `entity` are due on the frame ending `elapsed` seconds after startup.
+    pub(crate) fn fixed_hz_due(hz: f32, entity: Entity, elapsed: f64, delta: f64) -> bool {
+        if hz <= 0.0 {
+            return false;
+        }
+        let phase = f64::from(entity.id().wrapping_mul(0x9E37_79B9)) / f64::from(u32::MAX);
+        let tick = |seconds: f64| (seconds * f64::from(hz) + phase).floor();
+        tick(elapsed) != tick(elapsed - delta)
+    }
+}
+
+/// Whether the bounds of a skinned mesh stop being recomputed while it's hidden, overriding
+/// [`SkinnedAabbConfig::skip_hidden`]. A mesh counts as hidden when its [`Visibility`] is, or
+/// when its [`ComputedVisibility`] says it wasn't visible in any view on the last frame.
+/// Meshes whose bounds were never computed, or whose mesh or settings changed, are always
+/// computed. Only applied on the CPU, the GPU path always updates every frame.
+///
+/// This is opt-in because culling is decided by the bounds being skipped: a mesh culled with
+/// stale bounds stays culled until those bounds come into view, even if its pose has moved into
+/// view since, e.g. a character swinging an arm in from the edge of the screen. Moving the
+/// camera or the mesh entity still brings it back, as the [`Aabb`] is in the entity's local
+/// space, but its [`SkinnedAabb`] isn't updated in the meantime. Only skip meshes whose bounds
+/// have margin enough to cover what their pose does while off screen, e.g. with [`AabbPadding`]
+/// or [`SkinnedAabbConfig::hysteresis`], or that a coarse pre-cull of your own hides by setting
+/// their [`Visibility`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AabbSkipHidden(pub bool);
+
+/// The per-entity settings read by [`ComputeSkinnedAabbPlugin`], all optional.
+#[derive(WorldQuery)]
+pub(crate) struct SkinnedAabbSettings {
+    compute: Option<&'static AabbCompute>,
+    quality: Option<&'static AabbQuality>,
+    update_mode: Option<&'static AabbUpdateMode>,
+    tangent_thickness: Option<&'static TangentThickness>,
+    bone_space_margin: Option<&'static BoneSpaceMargin>,
+    point_radius: Option<&'static PointRadius>,
+    skip_hidden: Option<&'static AabbSkipHidden>,
+    /// Read for [`AabbSkipHidden`].
+    visibility: Option<&'static Visibility>,
+    computed_visibility: Option<&'static ComputedVisibility>,
+}
+
+/// Skinned meshes whose [`SkinnedAabbSettings`], the margins of their [`SkinnedAabbOutput`] or
+/// their [`MorphTargets`] were inserted or changed since the last update, so their bounds are due
+/// whatever their [`AabbUpdateMode`].
+pub(crate) type ChangedSkinnedAabbSettings = Or<(
+    Changed<AabbCompute>,
+    Changed<AabbQuality>,
+    Changed<AabbUpdateMode>,
+    Changed<TangentThickness>,
+    Changed<BoneSpaceMargin>,
+    Changed<PointRadius>,
+    Changed<SkinnedAabbMargin>,
+    Changed<AabbPadding>,
+    Changed<MorphTargets>,
+)>;
+
+impl SkinnedAabbSettingsItem<'_> {
+    /// Whether the bounds are skipped because the mesh is hidden, see [`AabbSkipHidden`].
+    fn skips_hidden(&self, config: &SkinnedAabbConfig) -> bool {
+        let skip_hidden = self.skip_hidden.map_or(config.skip_hidden, |skip| skip.0);
+        skip_hidden
+            && (self
+                .visibility
+                .is_some_and(|visibility| !visibility.is_visible)
+                || self
+                    .computed_visibility
+                    .is_some_and(|visibility| !visibility.is_visible()))
+    }
+}
+
+/// How the bounds of a skinned mesh are computed on the CPU.
+/// Insert it on a skinned mesh to override [`SkinnedAabbConfig::quality`].
+/// The GPU path always skins every vertex.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub enum AabbQuality {
+    /// Skin every vertex, the bounds fit the pose exactly.
+    #[default]
+    Exact,
+    /// Move the bind pose bounds of the vertices influenced by each joint with that joint,
+    /// see [`JointAabbs`]. It only costs 8 corners per joint instead of every vertex,
+    /// but the bounds are larger than the pose.
+    PerJoint,
+    /// Only skin every `stride`th vertex, and grow the bounds on every axis by `padding` times
+    /// their largest half extent, so flat meshes get padded too.
+    /// The same vertices are used every frame so the bounds don't jitter, and meshes with
+    /// no more vertices than `stride` are skinned exactly.
+    /// The bounds are only guaranteed to contain the mesh if `padding` covers the vertices
+    /// that were skipped.
+    Sampled { stride: usize, padding: f32 },
+    /// Write the [`AabbQuality::Sampled`] bounds right away, and replace them with the exact
+    /// bounds computed on the [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool) a
+    /// frame or more later, for editors that want something on screen immediately.
+    /// The exact bounds are dropped if newer sampled bounds were written in the meantime, so
+    /// they only land once the mesh stops moving, as with [`AabbUpdateMode::OnJointChange`], and
+    /// then stand until it moves again.
+    /// They're contained in the sampled bounds as long as `padding` covers the skipped
+    /// vertices.
+    /// Falls back to [`AabbQuality::Sampled`] until the mesh's [`SkinnedMeshCpuData`] is cached.
+    Progressive { stride: usize, padding: f32 },
+}
+
+/// Where the bounds of a skinned mesh are computed.
+/// Insert it on a skinned mesh to override [`SkinnedAabbConfig::compute`].
+#[derive(Component, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AabbCompute {
+    /// Skin the vertices on the CPU, the bounds match the pose of the current frame.
+    Cpu,
+    /// Skin the vertices in a compute shader and read the bounds back asynchronously.
+    /// They usually land one or two frames after the pose they were computed from,
+    /// see [`SkinnedAabb::frame`].
+    /// Falls back to [`AabbCompute::Cpu`] without the `gpu` feature or compute shader support.
+    Gpu,
+    /// Skin the cached [`SkinnedMeshCpuData`] on the
+    /// [`AsyncComputeTaskPool`](bevy::tasks::AsyncComputeTaskPool), the bounds land one or more
+    /// frames after the pose they were computed from, see [`SkinnedAabb::frame`].
+    /// A mesh doesn't start a new task until its last one finished, its latest pose is bounded
+    /// then, and [`AabbQuality::Progressive`] meshes are skinned exactly. No
+    /// [`SkinnedAabb::sphere`] or [`SkinnedAabbMetrics`] are computed.
+    /// Computed on the CPU right away until the mesh's [`SkinnedMeshCpuData`] is cached, and for
+    /// [`AabbQuality::PerJoint`], [`TangentThickness`] and [`PointRadius::Attribute`] meshes, and
+    /// with a [`SkinnedAabbConfig::percentile`].
+    Async,
+}
+
+impl AabbCompute {
+    /// The mode actually used for a mesh, given its own override.
+    pub(crate) fn resolve(
+        mode: Option<&AabbCompute>,
+        config: &SkinnedAabbConfig,
+        gpu_enabled: bool,
+    ) -> AabbCompute {
+        match mode.copied().unwrap_or(config.compute) {
+            AabbCompute::Gpu if gpu_enabled => AabbCompute::Gpu,
+            AabbCompute::Async => AabbCompute::Async,
+            _ => AabbCompute::Cpu,
+        }
+    }
+}
+
+/// Present when the GPU path is running.
+pub(crate) struct GpuAabbEnabled;
+
+/// Number of frames the app has run, counted from 0 at the first update.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SkinnedAabbFrameCount(pub u32);
+
+fn count_frames(mut frame_count: ResMut<SkinnedAabbFrameCount>) {
+    frame_count.0 = frame_count.0.wrapping_add(1);
+}
+
+/// The bounds computed for a skinned mesh, for systems other than culling to read.
+///
+/// Only written when the bounds move by more than [`SkinnedAabbConfig::change_epsilon`],
+/// so `Changed<SkinnedAabb>` can be used to react to the mesh actually moving.
+/// The [`Aabb`] component used for culling is updated every time the computed bounds move.
+///
+/// Bevy draws a skinned mesh where its joints put it, whatever the mesh entity's own
+/// [`GlobalTransform`]. `world` is where the vertices are drawn, and `local` is the same bounds
+/// brought back into the entity's space through its [`GlobalTransform`], translation and scale
+/// included, which is where bevy's culling expects the [`Aabb`]. Moving or scaling the mesh
+/// entity without its joints only changes `local`.
+#[derive(Component, Clone, Debug)]
+pub struct SkinnedAabb {
+    /// The bounds in the mesh entity's local space, the same as its [`Aabb`].
+    pub local: Aabb,
+    /// The bounds in world space, enclosing `local` transformed by the entity's
+    /// [`GlobalTransform`].
+    pub world: Aabb,
+    /// A sphere around the skinned vertices in the mesh entity's local space, see
+    /// [`compute_bounding_sphere`]. Only computed on the CPU when
+    /// [`SkinnedAabbConfig::bounding_sphere`] is set, and never grown in monotonic mode.
+    pub sphere: Option<Sphere>,
+    /// The [`SkinnedAabbFrameCount`] of the pose these bounds were computed from.
+    /// Bounds computed on the GPU or in a task are only written once they land,
+    /// so this is older than the current frame for [`AabbCompute::Gpu`] and
+    /// [`AabbCompute::Async`].
+    pub frame: u32,
+}
+
+impl SkinnedAabb {
+    /// The minimum corner of the local bounds.
+    pub fn min(&self) -> Vec3A {
+        self.local.min()
+    }
+
+    /// The maximum corner of the local bounds.
+    pub fn max(&self) -> Vec3A {
+        self.local.max()
+    }
+
+    /// The center of the local bounds.
+    pub fn center(&self) -> Vec3A {
+        self.local.center
+    }
+
+    /// The half extents of the local bounds.
+    pub fn half_extents(&self) -> Vec3A {
+        self.local.half_extents
+    }
+
+    /// Whether the bounds of `self` and `other` are within `epsilon` of each other.
+    fn abs_diff_eq(&self, other: &SkinnedAabb, epsilon: f32) -> bool {
+        let aabb_eq = |a: &Aabb, b: &Aabb| {
+            a.center.abs_diff_eq(b.center, epsilon)
+                && a.half_extents.abs_diff_eq(b.half_extents, epsilon)
+        };
+        let sphere_eq = match (&self.sphere, &other.sphere) {
+            (Some(a), Some(b)) => {
+                a.center.abs_diff_eq(b.center, epsilon) && (a.radius - b.radius).abs() <= epsilon
+            }
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        aabb_eq(&self.local, &other.local) && aabb_eq(&self.world, &other.world) && sphere_eq
+    }
+}
+
+/// The [`Aabb`] enclosing `aabb` transformed by `transform`.
+///
+/// Each half extent is the sum of the absolute contributions of the box's axes, which is the
+/// same as bounding the 8 transformed corners, so it stays exact with non-uniform, sheared or
+/// negative scale, where scaling the half extents by the transform's scale wouldn't.
+pub fn transform_aabb(aabb: &Aabb, transform: &GlobalTransform) -> Aabb {
+    let affine = transform.affine();
+    Aabb {
+        center: affine.transform_point3a(aabb.center),
+        half_extents: Vec3A::new(
+            affine.matrix3.row(0).abs().dot(aabb.half_extents),
+            affine.matrix3.row(1).abs().dot(aabb.half_extents),
+            affine.matrix3.row(2).abs().dot(aabb.half_extents),
+        ),
+    }
+}
+
+/// The components written by [`ComputeSkinnedAabbPlugin`] on each skinned mesh.
+#[derive(WorldQuery)]
+#[world_query(mutable)]
+pub(crate) struct SkinnedAabbOutput {
+    aabb: Option<&'static mut Aabb>,
+    monotonic: Option<&'static mut MonotonicAabb>,
+    hysteresis: Option<&'static mut HysteresisAabb>,
+    skinned_aabb: Option<&'static mut SkinnedAabb>,
+    transform: Option<&'static GlobalTransform>,
+    margin: Option<&'static SkinnedAabbMargin>,
+    padding: Option<&'static AabbPadding>,
+}
+
+/// How the bounds written for a skinned mesh trail its pose when it shrinks, see
+/// [`SkinnedAabbConfig::hysteresis`]. The bounds always grow as soon as the pose leaves them, so
+/// they contain it the same way as without hysteresis.
+///
+/// With both fields zero, the default, the bounds follow the pose exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AabbHysteresis {
+    /// How many updates in a row the bounds of the pose have to be smaller than the written
+    /// bounds before those shrink.
+    pub shrink_delay: u32,
+    /// How fast each side of the written bounds moves in towards the bounds of the pose once
+    /// they shrink, in the mesh's local units per second, or `0.0` to snap to them.
+    pub shrink_rate: f32,
+}
+
+impl AabbHysteresis {
+    fn is_exact(&self) -> bool {
+        self.shrink_delay == 0 && self.shrink_rate <= 0.0
+    }
+}
+
+/// The bounds written for a skinned mesh with [`SkinnedAabbConfig::hysteresis`], trailing the
+/// bounds of its pose.
+#[derive(Component, Clone, Debug)]
+pub(crate) struct HysteresisAabb {
+    aabb: Aabb,
+    /// How many updates in a row the bounds of the pose were inside `aabb` and smaller.
+    smaller_for: u32,
+    /// When `aabb` was last written, in seconds since startup.
+    written_at: f64,
+}
+
+impl HysteresisAabb {
+    /// Grow the bounds around `exact`, or shrink them towards it as `hysteresis` allows.
+    fn update(&mut self, exact: &Aabb, hysteresis: &AabbHysteresis, now: f64) -> Aabb {
+        let (minimum, maximum) = (self.aabb.min(), self.aabb.max());
+        let (exact_min, exact_max) = (exact.min(), exact.max());
+        let inside = exact_min.cmpge(minimum).all() && exact_max.cmple(maximum).all();
+        let smaller = inside && (exact_min.cmpgt(minimum).any() || exact_max.cmplt(maximum).any());
+        self.smaller_for = if smaller { self.smaller_for + 1 } else { 0 };
+        let (minimum, maximum) = (minimum.min(exact_min), maximum.max(exact_max));
+        let (minimum, maximum) = if self.smaller_for < hysteresis.shrink_delay {
+            (minimum, maximum)
+        } else if hysteresis.shrink_rate > 0.0 {
+            let step = Vec3A::splat(hysteresis.shrink_rate * (now - self.written_at) as f32);
+            (
+                (minimum + step).min(exact_min),
+                (maximum - step).max(exact_max),
+            )
+        } else {
+            (exact_min, exact_max)
+        };
+        self.aabb = Aabb::from_min_max(minimum.into(), maximum.into());
+        self.written_at = now;
+        self.aabb.clone()
+    }
+}
+
+/// The union of every [`Aabb`] computed for a skinned mesh while
+/// [`SkinnedAabbConfig::monotonic`] is enabled, in the mesh's local space.
+#[derive(Component, Clone, Debug)]
+pub struct MonotonicAabb(pub Aabb);
+
+/// Forget the bounds accumulated for `entity` in monotonic mode, so they fit its current pose
+/// again on the next frame, whether or not it moves.
+pub fn reset_bounds(commands: &mut Commands, entity: Entity) {
+    commands.entity(entity).remove::<MonotonicAabb>();
+}
+
+/// Opt a skinned mesh out of [`ComputeSkinnedAabbPlugin`].
+/// Its [`Aabb`] is left as bevy computed it from the bind pose.
+#[derive(Component)]
+pub struct NoSkinnedAabb;
+
+/// Grow the bounds of a skinned mesh by this distance, in its local space, on both sides of each
+/// vertex along its skinned tangent, for hair or fur cards whose shading gives them thickness
+/// along the tangent.
+/// Only used with [`AabbQuality::Exact`] on the CPU, ignored with a warning if the mesh
+/// has no `Float32x4` [`Mesh::ATTRIBUTE_TANGENT`].
+#[derive(Component, Clone, Copy, Debug)]
+pub struct TangentThickness(pub f32);
+
+/// Bound each vertex of a skinned mesh as a sphere of some radius instead of a point, for particle
+/// like meshes whose vertices are drawn as sprites or impostors.
+/// The radius is in the mesh's bind pose, and grows and shrinks with the scale of the joints, by
+/// their largest axis, for squash and stretch. Only applied on the CPU.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub enum PointRadius {
+    /// The same radius for every vertex.
+    Uniform(f32),
+    /// A radius per vertex, read from [`ATTRIBUTE_POINT_RADIUS`]. Only [`AabbQuality::Exact`]
+    /// bounds each sphere, the other qualities pad the bounds by the largest radius.
+    /// Ignored with a warning if the mesh doesn't have the attribute.
+    Attribute,
+}
+
+/// The radius of each vertex for [`PointRadius::Attribute`].
+pub const ATTRIBUTE_POINT_RADIUS: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_PointRadius", 2_843_115_009, VertexFormat::Float32);
+
+/// Pad the bounds of a skinned mesh by a box with these half extents,
+/// oriented along (and scaled with) the joint with the most total weight on the mesh,
+/// see [`SkinnedMeshCpuData::dominant_joint`].
+/// Unlike a margin added to the [`Aabb`] directly, it can follow a thin limb as it rotates.
+/// Only applied on the CPU.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct BoneSpaceMargin(pub Vec3);
+
+/// Grow the half extents of the bounds of a skinned mesh by this much on each axis, in the mesh's
+/// local space, e.g. to select a character by clicking near it. Negative values count as zero.
+/// Applied on every path, before the bounds are grown in monotonic mode. [`SkinnedAabb::sphere`]
+/// isn't padded.
+#[derive(Component, Clone, Copy, Debug)]
+pub struct SkinnedAabbMargin(pub Vec3);
+
+impl SkinnedAabbMargin {
+    /// The same margin on every axis.
+    pub fn uniform(margin: f32) -> Self {
+        Self(Vec3::splat(margin))
+    }
+}
+
+/// Move each side of the bounds of a skinned mesh out by `min` on the negative side of each axis
+/// and by `max` on the positive side, in the mesh's local space. E.g. `AabbPadding::new(Vec3::ZERO,
+/// Vec3::Y * 0.5)` for a sword held up that isn't part of the skin, or a little on the normal of a
+/// flat mesh so it isn't culled when seen edge on. Negative values count as zero.
+///
+/// Applied on every path along with [`SkinnedAabbMargin`], on top of the padding of
+/// [`AabbQuality::Sampled`], and before the bounds are grown in monotonic mode and transformed to
+/// [`SkinnedAabb::world`]. [`SkinnedAabb::sphere`] isn't padded.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct AabbPadding {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl AabbPadding {
+    /// Pad the negative sides by `min` and the positive sides by `max`.
+    pub fn new(min: Vec3, max: Vec3) -> Self {
+        Self { min, max }
+    }
+
+    /// The same padding on every side.
+    pub fn uniform(padding: f32) -> Self {
+        Self::per_axis(Vec3::splat(padding))
+    }
+
+    /// The same padding on both sides of each axis.
+    pub fn per_axis(padding: Vec3) -> Self {
+        Self::new(padding, padding)
+    }
+
+    /// `aabb` with its sides moved out by this padding.
+    pub fn pad(&self, aabb: &Aabb) -> Aabb {
+        Aabb::from_min_max(
+            Vec3::from(aabb.min()) - self.min.max(Vec3::ZERO),
+            Vec3::from(aabb.max()) + self.max.max(Vec3::ZERO),
+        )
+    }
+}
+
+/// The half extents of the [`Aabb`] of a box with half extents `margin`, transformed by `joint`.
+pub fn bone_space_margin_extents(joint: Mat4, margin: Vec3) -> Vec3 {
+    let linear = Mat3::from_mat4(joint);
+    Vec3::new(
+        linear.row(0).abs().dot(margin),
+        linear.row(1).abs().dot(margin),
+        linear.row(2).abs().dot(margin),
+    )
+}
+
+/// The skinning attributes of a skinned mesh, read once from its [`Mesh`] asset so they don't
+/// have to be looked up and matched every frame.
+///
+/// Built by [`ComputeSkinnedAabbPlugin`] once the mesh is loaded, rebuilt when the mesh handle
+/// changes or the asset is modified, and removed when the asset is removed.
+/// Vertices whose weights are all zero are dropped, as they don't move with any joint,
+/// unless [`SkinnedAabbConfig::zero_weights`] binds them to joint 0.
+/// The vertices are shared, so cloning the cache, e.g. for a background task, doesn't copy them.
+#[derive(Component, Clone, Debug, Default)]
+pub struct SkinnedMeshCpuData {
+    pub positions: Arc<[Vec3A]>,
+    pub joint_indices: Arc<[[u32; 4]]>,
+    pub joint_weights: Arc<[Vec4]>,
+    /// The joint with the most weight summed over every vertex.
+    pub dominant_joint: usize,
+    /// One past the largest joint index of the cached vertices, the number of joints they need.
+    pub joint_count: usize,
+    /// Where the cached vertices sit in the bind pose.
+    pub bind_pose: BindPoseStats,
+    /// How [`SkinnedMeshCpuData::skin`] and [`SkinnedMeshCpuData::skinned_aabb`] blend the joints.
+    pub skinning: SkinningMethod,
+}
+
+/// The centroid and extents of a skinned mesh in its bind pose, for tools that normalize rigs
+/// to a common scale, e.g. to retarget animations between them.
+///
+/// A joint's global transform undoes its inverse bindpose in the bind pose, so every joint
+/// matrix is the identity there and the bind pose positions are the mesh's rest positions.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct BindPoseStats {
+    /// The mean of the positions.
+    pub centroid: Vec3,
+    /// The half extents of the box around the positions, centered on the box rather than
+    /// on [`BindPoseStats::centroid`].
+    pub extents: Vec3,
+}
+
+impl BindPoseStats {
+    /// The stats of the finite positions in `positions`, all zero if there is none.
+    pub fn from_positions(positions: &[Vec3A]) -> Self {
+        let mut sum = Vec3A::ZERO;
+        let mut count = 0;
+        let mut bounds: Option<(Vec3A, Vec3A)> = None;
+        for p in positions.iter().filter(|p| p.is_finite()) {
+            sum += *p;
+            count += 1;
+            bounds = Some(match bounds {
+                Some((minimum, maximum)) => (minimum.min(*p), maximum.max(*p)),
+                None => (*p, *p),
+            });
+        }
+        match bounds {
+            Some((minimum, maximum)) => BindPoseStats {
+                centroid: Vec3::from(sum / count as f32),
+                extents: Vec3::from((maximum - minimum) * 0.5),
+            },
+            None => BindPoseStats::default(),
+        }
+    }
+}
+
+impl SkinnedMeshCpuData {
+    /// Read the skinning attributes of `mesh`, dequantizing them if they are stored as integers,
+    /// see [`mesh_positions`] and [`mesh_joint_weights`].
+    /// Returns `None` if the mesh is missing skinning attributes.
+    pub fn new(mesh: &Mesh) -> Option<Self> {
+        Self::with_zero_weights(mesh, ZeroWeights::Skip)
+    }
+
+    /// Like [`SkinnedMeshCpuData::new`], keeping the vertices whose weights are all zero
+    /// if `zero_weights` is [`ZeroWeights::BindToJointZero`].
+    pub fn with_zero_weights(mesh: &Mesh, zero_weights: ZeroWeights) -> Option<Self> {
+        Self::with_options(mesh, zero_weights, false, WeightPrecision::Full)
+    }
+
+    /// Like [`SkinnedMeshCpuData::with_zero_weights`], also leaving out the vertices no
+    /// triangle references if `skip_unreferenced` is set and the mesh is indexed, see
+    /// [`referenced_vertices`], and rounding the weights to `precision`.
+    pub fn with_options(
+        mesh: &Mesh,
+        zero_weights: ZeroWeights,
+        skip_unreferenced: bool,
+        precision: WeightPrecision,
+    ) -> Option<Self> {
+        let mesh_positions = mesh_positions(mesh)?;
+        let mesh_indices = mesh_joint_indices(mesh)?;
+        let mut mesh_weights = mesh_joint_weights(mesh)?;
+        for weights in &mut mesh_weights {
+            *weights = precision.quantize(*weights);
+        }
+
+        let referenced = referenced_vertices(mesh).filter(|_| skip_unreferenced);
+        let (mut positions, mut joint_indices, mut joint_weights) =
+            (Vec::new(), Vec::new(), Vec::new());
+        for (i, ((pos, indices), weights)) in mesh_positions
+            .into_iter()
+            .zip(mesh_indices)
+            .zip(mesh_weights)
+            .enumerate()
+        {
+            if !zero_weights.keep(weights)
+                || referenced
+                    .as_ref()
+                    .is_some_and(|referenced| referenced.get(i) == Some(&false))
+            {
+                continue;
+            }
+            positions.push(Vec3A::from(pos));
+            joint_indices.push(indices);
+            joint_weights.push(weights);
+        }
+
+        let mut weight_per_joint = Vec::new();
+        for (indices, weights) in joint_indices.iter().zip(&joint_weights) {
+            for (index, weight) in indices.iter().zip(weights.to_array()) {
+                let index = *index as usize;
+                if weight_per_joint.len() <= index {
+                    weight_per_joint.resize(index + 1, 0.0);
+                }
+                weight_per_joint[index] += weight;
+            }
+        }
+        let dominant_joint = weight_per_joint
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map_or(0, |(joint, _)| joint);
+        Some(SkinnedMeshCpuData {
+            bind_pose: BindPoseStats::from_positions(&positions),
+            positions: positions.into(),
+            joint_indices: joint_indices.into(),
+            joint_weights: joint_weights.into(),
+            dominant_joint,
+            joint_count: weight_per_joint.len(),
+            skinning: SkinningMethod::default(),
+        })
+    }
+
+    /// Skin the cached vertices with `joints`, as [`skin_vertices`] does with
+    /// [`SkinningMethod::LinearBlend`].
+    pub fn skin(&self, joints: &[Mat4]) -> Vec<Vec3> {
+        let dual_quats = self.dual_quats(joints);
+        (0..self.positions.len())
+            .map(|i| self.skin_vertex(joints, &dual_quats, i))
+            .collect()
+    }
+
+    /// The joints as [`DualQuat`]s if they're blended as such, empty otherwise.
+    fn dual_quats(&self, joints: &[Mat4]) -> Vec<DualQuat> {
+        match self.skinning {
+            SkinningMethod::LinearBlend => Vec::new(),
+            SkinningMethod::DualQuaternion => joints.iter().copied().map(DualQuat::from).collect(),
+        }
+    }
+
+    /// Skin cached vertex `i` with `joints`, or `dual_quats` if they're blended as such.
+    fn skin_vertex(&self, joints: &[Mat4], dual_quats: &[DualQuat], i: usize) -> Vec3 {
+        let position = Vec3::from(self.positions[i]);
+        match self.skinning {
+            SkinningMethod::LinearBlend => {
+                skin_model_normalized(joints, &self.joint_indices[i], self.joint_weights[i])
+                    .transform_point3(position)
+            }
+            SkinningMethod::DualQuaternion => skin_point_dual_quaternion(
+                dual_quats,
+                &self.joint_indices[i],
+                self.joint_weights[i],
+                position,
+            ),
+        }
+    }
+
+    /// The bounds of the cached vertices skinned with `joints`, without storing the skinned
+    /// positions. Large meshes are split into chunks bounded in parallel on the
+    /// [`ComputeTaskPool`]. Non-finite positions are skipped, as in [`compute_aabb`].
+    pub fn skinned_aabb(&self, joints: &[Mat4]) -> Option<Aabb> {
+        self.sampled_skinned_aabb(joints, 1, DEFAULT_PARALLEL_CHUNK_SIZE)
+    }
+
+    /// Like [`SkinnedMeshCpuData::skinned_aabb`], but only skinning every `stride`th vertex,
+    /// and splitting meshes with more than `chunk_size` sampled vertices into chunks of that size.
+    pub fn sampled_skinned_aabb(
+        &self,
+        joints: &[Mat4],
+        stride: usize,
+        chunk_size: usize,
+    ) -> Option<Aabb> {
+        self.bound_sampled(joints, stride, chunk_size, true)
+            .ok()
+            .flatten()
+            .map(|(minimum, maximum)| Aabb::from_min_max(minimum, maximum))
+    }
+
+    /// Like [`SkinnedMeshCpuData::sampled_skinned_aabb`], but failing on the first skinned vertex
+    /// with a NaN or infinite coordinate instead of skipping it, as [`try_compute_aabb`] does.
+    /// The index of [`AabbError::NonFinite`] is the one of the cached vertex.
+    pub fn try_sampled_skinned_aabb(
+        &self,
+        joints: &[Mat4],
+        stride: usize,
+        chunk_size: usize,
+    ) -> Result<Aabb, AabbError> {
+        match self.bound_sampled(joints, stride, chunk_size, false) {
+            Ok(Some((minimum, maximum))) => Ok(Aabb::from_min_max(minimum, maximum)),
+            Ok(None) => Err(AabbError::Empty),
+            Err(index) => Err(AabbError::NonFinite { index }),
+        }
+    }
+
+    /// The min and max of every `stride`th skinned vertex, `None` if none was bounded. Non-finite
+    /// vertices are skipped if `skip_non_finite`, otherwise the first one's index is returned.
+    fn bound_sampled(
+        &self,
+        joints: &[Mat4],
+        stride: usize,
+        chunk_size: usize,
+        skip_non_finite: bool,
+    ) -> Result<Option<(Vec3, Vec3)>, usize> {
+        let stride = stride.max(1);
+        let dual_quats = self.dual_quats(joints);
+        let dual_quats = &dual_quats;
+        let bound = |range: std::ops::Range<usize>| {
+            let mut bounds: Option<(Vec3, Vec3)> = None;
+            for i in range.step_by(stride) {
+                let p = self.skin_vertex(joints, dual_quats, i);
+                if !p.is_finite() {
+                    if skip_non_finite {
+                        continue;
+                    }
+                    return Err(i);
+                }
+                bounds = Some(match bounds {
+                    Some((minimum, maximum)) => (minimum.min(p), maximum.max(p)),
+                    None => (p, p),
+                });
+            }
+            Ok(bounds)
+        };
+
+        let len = self.positions.len();
+        // Chunks start on a multiple of the stride, so they sample the same vertices as one pass.
+        let chunk_size = chunk_size.max(1) * stride;
+        if len <= chunk_size {
+            return bound(0..len);
+        }
+        let chunks = ComputeTaskPool::get().scope(|scope| {
+            for start in (0..len).step_by(chunk_size) {
+                let end = (start + chunk_size).min(len);
+                scope.spawn(async move { bound(start..end) });
+            }
+        });
+        // The chunks come back in the order they were spawned, so the first error is the first
+        // non-finite vertex.
+        let chunks = chunks.into_iter().collect::<Result<Vec<_>, _>>()?;
+        Ok(chunks
+            .into_iter()
+            .flatten()
+            .reduce(|(min_a, max_a), (min_b, max_b)| (min_a.min(min_b), max_a.max(max_b))))
+    }
+}
+
+/// The default [`SkinnedAabbConfig::parallel_chunk_size`].
+const DEFAULT_PARALLEL_CHUNK_SIZE: usize = 8192;
+
+/// Keep the [`SkinnedMeshCpuData`] of each skinned mesh in sync with its mesh asset, and drop its
+/// [`JointAabbs`] when its mesh or inverse bindposes asset changes, e.g. when hot reloaded, so
+/// they are rebuilt from the new asset on their next use.
+#[allow(clippy::type_complexity)]
+fn cache_skinned_mesh_data(
+    mut commands: Commands,
+    mut mesh_events: EventReader<AssetEvent<Mesh>>,
+    mut bindpose_events: EventReader<AssetEvent<SkinnedMeshInverseBindposes>>,
+    meshes: Res<Assets<Mesh>>,
+    query: Query<
+        (
+            Entity,
+            &Handle<Mesh>,
+            &SkinnedMesh,
+            Option<&SkinnedMeshCpuData>,
+            Option<&JointAabbs>,
+            ChangeTrackers<Handle<Mesh>>,
+            Option<&SkinningMethod>,
+        ),
+        (With<SkinnedMesh>, Without<NoSkinnedAabb>),
+    >,
+    config: Res<SkinnedAabbConfig>,
+    mut policy: Local<Option<(ZeroWeights, bool, WeightPrecision)>>,
+) {
+    // Every cache depends on which vertices are kept and how their weights are read.
+    let new_policy = (
+        config.zero_weights,
+        config.skip_unreferenced_vertices,
+        config.weight_precision,
+    );
+    let policy_changed = policy.replace(new_policy) != Some(new_policy);
+    let mut modified = HashSet::new();
+    let mut removed = HashSet::new();
+    for event in mesh_events.iter() {
+        match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => {
+                modified.insert(handle.id);
+            }
+            AssetEvent::Removed { handle } => {
+                removed.insert(handle.id);
+            }
+        }
+    }
+    let changed_bindposes: HashSet<HandleId> = bindpose_events
+        .iter()
+        .map(|event| match event {
+            AssetEvent::Created { handle }
+            | AssetEvent::Modified { handle }
+            | AssetEvent::Removed { handle } => handle.id,
+        })
+        .collect();
+
+    for (entity, mesh_h, skinned_mesh, data, joint_aabbs, mesh_tracker, skinning) in &query {
+        let skinning = skinning.copied().unwrap_or(config.skinning);
+        if joint_aabbs.is_some()
+            && (modified.contains(&mesh_h.id)
+                || removed.contains(&mesh_h.id)
+                || changed_bindposes.contains(&skinned_mesh.inverse_bindposes.id))
+        {
+            commands.entity(entity).remove::<JointAabbs>();
+        }
+        if removed.contains(&mesh_h.id) {
+            if data.is_some() {
+                commands.entity(entity).remove::<SkinnedMeshCpuData>();
+            }
+            continue;
+        }
+        if data.is_some()
+            && !mesh_tracker.is_changed()
+            && !modified.contains(&mesh_h.id)
+            && !policy_changed
+        {
+            if let Some(data) = data.filter(|data| data.skinning != skinning) {
+                commands.entity(entity).insert(SkinnedMeshCpuData {
+                    skinning,
+                    ..data.clone()
+                });
+            }
+            continue;
+        }
+        match meshes.get(mesh_h).and_then(|mesh| {
+            SkinnedMeshCpuData::with_options(
+                mesh,
+                config.zero_weights,
+                config.skip_unreferenced_vertices,
+                config.weight_precision,
+            )
+        }) {
+            Some(data) => {
+                commands
+                    .entity(entity)
+                    .insert(SkinnedMeshCpuData { skinning, ..data });
+            }
+            None => {
+                if data.is_some() {
+                    commands.entity(entity).remove::<SkinnedMeshCpuData>();
+                }
+            }
+        }
+    }
+}
+
+/// The skinned meshes already warned about, so each problem is only logged once per mesh.
+#[derive(Default)]
+pub(crate) struct Warned {
+    pub(crate) attributes: HashSet<Entity>,
+    pub(crate) tangents: HashSet<Entity>,
+    pub(crate) radii: HashSet<Entity>,
+    /// Cleared once the joints can be built again, so a later breakage is reported too.
+    pub(crate) unbuildable_joints: HashSet<Entity>,
+    pub(crate) max_joints: HashSet<Entity>,
+    /// Cleared once the mesh and its skin agree again, so a later mismatch is reported too.
+    pub(crate) out_of_range_joints: HashSet<Entity>,
+    /// Cleared once the mesh is bounded again, so a later error is reported too.
+    pub(crate) aabb_errors: HashSet<Entity>,
+}
+
+/// Which meshes [`SkinnedAabbConfig::vertex_budget`] let through, and which it held back.
+#[derive(Default)]
+struct VertexBudgetState {
+    /// The frame each mesh was last computed on.
+    computed: HashMap<Entity, u32>,
+    /// Meshes that were due but didn't fit in the budget, due again until they are computed.
+    deferred: HashSet<Entity>,
+}
+
+/// The transforms of the joints and mesh entity a skinned mesh was last bounded with.
+struct JointPose {
+    inverse_bindposes: HandleId,
+    mesh: Affine3A,
+    joints: Vec<Affine3A>,
+}
+
+/// The pose each [`AabbUpdateMode::OnJointChange`] mesh was last bounded with, to tell whether
+/// it actually moved since.
+#[derive(SystemParam)]
+struct JointPoses<'w, 's> {
+    poses: Local<'s, HashMap<Entity, JointPose>>,
+    bindpose_events: EventReader<'w, 's, AssetEvent<SkinnedMeshInverseBindposes>>,
+    /// The inverse bindposes created or modified since last frame.
+    modified_bindposes: Local<'s, HashSet<HandleId>>,
+    transform_changes: Query<'w, 's, ChangeTrackers<GlobalTransform>>,
+}
+
+impl<'w, 's> JointPoses<'w, 's> {
+    /// Read this frame's asset events, and forget the meshes `keep` rejects.
+    fn update(&mut self, keep: impl Fn(Entity) -> bool) {
+        let JointPoses {
+            poses,
+            bindpose_events,
+            modified_bindposes,
+            ..
+        } = self;
+        modified_bindposes.clear();
+        for event in bindpose_events.iter() {
+            if let AssetEvent::Created { handle } | AssetEvent::Modified { handle } = event {
+                modified_bindposes.insert(handle.id);
+            }
+        }
+        poses.retain(|entity, _| keep(*entity));
+    }
+
+    /// Whether `entity` or its joints moved by more than `epsilon`, or its inverse bindposes
+    /// changed, since [`JointPoses::record`].
+    fn moved(
+        &self,
+        entity: Entity,
+        skinned_mesh: &SkinnedMesh,
+        joint_query: &Query<&GlobalTransform>,
+        epsilon: f32,
+    ) -> bool {
+        let pose = match self.poses.get(&entity) {
+            Some(pose) => pose,
+            None => return true,
+        };
+        // Transforms that weren't touched this frame can't have moved since the last check.
+        let touched = std::iter::once(&entity)
+            .chain(&skinned_mesh.joints)
+            .any(|entity| {
+                self.transform_changes
+                    .get(*entity)
+                    .map_or(true, |tracker| tracker.is_changed())
+            });
+        if self
+            .modified_bindposes
+            .contains(&skinned_mesh.inverse_bindposes.id)
+            || pose.inverse_bindposes != skinned_mesh.inverse_bindposes.id
+            || pose.joints.len() != skinned_mesh.joints.len()
+        {
+            return true;
+        }
+        if !touched {
+            return false;
+        }
+        let moved = |affine: &Affine3A, entity: &Entity| {
+            joint_query.get(*entity).map_or(true, |transform| {
+                !transform.affine().abs_diff_eq(*affine, epsilon)
+            })
+        };
+        moved(&pose.mesh, &entity)
+            || pose
+                .joints
+                .iter()
+                .zip(&skinned_mesh.joints)
+                .any(|(affine, joint)| moved(affine, joint))
+    }
+
+    /// Whether the bounds `entity` already has are due for an update under `update_mode`.
+    #[allow(clippy::too_many_arguments)]
+    fn due(
+        &self,
+        update_mode: AabbUpdateMode,
+        entity: Entity,
+        skinned_mesh: &SkinnedMesh,
+        joint_query: &Query<&GlobalTransform>,
+        config: &SkinnedAabbConfig,
+        frame: u32,
+        time: &Time,
+    ) -> bool {
+        match update_mode {
+            AabbUpdateMode::EveryFrame => true,
+            AabbUpdateMode::Interval(interval) => {
+                AabbUpdateMode::interval_due(interval, entity, frame)
+            }
+            AabbUpdateMode::FixedHz(hz) => AabbUpdateMode::fixed_hz_due(
+                hz,
+                entity,
+                time.seconds_since_startup(),
+                time.delta_seconds_f64(),
+            ),
+            AabbUpdateMode::OnJointChange => {
+                self.moved(entity, skinned_mesh, joint_query, config.joint_epsilon)
+            }
+        }
+    }
+
+    /// Forget the pose of `entity`, so it's due on the next frame whether it moves or not.
+    fn forget(&mut self, entity: Entity) {
+        self.poses.remove(&entity);
+    }
+
+    /// Remember the pose `entity` is being bounded with.
+    fn record(
+        &mut self,
+        entity: Entity,
+        transform: &GlobalTransform,
+        skinned_mesh: &SkinnedMesh,
+        joint_query: &Query<&GlobalTransform>,
+    ) {
+        let joints = skinned_mesh
+            .joints
+            .iter()
+            .map(|joint| joint_query.get(*joint).map(GlobalTransform::affine))
+            .collect::<Result<_, _>>();
+        match joints {
+            Ok(joints) => {
+                self.poses.insert(
+                    entity,
+                    JointPose {
+                        inverse_bindposes: skinned_mesh.inverse_bindposes.id,
+                        mesh: transform.affine(),
+                        joints,
+                    },
+                );
+            }
+            Err(_) => {
+                self.poses.remove(&entity);
+            }
+        }
+    }
+}
+
+/// Overwrite the [`Aabb`] of each skinned mesh with the bounds of its skinned vertices,
+/// inserting it if the entity doesn't have one yet.
+/// A newly inserted [`Aabb`] only lands at the end of the stage, so it is used for culling
+/// from the next frame on; every later update is written in place and seen the same frame.
+/// The joint matrices are premultiplied by the inverse of the mesh entity's transform,
+/// so the vertices are skinned straight into its local space, which is where bevy expects
+/// the [`Aabb`] to be.
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+fn update_skinned_aabbs(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &Handle<Mesh>,
+            &SkinnedMesh,
+            &GlobalTransform,
+            SkinnedAabbSettings,
+            Option<&JointAabbs>,
+            Option<&SkinnedMeshCpuData>,
+            ChangeTrackers<Handle<Mesh>>,
+            Option<ChangeTrackers<SkinnedMeshCpuData>>,
+            Option<&mut SelfIntersecting>,
+            Option<&mut SkinnedAabbMetrics>,
+            Option<&mut SkinnedObb>,
+            Option<&mut CollapsedSkin>,
+            SkinnedAabbOutput,
+        ),
+        (Without<NoSkinnedAabb>, Without<BoundsSkeleton>),
+    >,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    changed_settings: Query<Option<ChangeTrackers<MorphTargets>>, ChangedSkinnedAabbSettings>,
+    joint_query: Query<&GlobalTransform>,
+    mut joint_poses: JointPoses,
+    config: Res<SkinnedAabbConfig>,
+    frame_count: Res<SkinnedAabbFrameCount>,
+    time: Res<Time>,
+    gpu_enabled: Option<Res<GpuAabbEnabled>>,
+    mut warned: Local<Warned>,
+    mut tasks: ResMut<AabbTasks>,
+    mut budget_state: Local<VertexBudgetState>,
+    mut joints: Local<Vec<Mat4>>,
+    mut positions: Local<Vec<Vec3>>,
+) {
+    let mut order: Vec<Entity> = query.iter().map(|item| item.0).collect();
+    joint_poses.update(|entity| query.contains(entity));
+    let mut budget = config.vertex_budget;
+    let mut spent_budget = false;
+    if budget.is_some() {
+        let VertexBudgetState { computed, deferred } = &mut *budget_state;
+        computed.retain(|entity, _| query.contains(*entity));
+        deferred.retain(|entity| query.contains(*entity));
+        // Meshes never computed come first, then those computed the longest ago.
+        order.sort_by_key(|entity| computed.get(entity).copied());
+    } else if !budget_state.computed.is_empty() || !budget_state.deferred.is_empty() {
+        *budget_state = VertexBudgetState::default();
+    }
+    for entity in order {
+        let (
+            entity,
+            mesh_h,
+            skinned_mesh,
+            transform,
+            settings,
+            joint_aabbs,
+            cpu_data,
+            mesh_tracker,
+            cpu_data_tracker,
+            self_intersecting,
+            metrics,
+            obb,
+            collapsed,
+            output,
+        ) = match query.get_mut(entity) {
+            Ok(item) => item,
+            Err(_) => continue,
+        };
+        let compute = AabbCompute::resolve(settings.compute, &config, gpu_enabled.is_some());
+        if compute == AabbCompute::Gpu {
+            continue;
+        }
+        let update_mode = settings.update_mode.copied().unwrap_or(config.update_mode);
+        // Bounds that were never computed, or whose mesh or settings changed, are always due.
+        let changed_settings = changed_settings.get(entity);
+        // Blended into the mesh this frame, so it isn't cached again yet.
+        let morphed = matches!(&changed_settings, Ok(Some(tracker)) if tracker.is_changed());
+        let mesh_changed = mesh_tracker.is_changed() || morphed;
+        let inputs_changed = mesh_changed
+            || cpu_data_tracker.is_some_and(|tracker| tracker.is_changed())
+            || changed_settings.is_ok()
+            || config.is_changed()
+            // Reset with `reset_bounds`, so the grown bounds shrink back right away.
+            || (config.monotonic && output.monotonic.is_none());
+        if output.skinned_aabb.is_some()
+            && !inputs_changed
+            && !budget_state.deferred.contains(&entity)
+        {
+            if settings.skips_hidden(&config) {
+                continue;
+            }
+            if !joint_poses.due(
+                update_mode,
+                entity,
+                skinned_mesh,
+                &joint_query,
+                &config,
+                frame_count.0,
+                &time,
+            ) {
+                continue;
+            }
+        }
+        let mesh = match meshes.get(mesh_h) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let quality = settings.quality.copied().unwrap_or(config.quality);
+        let (stride, padding) = match quality {
+            AabbQuality::Sampled { stride, padding }
+            | AabbQuality::Progressive { stride, padding }
+                if stride < mesh.count_vertices() =>
+            {
+                (stride.max(1), padding)
+            }
+            _ => (1, 0.0),
+        };
+        if let Some(remaining) = &mut budget {
+            let cost = mesh.count_vertices() / stride;
+            // The first mesh of the frame always fits, so one larger than the budget isn't
+            // starved.
+            if cost > *remaining && spent_budget {
+                budget_state.deferred.insert(entity);
+                continue;
+            }
+            *remaining = remaining.saturating_sub(cost);
+            spent_budget = true;
+            budget_state.deferred.remove(&entity);
+            budget_state.computed.insert(entity, frame_count.0);
+        }
+        if build_joint_matrices(skinned_mesh, &inverse_bindposes, &joint_query, &mut joints)
+            .is_none()
+        {
+            let newly_broken = warn_unbuildable_joints(
+                entity,
+                skinned_mesh,
+                &inverse_bindposes,
+                &joint_query,
+                &mut warned.unbuildable_joints,
+            );
+            // Grow the last bounds once, as the mesh may keep moving without them.
+            if let (true, Some(inflation), Some(skinned_aabb)) = (
+                newly_broken,
+                config.broken_joints_inflation,
+                &output.skinned_aabb,
+            ) {
+                // Written again below, so without the padding it already went through once.
+                let mut aabb = strip_output_padding(&skinned_aabb.local, &output);
+                aabb.half_extents *= inflation.max(0.0);
+                let frame = skinned_aabb.frame;
+                write_skinned_aabb(
+                    &mut commands,
+                    entity,
+                    output,
+                    aabb,
+                    None,
+                    frame,
+                    &config,
+                    &time,
+                );
+            }
+            continue;
+        }
+        warned.unbuildable_joints.remove(&entity);
+        warn_over_max_joints(entity, skinned_mesh, &mut warned.max_joints);
+        // A changed mesh handle or morphed mesh is only cached at the start of the next frame.
+        let cached = cpu_data.filter(|_| !mesh_changed);
+        let joint_count = match cached {
+            Some(cpu_data) => cpu_data.joint_count,
+            None => mesh_joint_indices(mesh).map_or(0, |indices| referenced_joint_count(&indices)),
+        };
+        if !joint_indices_in_range(
+            entity,
+            mesh,
+            &joints,
+            joint_count,
+            &mut warned.out_of_range_joints,
+        ) {
+            // The last metrics stand, with the vertices that keep the mesh from being bounded.
+            if let Some(mut metrics) = metrics {
+                metrics.clamped_index_count = mesh_joint_indices(mesh).map_or(0, |indices| {
+                    out_of_range_joint_indices(&indices, joints.len())
+                });
+            }
+            continue;
+        }
+        if let Some(index) = joints.iter().position(|joint| !joint.is_finite()) {
+            report_aabb_error(
+                entity,
+                AabbError::NonFiniteJoint { index },
+                &mut warned.aabb_errors,
+            );
+            continue;
+        }
+        // Skin straight into the mesh entity's local space, so the local bounds come from the
+        // skinned vertices themselves, whatever the scale of the joints and of the mesh's parents.
+        let mut world_to_local = transform.compute_matrix().inverse();
+        if let Some(offset) = config.global_pose_offset {
+            world_to_local *= Mat4::from(offset);
+        }
+        for joint in joints.iter_mut() {
+            *joint = world_to_local * *joint;
+        }
+        // The margins only depend on the pose, so tasks can add them to their bounds too.
+        let extra_half_extents = pose_margin_extents(
+            entity,
+            mesh,
+            skinned_mesh,
+            &settings,
+            quality,
+            cached,
+            &joints,
+            &inverse_bindposes,
+            &mut warned,
+        );
+
+        let sphere_per_point =
+            quality == AabbQuality::Exact && settings.point_radius == Some(&PointRadius::Attribute);
+        let mut sphere = None;
+        // Whether the pose is bounded this frame, or by a task that was spawned for it.
+        let mut bounded = true;
+        let ls_aabb = match cached {
+            Some(cpu_data)
+                if !matches!(quality, AabbQuality::PerJoint)
+                    && settings.tangent_thickness.is_none()
+                    && !sphere_per_point
+                    && config.percentile.is_none() =>
+            {
+                let progressive = matches!(quality, AabbQuality::Progressive { .. });
+                let job = AabbJob {
+                    entity,
+                    mesh: mesh_h.id,
+                    frame: frame_count.0,
+                    stride: 1,
+                    padding: 0.0,
+                    extra_half_extents,
+                };
+                if compute == AabbCompute::Async {
+                    let (stride, padding) = if progressive {
+                        (1, 0.0)
+                    } else {
+                        (stride, padding)
+                    };
+                    bounded = tasks.spawn(
+                        AabbJob {
+                            stride,
+                            padding,
+                            ..job
+                        },
+                        cpu_data,
+                        &joints,
+                    );
+                    None
+                } else {
+                    if progressive && stride > 1 {
+                        // The sampled bounds would overwrite the exact ones until the mesh moves.
+                        if !inputs_changed && tasks.has_exact(entity, &joints, config.joint_epsilon)
+                        {
+                            continue;
+                        }
+                        bounded = tasks.spawn(job, cpu_data, &joints);
+                    }
+                    match cpu_data.try_sampled_skinned_aabb(
+                        &joints,
+                        stride,
+                        config.parallel_chunk_size,
+                    ) {
+                        Ok(ls_aabb) => Some(ls_aabb),
+                        Err(error) => {
+                            report_aabb_error(entity, error, &mut warned.aabb_errors);
+                            continue;
+                        }
+                    }
+                }
+            }
+            _ => {
+                let skinned = skin_quality_positions(
+                    &mut commands,
+                    entity,
+                    mesh,
+                    skinned_mesh,
+                    &settings,
+                    quality,
+                    stride,
+                    joint_aabbs.filter(|_| !mesh_changed),
+                    &joints,
+                    &inverse_bindposes,
+                    &config,
+                    &mut warned,
+                    &mut positions,
+                );
+                match skinned {
+                    Some(()) => {
+                        let ls_positions = &positions[..];
+                        let ls_aabb = match config.percentile {
+                            Some(percentile) if quality != AabbQuality::PerJoint => {
+                                compute_aabb_percentile(ls_positions, percentile)
+                            }
+                            _ => match try_compute_aabb(ls_positions, 0.0) {
+                                Ok(ls_aabb) => Some(ls_aabb),
+                                Err(error) => {
+                                    report_aabb_error(entity, error, &mut warned.aabb_errors);
+                                    continue;
+                                }
+                            },
+                        };
+                        // Every vertex was skinned, so the sphere can reuse them.
+                        if config.bounding_sphere && quality == AabbQuality::Exact {
+                            sphere = compute_bounding_sphere(ls_positions);
+                        }
+                        ls_aabb
+                    }
+                    None => {
+                        warn_unreadable_attributes(entity, mesh, &mut warned.attributes);
+                        continue;
+                    }
+                }
+            }
+        };
+
+        // A pose whose task couldn't be spawned yet is bounded again on the next frame.
+        if update_mode == AabbUpdateMode::OnJointChange {
+            if bounded {
+                joint_poses.record(entity, transform, skinned_mesh, &joint_query);
+            } else {
+                joint_poses.forget(entity);
+            }
+        }
+
+        // Triangles intersecting in world space also do in local space.
+        if config.detect_self_intersection {
+            if let (Some(vertices), Some(indices)) = (skin_vertices(mesh, &joints), mesh.indices())
+            {
+                let intersecting = SelfIntersecting(mesh_self_intersects(&vertices, indices));
+                match self_intersecting {
+                    Some(mut self_intersecting) => {
+                        if *self_intersecting != intersecting {
+                            *self_intersecting = intersecting;
+                        }
+                    }
+                    None => {
+                        commands.entity(entity).insert(intersecting);
+                    }
+                }
+            }
+        }
+
+        if let Some(mut ls_aabb) = ls_aabb {
+            warned.aabb_errors.remove(&entity);
+            if stride > 1 {
+                ls_aabb.half_extents += padding.max(0.0) * ls_aabb.half_extents.max_element();
+            }
+            ls_aabb.half_extents += extra_half_extents;
+            if let Some(kind) = config.metrics {
+                let clamped_index_count = mesh_joint_indices(mesh).map_or(0, |indices| {
+                    out_of_range_joint_indices(&indices, joints.len())
+                });
+                // The cache lags a reloaded mesh by a frame, so the mesh itself can disagree with
+                // the joints until it's skipped on the next one.
+                let tightness = (clamped_index_count == 0)
+                    .then(|| skin_vertices_with(mesh, &joints, config.zero_weights))
+                    .flatten()
+                    .and_then(|ls_positions| {
+                        bounding_volume_tightness(kind, &ls_positions, &ls_aabb)
+                    });
+                if let Some(tightness) = tightness {
+                    let new_metrics = SkinnedAabbMetrics {
+                        tightness,
+                        clamped_index_count,
+                    };
+                    match metrics {
+                        Some(mut metrics) => *metrics = new_metrics,
+                        None => {
+                            commands.entity(entity).insert(new_metrics);
+                        }
+                    }
+                }
+            }
+            if config.bounding_sphere && sphere.is_none() {
+                sphere = match cached {
+                    Some(cpu_data) => Some(cpu_data.skin(&joints)),
+                    None => skin_vertices_with(mesh, &joints, config.zero_weights),
+                }
+                .and_then(|ls_positions| compute_bounding_sphere(&ls_positions));
+            }
+            if let Some(mut obb) = obb {
+                let ls_positions = match cached {
+                    Some(cpu_data) => Some(cpu_data.skin(&joints)),
+                    None => skin_vertices_with(mesh, &joints, config.zero_weights),
+                };
+                let new_obb = ls_positions.and_then(|ls_positions| {
+                    fit_skinned_obb(
+                        obb.axes,
+                        &ls_positions,
+                        &joints,
+                        inverse_bindposes.get(&skinned_mesh.inverse_bindposes),
+                    )
+                });
+                if let Some(new_obb) = new_obb {
+                    if *obb != new_obb {
+                        *obb = new_obb;
+                    }
+                }
+            }
+            if let Some(mut collapsed) = collapsed {
+                if let Some(checked) = skin_determinants(mesh, &joints)
+                    .and_then(|determinants| collapsed.checked(&determinants))
+                {
+                    *collapsed = checked;
+                }
+            }
+            write_skinned_aabb(
+                &mut commands,
+                entity,
+                output,
+                ls_aabb,
+                sphere,
+                frame_count.0,
+                &config,
+                &time,
+            );
+        }
+    }
+}
+
+/// The transform of a joint, from its skinning matrix `joint` and the `inverse_bindpose` it was
+/// premultiplied by.
+fn joint_transform(joint: Mat4, inverse_bindpose: Mat4) -> Mat4 {
+    joint * inverse_bindpose.inverse()
+}
+
+/// The half extents added to the bounds of `mesh` for the margins that depend on its local space
+/// `joints`: its largest [`PointRadius`] scaled by the most scaled joint, unless exact bounds
+/// already skin one sphere per vertex, and its [`BoneSpaceMargin`] along the dominant joint.
+#[allow(clippy::too_many_arguments)]
+fn pose_margin_extents(
+    entity: Entity,
+    mesh: &Mesh,
+    skinned_mesh: &SkinnedMesh,
+    settings: &SkinnedAabbSettingsItem,
+    quality: AabbQuality,
+    cached: Option<&SkinnedMeshCpuData>,
+    joints: &[Mat4],
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+    warned: &mut Warned,
+) -> Vec3A {
+    let point_radius = match settings.point_radius {
+        Some(PointRadius::Uniform(radius)) => *radius,
+        Some(PointRadius::Attribute) if quality != AabbQuality::Exact => {
+            match mesh_point_radii(mesh) {
+                Some(radii) => radii.iter().copied().fold(0.0, f32::max),
+                None => {
+                    warn_missing_point_radii(entity, &mut warned.radii);
+                    0.0
+                }
+            }
+        }
+        _ => 0.0,
+    };
+    // Without a radius per vertex, scale it as much as the most scaled joint does.
+    let joint_scale = joints.iter().map(max_axis_scale).reduce(f32::max);
+    let mut extra_half_extents = Vec3A::splat(point_radius.max(0.0) * joint_scale.unwrap_or(1.0));
+    if let Some(margin) = settings.bone_space_margin {
+        let dominant_joint = match cached {
+            Some(cpu_data) => Some(cpu_data.dominant_joint),
+            None => SkinnedMeshCpuData::new(mesh).map(|cpu_data| cpu_data.dominant_joint),
+        };
+        let joint = dominant_joint.and_then(|joint| {
+            let inverse_bindpose = inverse_bindposes
+                .get(&skinned_mesh.inverse_bindposes)?
+                .get(joint)?;
+            Some(joint_transform(*joints.get(joint)?, *inverse_bindpose))
+        });
+        if let Some(joint) = joint {
+            extra_half_extents += Vec3A::from(bone_space_margin_extents(joint, margin.0));
+        }
+    }
+    extra_half_extents
+}
+
+/// Skin the vertices of `mesh` that `quality` bounds with the local space `joints`, for meshes not
+/// bounded from their cached [`SkinnedMeshCpuData`]. [`AabbQuality::PerJoint`] skins the corners
+/// of each joint's box instead, and inserts the mesh's [`JointAabbs`] if `joint_aabbs` is `None`.
+/// The positions are written to `positions`, a scratch buffer kept between meshes.
+#[allow(clippy::too_many_arguments)]
+fn skin_quality_positions(
+    commands: &mut Commands,
+    entity: Entity,
+    mesh: &Mesh,
+    skinned_mesh: &SkinnedMesh,
+    settings: &SkinnedAabbSettingsItem,
+    quality: AabbQuality,
+    stride: usize,
+    joint_aabbs: Option<&JointAabbs>,
+    joints: &[Mat4],
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+    config: &SkinnedAabbConfig,
+    warned: &mut Warned,
+    positions: &mut Vec<Vec3>,
+) -> Option<()> {
+    let sphere_per_point =
+        quality == AabbQuality::Exact && settings.point_radius == Some(&PointRadius::Attribute);
+    match quality {
+        AabbQuality::Exact => match settings.tangent_thickness {
+            _ if sphere_per_point => match skin_point_spheres(mesh, joints, config.zero_weights) {
+                Some(spheres) => {
+                    *positions = spheres;
+                    Some(())
+                }
+                None => {
+                    warn_missing_point_radii(entity, &mut warned.radii);
+                    skin_vertices_into(mesh, joints, config.zero_weights, positions)
+                }
+            },
+            Some(thickness) => match skin_vertices_along_tangents(mesh, joints, thickness.0) {
+                Some(extruded) => {
+                    *positions = extruded;
+                    Some(())
+                }
+                None => {
+                    let has_tangents = matches!(
+                        mesh.attribute(Mesh::ATTRIBUTE_TANGENT),
+                        Some(VertexAttributeValues::Float32x4(_))
+                    );
+                    if !has_tangents && warned.tangents.insert(entity) {
+                        warn!(
+                            "Skinned mesh {:?} has a TangentThickness but no usable tangents, \
+                            its Aabb ignores the thickness",
+                            entity
+                        );
+                    }
+                    skin_vertices_into(mesh, joints, config.zero_weights, positions)
+                }
+            },
+            None => skin_vertices_into(mesh, joints, config.zero_weights, positions),
+        },
+        AabbQuality::Sampled { .. } | AabbQuality::Progressive { .. } => {
+            skin_sampled_vertices_into(mesh, joints, config.zero_weights, stride, positions)
+        }
+        AabbQuality::PerJoint => match joint_aabbs {
+            Some(joint_aabbs) => {
+                *positions = joint_aabbs.skinned_corners(joints);
+                Some(())
+            }
+            None => inverse_bindposes
+                .get(&skinned_mesh.inverse_bindposes)
+                .and_then(|bindposes| JointAabbs::new(mesh, bindposes))
+                .map(|joint_aabbs| {
+                    *positions = joint_aabbs.skinned_corners(joints);
+                    commands.entity(entity).insert(joint_aabbs);
+                }),
+        },
+    }
+}
+
+/// Fit a [`SkinnedObb`] oriented along `axes` around the local space `ls_positions`, skinned with
+/// `joints`.
+fn fit_skinned_obb(
+    axes: ObbAxes,
+    ls_positions: &[Vec3],
+    joints: &[Mat4],
+    inverse_bindposes: Option<&SkinnedMeshInverseBindposes>,
+) -> Option<SkinnedObb> {
+    let obb = match axes {
+        ObbAxes::PrincipalAxes => compute_obb(ls_positions),
+        ObbAxes::Joint(index) => {
+            let joint = joint_transform(*joints.get(index)?, *inverse_bindposes?.get(index)?);
+            // Without its scale, so the half extents stay in the mesh's units.
+            let (_, rotation, translation) = joint.to_scale_rotation_translation();
+            let frame = Affine3A::from_rotation_translation(rotation, translation);
+            compute_obb_in_frame(ls_positions, frame)
+        }
+    }?;
+    Some(SkinnedObb { axes, ..obb })
+}
+
+/// Write a local space `new_aabb` and `sphere` computed from the pose of `frame` to `entity`,
+/// inserting the components it doesn't have yet, and growing the bounds instead when they are
+/// monotonic or held back by hysteresis.
+/// [`SkinnedAabb`] is only touched when the bounds moved by more than the configured epsilon,
+/// and the [`Aabb`] when they moved at all.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn write_skinned_aabb(
+    commands: &mut Commands,
+    entity: Entity,
+    output: SkinnedAabbOutputItem,
+    mut new_aabb: Aabb,
+    sphere: Option<Sphere>,
+    frame: u32,
+    config: &SkinnedAabbConfig,
+    time: &Time,
+) {
+    if let Some(margin) = output.margin {
+        new_aabb.half_extents += Vec3A::from(margin.0.max(Vec3::ZERO));
+    }
+    if let Some(padding) = output.padding {
+        new_aabb = padding.pad(&new_aabb);
+    }
+    if let Some(min_half_extent) = config.min_half_extent {
+        new_aabb.half_extents = new_aabb.half_extents.max(Vec3A::splat(min_half_extent));
+    }
+    if let Some(scale) = config.fixed_point_scale {
+        new_aabb = snap_aabb(&new_aabb, scale);
+    }
+    if config.monotonic {
+        match output.monotonic {
+            Some(mut monotonic) => {
+                monotonic.0 = aabb_union(&monotonic.0, &new_aabb);
+                new_aabb = monotonic.0.clone();
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(MonotonicAabb(new_aabb.clone()));
+            }
+        }
+    }
+    let now = time.seconds_since_startup();
+    match output.hysteresis {
+        Some(mut hysteresis) if !config.hysteresis.is_exact() => {
+            new_aabb = hysteresis.update(&new_aabb, &config.hysteresis, now);
+        }
+        Some(_) => {
+            commands.entity(entity).remove::<HysteresisAabb>();
+        }
+        None if !config.hysteresis.is_exact() => {
+            commands.entity(entity).insert(HysteresisAabb {
+                aabb: new_aabb.clone(),
+                smaller_for: 0,
+                written_at: now,
+            });
+        }
+        None => {}
+    }
+    let new_skinned_aabb = SkinnedAabb {
+        world: match output.transform {
+            Some(transform) => transform_aabb(&new_aabb, transform),
+            None => new_aabb.clone(),
+        },
+        local: new_aabb.clone(),
+        sphere,
+        frame,
+    };
+    match output.skinned_aabb {
+        Some(mut skinned_aabb) => {
+            if !skinned_aabb.abs_diff_eq(&new_skinned_aabb, config.change_epsilon) {
+                *skinned_aabb = new_skinned_aabb;
+            }
+        }
+        None => {
+            commands.entity(entity).insert(new_skinned_aabb);
+        }
+    }
+    match output.aabb {
+        Some(mut aabb) => {
+            if aabb.center != new_aabb.center || aabb.half_extents != new_aabb.half_extents {
+                *aabb = new_aabb;
+            }
+        }
+        None => {
+            commands.entity(entity).insert(new_aabb);
+        }
+    }
+}
+
+/// `aabb` as written by [`write_skinned_aabb`] to `output`, without its [`SkinnedAabbMargin`] and
+/// [`AabbPadding`], so it can be written again without being padded twice.
+fn strip_output_padding(aabb: &Aabb, output: &SkinnedAabbOutputItem) -> Aabb {
+    let mut aabb = aabb.clone();
+    if let Some(padding) = output.padding {
+        aabb = Aabb::from_min_max(
+            Vec3::from(aabb.min()) + padding.min.max(Vec3::ZERO),
+            Vec3::from(aabb.max()) - padding.max.max(Vec3::ZERO),
+        );
+    }
+    if let Some(margin) = output.margin {
+        aabb.half_extents -= Vec3A::from(margin.0.max(Vec3::ZERO));
+    }
+    aabb.half_extents = aabb.half_extents.max(Vec3A::ZERO);
+    aabb
+}
+
+/// The smallest [`Aabb`] containing both `a` and `b`.
+pub fn aabb_union(a: &Aabb, b: &Aabb) -> Aabb {
+    Aabb::from_min_max(a.min().min(b.min()).into(), a.max().max(b.max()).into())
+}
+
+/// The bounds of a mesh's skinned positions over two frames, for effects like motion blur or TAA
+/// that read both the previous and the current pose.
+///
+/// bevy doesn't keep a cache of skinned positions, so this takes the position buffers of a
+/// renderer that does, once they are read back, e.g. its previous and current frame history
+/// buffers. Returns `None` if neither buffer has a finite position.
+pub fn history_aabb(previous: &[Vec3], current: &[Vec3]) -> Option<Aabb> {
+    match (compute_aabb(previous), compute_aabb(current)) {
+        (Some(previous), Some(current)) => Some(aabb_union(&previous, &current)),
+        (previous, current) => previous.or(current),
+    }
+}
+
+/// Skin the vertices of `mesh` with the current pose of its joints.
+/// Returns the world space vertex positions, or `None` if the mesh is missing
+/// skinning attributes or the joints can't be resolved.
+pub fn get_skinned_vertex_locations(
+    mesh: &Mesh,
+    skinned_mesh: &SkinnedMesh,
+    joint_query: &Query<&GlobalTransform>,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+) -> Option<Vec<Vec3>> {
+    let mut joints = Vec::new();
+    let mut ws_positions = Vec::new();
+    get_skinned_vertex_locations_into(
+        mesh,
+        skinned_mesh,
+        joint_query,
+        inverse_bindposes,
+        &mut joints,
+        &mut ws_positions,
+    )?;
+    Some(ws_positions)
+}
+
+/// Like [`get_skinned_vertex_locations`], building the joint matrices into `joints` and the
+/// world space vertex positions into `ws_positions`, both cleared first. Systems calling this
+/// every frame can keep both buffers in a [`Local`] to reuse their allocations.
+pub fn get_skinned_vertex_locations_into(
+    mesh: &Mesh,
+    skinned_mesh: &SkinnedMesh,
+    joint_query: &Query<&GlobalTransform>,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+    joints: &mut Vec<Mat4>,
+    ws_positions: &mut Vec<Vec3>,
+) -> Option<()> {
+    // get skinned mesh joint models
+    build_joint_matrices(skinned_mesh, inverse_bindposes, joint_query, joints)?;
+    skin_vertices_into(mesh, joints, ZeroWeights::BindToJointZero, ws_positions)
+}
+
+/// The world space [`Aabb`] of `mesh` skinned with the current pose of its joints,
+/// or `None` if the mesh is missing skinning attributes or the joints can't be resolved.
+/// This is the computation [`ComputeSkinnedAabbPlugin`] runs, without writing the result anywhere.
+pub fn compute_skinned_aabb(
+    mesh: &Mesh,
+    skinned_mesh: &SkinnedMesh,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+    joints: &Query<&GlobalTransform>,
+) -> Option<Aabb> {
+    let mut joint_matrices = Vec::new();
+    build_joint_matrices(skinned_mesh, inverse_bindposes, joints, &mut joint_matrices)?;
+    skinned_mesh_aabb(mesh, &joint_matrices)
+}
+
+/// The [`Aabb`] of `mesh` skinned with `joint_matrices`, as built by [`build_joint_matrices`],
+/// without any ECS types involved, e.g. to compute bounds offline in an asset processor.
+/// Returns `None` if the mesh is missing skinning attributes, has a joint index past the end of
+/// `joint_matrices`, or has no finite skinned vertex.
+pub fn skinned_mesh_aabb(mesh: &Mesh, joint_matrices: &[Mat4]) -> Option<Aabb> {
+    let positions = mesh_positions(mesh)?;
+    let joint_indices = mesh_joint_indices(mesh)?;
+    if joint_indices
+        .iter()
+        .flatten()
+        .any(|&index| index as usize >= joint_matrices.len())
+    {
+        return None;
+    }
+    let joint_weights = mesh_joint_weights(mesh)?;
+    skinned_vertices_aabb(&positions, &joint_indices, &joint_weights, joint_matrices)
+}
+
+/// How the bounds of one pose differ from those of another, per axis, as returned by
+/// [`bounds_delta`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct AabbDelta {
+    /// How far the minimum corner moved.
+    pub min: Vec3A,
+    /// How far the maximum corner moved.
+    pub max: Vec3A,
+    /// How much the bounds grew along each axis, negative where they shrank.
+    pub size: Vec3A,
+}
+
+impl AabbDelta {
+    /// The change from `a` to `b`.
+    pub fn between(a: &Aabb, b: &Aabb) -> Self {
+        AabbDelta {
+            min: b.min() - a.min(),
+            max: b.max() - a.max(),
+            size: (b.half_extents - a.half_extents) * 2.0,
+        }
+    }
+}
+
+/// The bounds of `mesh` in two poses and how they differ, e.g. for tools comparing poses.
+/// `poses_a` and `poses_b` are the global transforms of the joints in each pose, combined with
+/// their `inverse_bindposes` as [`build_joint_matrices`] does, and both bounds are computed with
+/// [`skinned_mesh_aabb`]. Nothing is stored between calls.
+/// Returns `None` if either pose doesn't have one transform per bindpose or can't be bounded, e.g.
+/// because the mesh has a joint index past the end of `inverse_bindposes`.
+pub fn bounds_delta(
+    mesh: &Mesh,
+    inverse_bindposes: &[Mat4],
+    poses_a: &[Mat4],
+    poses_b: &[Mat4],
+) -> Option<(Aabb, Aabb, AabbDelta)> {
+    let bound = |poses: &[Mat4]| {
+        if poses.len() != inverse_bindposes.len() {
+            return None;
+        }
+        let joint_matrices: Vec<Mat4> = poses
+            .iter()
+            .zip(inverse_bindposes)
+            .map(|(pose, inverse_bindpose)| *pose * *inverse_bindpose)
+            .collect();
+        skinned_mesh_aabb(mesh, &joint_matrices)
+    };
+    let a = bound(poses_a)?;
+    let b = bound(poses_b)?;
+    let delta = AabbDelta::between(&a, &b);
+    Some((a, b, delta))
+}
+
+/// The [`Aabb`] of vertices skinned with `joint_matrices`, from plain slices of their attributes.
+/// The weights are normalized with [`normalize_weights`], and non-finite skinned positions are
+/// skipped as in [`compute_aabb`], as are vertices with a joint index past the end of
+/// `joint_matrices`. Vertices past the end of the shortest slice are ignored.
+/// Returns `None` if there is no finite skinned vertex.
+pub fn skinned_vertices_aabb(
+    positions: &[Vec3],
+    joint_indices: &[[u32; 4]],
+    joint_weights: &[Vec4],
+    joint_matrices: &[Mat4],
+) -> Option<Aabb> {
+    let mut bounds: Option<(Vec3, Vec3)> = None;
+    for ((position, indices), weights) in positions.iter().zip(joint_indices).zip(joint_weights) {
+        if indices
+            .iter()
+            .any(|&index| index as usize >= joint_matrices.len())
+        {
+            continue;
+        }
+        let p =
+            skin_model_normalized(joint_matrices, indices, *weights).transform_point3(*position);
+        if !p.is_finite() {
+            continue;
+        }
+        bounds = Some(match bounds {
+            Some((minimum, maximum)) => (minimum.min(p), maximum.max(p)),
+            None => (p, p),
+        });
+    }
+    bounds.map(|(minimum, maximum)| Aabb::from_min_max(minimum, maximum))
+}
+
+/// Like [`compute_skinned_aabb`], also returning the [`compute_bounding_sphere`] of the same
+/// skinned vertices, so they're only skinned once.
+pub fn compute_skinned_bounds(
+    mesh: &Mesh,
+    skinned_mesh: &SkinnedMesh,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+    joints: &Query<&GlobalTransform>,
+) -> Option<(Aabb, Sphere)> {
+    let ws_positions = get_skinned_vertex_locations(mesh, skinned_mesh, joints, inverse_bindposes)?;
+    Some((
+        compute_aabb(&ws_positions)?,
+        compute_bounding_sphere(&ws_positions)?,
+    ))
+}
+
+/// The skinning matrices of the joints of `skinned_mesh`, each joint's [`GlobalTransform`]
+/// times its inverse bindpose, written to `buffer`.
+///
+/// Like bevy's `SkinnedMeshJoints::build`, without its limit of [`MAX_JOINTS`] joints per mesh
+/// or its padding, so rigs with more joints than bevy can render are still bounded.
+/// Returns `None`, leaving `buffer` empty, if the inverse bindposes aren't loaded or a joint has
+/// no [`GlobalTransform`].
+pub fn build_joint_matrices(
+    skinned_mesh: &SkinnedMesh,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+    joint_query: &Query<&GlobalTransform>,
+    buffer: &mut Vec<Mat4>,
+) -> Option<()> {
+    buffer.clear();
+    let inverse_bindposes = inverse_bindposes.get(&skinned_mesh.inverse_bindposes)?;
+    for (inverse_bindpose, joint) in inverse_bindposes.iter().zip(&skinned_mesh.joints) {
+        match joint_query.get(*joint) {
+            Ok(joint) => buffer.push(joint.affine() * *inverse_bindpose),
+            Err(_) => {
+                buffer.clear();
+                return None;
+            }
+        }
+    }
+    Some(())
+}
+
+/// The most joints bevy skins a mesh with when rendering, the rest are ignored by its shader.
+pub const MAX_JOINTS: usize = 256;
+
+/// Warn once per entity when [`build_joint_matrices`] fails for a reason other than the
+/// inverse bindposes still loading, naming the first joint without a [`GlobalTransform`].
+/// Returns whether it warned, i.e. the joints just broke.
+pub(crate) fn warn_unbuildable_joints(
+    entity: Entity,
+    skinned_mesh: &SkinnedMesh,
+    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
+    joint_query: &Query<&GlobalTransform>,
+    warned: &mut HashSet<Entity>,
+) -> bool {
+    if !inverse_bindposes.contains(&skinned_mesh.inverse_bindposes) || !warned.insert(entity) {
+        return false;
+    }
+    match skinned_mesh
+        .joints
+        .iter()
+        .enumerate()
+        .find(|(_, joint)| !joint_query.contains(**joint))
+    {
+        Some((index, joint)) => warn!(
+            "Skinned mesh {:?} has {} joints but joint {} ({:?}) is despawned or has no \
+            GlobalTransform, its Aabb won't be updated until it's back",
+            entity,
+            skinned_mesh.joints.len(),
+            index,
+            joint
+        ),
+        None => warn!(
+            "Skinned mesh {:?} has {} joints but its joint matrices can't be built, \
+            its Aabb won't be updated",
+            entity,
+            skinned_mesh.joints.len()
+        ),
+    }
+    true
+}
+
+/// Warn once per entity when it has more joints than bevy renders, as its bounds then cover a
+/// pose bevy doesn't draw.
+pub(crate) fn warn_over_max_joints(
+    entity: Entity,
+    skinned_mesh: &SkinnedMesh,
+    warned: &mut HashSet<Entity>,
+) {
+    if skinned_mesh.joints.len() > MAX_JOINTS && warned.insert(entity) {
+        warn!(
+            "Skinned mesh {:?} has {} joints, more than the {} bevy renders, \
+            its Aabb is computed with all of them",
+            entity,
+            skinned_mesh.joints.len(),
+            MAX_JOINTS
+        );
+    }
+}
+
+/// One past the largest of `joint_indices`, the number of joints they need.
+pub fn referenced_joint_count(joint_indices: &[[u32; 4]]) -> usize {
+    joint_indices
+        .iter()
+        .flatten()
+        .max()
+        .map_or(0, |index| *index as usize + 1)
+}
+
+/// The number of vertices with a joint index past the `joint_count` joints of their skeleton,
+/// for which [`ComputeSkinnedAabbPlugin`] leaves the mesh's bounds as they were. Many of them
+/// usually means the mesh is skinned with the wrong skeleton.
+pub fn out_of_range_joint_indices(joint_indices: &[[u32; 4]], joint_count: usize) -> usize {
+    joint_indices
+        .iter()
+        .filter(|indices| indices.iter().any(|index| *index as usize >= joint_count))
+        .count()
+}
+
+/// Whether the `joint_count` joints the vertices of `mesh` need are all in `joints`. Otherwise
+/// the vertices would index past the joint matrices, so the entity is skipped until its mesh and
+/// skin agree again, e.g. once both are hot reloaded. Warns once per mismatch with how many
+/// vertices are out of range.
+pub(crate) fn joint_indices_in_range(
+    entity: Entity,
+    mesh: &Mesh,
+    joints: &[Mat4],
+    joint_count: usize,
+    warned: &mut HashSet<Entity>,
+) -> bool {
+    if joint_count <= joints.len() {
+        warned.remove(&entity);
+        return true;
+    }
+    if warned.insert(entity) {
+        let out_of_range = mesh_joint_indices(mesh).map_or(0, |indices| {
+            out_of_range_joint_indices(&indices, joints.len())
+        });
+        warn!(
+            "Skinned mesh {:?} has {} vertices with joint indices up to {}, past its {} joints, \
+            it keeps its previous Aabb until its mesh and skin agree",
+            entity,
+            out_of_range,
+            joint_count - 1,
+            joints.len()
+        );
+    }
+    false
+}
+
+/// For each vertex of `mesh`, whether a triangle of its index buffer references it.
+/// Out of range indices are ignored. Returns `None` if the mesh isn't indexed.
+pub fn referenced_vertices(mesh: &Mesh) -> Option<Vec<bool>> {
+    let mut referenced = vec![false; mesh.count_vertices()];
+    for index in mesh.indices()?.iter() {
+        if let Some(referenced) = referenced.get_mut(index) {
+            *referenced = true;
+        }
+    }
+    Some(referenced)
+}
+
+/// Skin the vertices of `mesh` with `joints`, the joint matrices premultiplied
+/// by their inverse bindposes as built by [`build_joint_matrices`].
+/// Vertices whose weights are all zero are bound to joint 0, so there is one position per vertex.
+/// Returns `None` if the mesh is missing skinning attributes.
+pub fn skin_vertices(mesh: &Mesh, joints: &[Mat4]) -> Option<Vec<Vec3>> {
+    skin_vertices_with(mesh, joints, ZeroWeights::BindToJointZero)
+}
+
+/// Like [`skin_vertices`], leaving out the vertices whose weights are all zero
+/// if `zero_weights` is [`ZeroWeights::Skip`].
+pub fn skin_vertices_with(
+    mesh: &Mesh,
+    joints: &[Mat4],
+    zero_weights: ZeroWeights,
+) -> Option<Vec<Vec3>> {
+    let mut ws_positions = Vec::new();
+    skin_vertices_into(mesh, joints, zero_weights, &mut ws_positions)?;
+    Some(ws_positions)
+}
+
+/// Like [`skin_vertices_with`], skinning into `ws_positions` after clearing it, so its
+/// allocation can be reused from one frame to the next.
+pub fn skin_vertices_into(
+    mesh: &Mesh,
+    joints: &[Mat4],
+    zero_weights: ZeroWeights,
+    ws_positions: &mut Vec<Vec3>,
+) -> Option<()> {
+    skin_sampled_vertices_into(mesh, joints, zero_weights, 1, ws_positions)
+}
+
+/// Like [`skin_vertices_into`], but only skinning every `stride`th vertex `zero_weights` keeps,
+/// the vertices [`AabbQuality::Sampled`] bounds.
+pub fn skin_sampled_vertices_into(
+    mesh: &Mesh,
+    joints: &[Mat4],
+    zero_weights: ZeroWeights,
+    stride: usize,
+    ws_positions: &mut Vec<Vec3>,
+) -> Option<()> {
+    // Get required vertex attributes
+    let positions = mesh_positions(mesh)?;
+    let mesh_indices = mesh_joint_indices(mesh)?;
+    let mesh_weights = mesh_joint_weights(mesh)?;
+
+    // Use skin model to get world space vertex positions
+    let stride = stride.max(1);
+    ws_positions.clear();
+    ws_positions.reserve(positions.len().div_ceil(stride));
+
+    let kept = positions
+        .iter()
+        .zip(&mesh_indices)
+        .zip(mesh_weights)
+        .filter(|(_, weights)| zero_weights.keep(*weights))
+        .step_by(stride);
+    for ((pos, indices), weights) in kept {
+        let model = skin_model_normalized(joints, indices, weights);
+        let ws_pos = model.transform_point3(*pos);
+        ws_positions.push(ws_pos);
+    }
+
+    Some(())
+}
+
+/// Like [`skin_vertices_with`], also returning the normal of each kept vertex skinned with the
+/// inverse transpose of its skinning matrix and renormalized. Zero length normals, and those of
+/// vertices whose skinning matrix can't be inverted, are skinned to zero.
+/// Returns `None` if the mesh is missing skinning attributes or `Float32x3` normals.
+pub fn skin_vertices_with_normals(
+    mesh: &Mesh,
+    joints: &[Mat4],
+    zero_weights: ZeroWeights,
+) -> Option<(Vec<Vec3>, Vec<Vec3>)> {
+    let positions = mesh_positions(mesh)?;
+    let mesh_normals = match mesh.attribute(Mesh::ATTRIBUTE_NORMAL) {
+        Some(VertexAttributeValues::Float32x3(normals)) => normals,
+        _ => return None,
+    };
+    let mesh_indices = mesh_joint_indices(mesh)?;
+    let mesh_weights = mesh_joint_weights(mesh)?;
+
+    let mut ws_positions = Vec::with_capacity(positions.len());
+    let mut ws_normals = Vec::with_capacity(positions.len());
+    for (((pos, normal), indices), weights) in positions
+        .iter()
+        .zip(mesh_normals)
+        .zip(&mesh_indices)
+        .zip(mesh_weights)
+    {
+        if !zero_weights.keep(weights) {
+            continue;
+        }
+        let model = skin_model_normalized(joints, indices, weights);
+        ws_positions.push(model.transform_point3(*pos));
+        let normal_matrix = Mat3::from_mat4(model).inverse().transpose();
+        ws_normals.push((normal_matrix * Vec3::from(*normal)).normalize_or_zero());
+    }
+
+    Some((ws_positions, ws_normals))
+}
+
+/// The bounds of the vertices influenced by each joint of a skinned mesh in that joint's space,
+/// built once from the bind pose for [`AabbQuality::PerJoint`], and rebuilt when the mesh
+/// handle changes or its mesh or inverse bindposes asset is modified.
+///
+/// Each frame the corners of every box are moved with their joint and bounded together.
+/// A skinned vertex is a weighted average of where each of its joints would move it,
+/// so as long as the weights are positive it lies inside the result.
+#[derive(Component, Clone, Debug)]
+pub struct JointAabbs {
+    /// Indexed by joint, `None` for joints that don't influence any vertex.
+    pub aabbs: Vec<Option<Aabb>>,
+    /// The inverse of each inverse bindpose, inverted once instead of by [`joint_transform`] every
+    /// frame.
+    bindposes: Vec<Mat4>,
+}
+
+impl JointAabbs {
+    /// Bound the vertices of `mesh` influenced by each joint.
+    /// Returns `None` if the mesh is missing skinning attributes.
+    pub fn new(mesh: &Mesh, inverse_bindposes: &[Mat4]) -> Option<Self> {
+        let positions = mesh_positions(mesh)?;
+        let mesh_indices = mesh_joint_indices(mesh)?;
+        let mesh_weights = mesh_joint_weights(mesh)?;
+
+        let mut joint_positions = vec![Vec::new(); inverse_bindposes.len()];
+        for ((pos, indices), weights) in positions.iter().zip(&mesh_indices).zip(mesh_weights) {
+            for (index, weight) in indices.iter().zip(weights.to_array()) {
+                if weight > 0.0 {
+                    if let Some(inverse_bindpose) = inverse_bindposes.get(*index as usize) {
+                        joint_positions[*index as usize]
+                            .push(inverse_bindpose.transform_point3(*pos));
+                    }
+                }
+            }
+        }
+
+        Some(JointAabbs {
+            aabbs: joint_positions
+                .iter()
+                .map(|positions| compute_aabb(positions))
+                .collect(),
+            bindposes: inverse_bindposes.iter().map(Mat4::inverse).collect(),
+        })
+    }
+
+    /// The corners of each joint's box moved by `joints`, the joint matrices as passed to
+    /// [`skin_vertices`]. Their bounds contain the skinned vertices.
+    pub fn skinned_corners(&self, joints: &[Mat4]) -> Vec<Vec3> {
+        let mut corners = Vec::with_capacity(self.aabbs.len() * 8);
+        for ((aabb, bindpose), joint) in self.aabbs.iter().zip(&self.bindposes).zip(joints) {
+            if let Some(aabb) = aabb {
+                let joint = *joint * *bindpose;
+                let (minimum, maximum) = (Vec3::from(aabb.min()), Vec3::from(aabb.max()));
+                for corner in 0..8 {
+                    let corner = Vec3::select(
+                        BVec3::new(corner & 1 != 0, corner & 2 != 0, corner & 4 != 0),
+                        maximum,
+                        minimum,
+                    );
+                    corners.push(joint.transform_point3(corner));
+                }
+            }
+        }
+        corners
+    }
+}
+
+/// Transform each vertex of `mesh` by its own matrix in `transforms`, for free-form deformation
+/// that isn't driven by a shared set of joints. No joint indices or weights are read.
+/// Bound the result with [`compute_aabb`].
+/// Returns `None` if the mesh has no `Float32x3` positions or there isn't one transform per vertex.
+pub fn deform_vertices(mesh: &Mesh, transforms: &[Mat4]) -> Option<Vec<Vec3>> {
+    let mesh_positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions,
+        _ => return None,
+    };
+    if mesh_positions.len() != transforms.len() {
+        return None;
+    }
+    Some(
+        mesh_positions
+            .iter()
+            .zip(transforms)
+            .map(|(pos, transform)| transform.transform_point3(Vec3::from(*pos)))
+            .collect(),
+    )
+}
+
+/// Like [`skin_vertices`], but returns two positions per vertex, offset by `thickness` either way
+/// along its skinned tangent. Returns `None` if the mesh is missing skinning attributes
+/// or `Float32x4` tangents.
+pub fn skin_vertices_along_tangents(
+    mesh: &Mesh,
+    joints: &[Mat4],
+    thickness: f32,
+) -> Option<Vec<Vec3>> {
+    let positions = mesh_positions(mesh)?;
+    let mesh_tangents = match mesh.attribute(Mesh::ATTRIBUTE_TANGENT) {
+        Some(VertexAttributeValues::Float32x4(tangents)) => tangents,
+        _ => return None,
+    };
+    let mesh_indices = mesh_joint_indices(mesh)?;
+    let mesh_weights = mesh_joint_weights(mesh)?;
+
+    let mut ws_positions = Vec::with_capacity(positions.len() * 2);
+    for (((pos, tangent), indices), weights) in positions
+        .iter()
+        .zip(mesh_tangents)
+        .zip(&mesh_indices)
+        .zip(mesh_weights)
+    {
+        let model = skin_model_normalized(joints, indices, weights);
+        let ws_pos = model.transform_point3(*pos);
+        let offset = model
+            .transform_vector3(Vec4::from(*tangent).truncate())
+            .normalize_or_zero()
+            * thickness;
+        ws_positions.push(ws_pos + offset);
+        ws_positions.push(ws_pos - offset);
+    }
+
+    Some(ws_positions)
+}
+
+/// Skin the vertices of `mesh` with `joints` like [`skin_vertices_with`], returning the two
+/// opposite corners of the box around the sphere of each vertex, whose radius is read from
+/// [`ATTRIBUTE_POINT_RADIUS`] and scaled by the [`max_axis_scale`] of its skinning matrix.
+/// Negative radii count as zero.
+/// Returns `None` if the mesh is missing skinning attributes or radii.
+pub fn skin_point_spheres(
+    mesh: &Mesh,
+    joints: &[Mat4],
+    zero_weights: ZeroWeights,
+) -> Option<Vec<Vec3>> {
+    let positions = mesh_positions(mesh)?;
+    let mesh_radii = mesh_point_radii(mesh)?;
+    let mesh_indices = mesh_joint_indices(mesh)?;
+    let mesh_weights = mesh_joint_weights(mesh)?;
+
+    let mut ls_corners = Vec::with_capacity(positions.len() * 2);
+    for (((pos, radius), indices), weights) in positions
+        .iter()
+        .zip(mesh_radii)
+        .zip(&mesh_indices)
+        .zip(mesh_weights)
+    {
+        if !zero_weights.keep(weights) {
+            continue;
+        }
+        let model = skin_model_normalized(joints, indices, weights);
+        let ls_pos = model.transform_point3(*pos);
+        let radius = Vec3::splat(radius.max(0.0) * max_axis_scale(&model));
+        ls_corners.push(ls_pos - radius);
+        ls_corners.push(ls_pos + radius);
+    }
+
+    Some(ls_corners)
+}
+
+/// The length of the longest axis of `matrix`, e.g. 2 for a joint stretched to twice its size
+/// along one axis and squashed along the others.
+pub fn max_axis_scale(matrix: &Mat4) -> f32 {
+    let matrix = Mat3::from_mat4(*matrix);
+    matrix
+        .x_axis
+        .length()
+        .max(matrix.y_axis.length())
+        .max(matrix.z_axis.length())
+}
+
+/// The radii of [`ATTRIBUTE_POINT_RADIUS`], if `mesh` has them.
+fn mesh_point_radii(mesh: &Mesh) -> Option<&[f32]> {
+    match mesh.attribute(ATTRIBUTE_POINT_RADIUS) {
+        Some(VertexAttributeValues::Float32(radii)) => Some(radii),
+        _ => None,
+    }
+}
+
+/// Warn once per entity when it has a [`PointRadius::Attribute`] but no radii to read.
+fn warn_missing_point_radii(entity: Entity, warned: &mut HashSet<Entity>) {
+    if warned.insert(entity) {
+        warn!(
+            "Skinned mesh {:?} has a PointRadius::Attribute but no Float32 ATTRIBUTE_POINT_RADIUS, \
+            its Aabb ignores the radii",
+            entity
+        );
+    }
+}
+
+/// Log once per entity why its bounds couldn't be computed, they keep their previous value.
+fn report_aabb_error(entity: Entity, error: AabbError, warned: &mut HashSet<Entity>) {
+    if warned.insert(entity) {
+        error!(
+            "Skinned mesh {:?} can't be bounded, {}, it keeps its previous Aabb",
+            entity, error
+        );
+    }
+}
+
+/// Read the joint indices of `mesh`, widened to `u32` whichever integer format they are stored in.
+/// Returns `None` if the attribute is missing or isn't `Uint8x4`, `Uint16x4` or `Uint32x4`.
+pub fn mesh_joint_indices(mesh: &Mesh) -> Option<Vec<[u32; 4]>> {
+    match mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX)? {
+        VertexAttributeValues::Uint32x4(indices) => Some(indices.clone()),
+        VertexAttributeValues::Uint16x4(indices) => {
+            Some(indices.iter().map(|i| i.map(u32::from)).collect())
+        }
+        VertexAttributeValues::Uint8x4(indices) => {
+            Some(indices.iter().map(|i| i.map(u32::from)).collect())
+        }
+        _ => None,
+    }
+}
+
+/// Read the positions of `mesh` as `f32`s, whichever format they are stored in.
+///
+/// Bevy's glTF loader already converts positions and weights to floats, the other formats are
+/// for meshes built with a [`MeshVertexAttribute`] of the same id and a quantized format, to
+/// skin them in a custom shader.
+/// Normalized integers, as written by `KHR_mesh_quantization`, are mapped to `-1..=1` or `0..=1`
+/// and other integers keep their value. The rest of the dequantization is the transform of the
+/// mesh's node, which the mesh entity already has. Returns `None` if the attribute is missing or
+/// isn't `Float32x3`, `Float32x4` or one of the 8 and 16 bit four component integer formats.
+pub fn mesh_positions(mesh: &Mesh) -> Option<Vec<Vec3>> {
+    fn read<T: Copy>(values: &[[T; 4]], to_f32: impl Fn(T) -> f32) -> Vec<Vec3> {
+        values
+            .iter()
+            .map(|v| Vec3::new(to_f32(v[0]), to_f32(v[1]), to_f32(v[2])))
+            .collect()
+    }
+    Some(match mesh.attribute(Mesh::ATTRIBUTE_POSITION)? {
+        VertexAttributeValues::Float32x3(positions) => {
+            positions.iter().copied().map(Vec3::from).collect()
+        }
+        VertexAttributeValues::Float32x4(positions) => read(positions, |v| v),
+        VertexAttributeValues::Snorm16x4(positions) => {
+            read(positions, |v| (v as f32 / i16::MAX as f32).max(-1.0))
+        }
+        VertexAttributeValues::Unorm16x4(positions) => {
+            read(positions, |v| v as f32 / u16::MAX as f32)
+        }
+        VertexAttributeValues::Sint16x4(positions) => read(positions, f32::from),
+        VertexAttributeValues::Uint16x4(positions) => read(positions, f32::from),
+        VertexAttributeValues::Snorm8x4(positions) => {
+            read(positions, |v| (v as f32 / i8::MAX as f32).max(-1.0))
+        }
+        VertexAttributeValues::Unorm8x4(positions) => {
+            read(positions, |v| v as f32 / u8::MAX as f32)
+        }
+        VertexAttributeValues::Sint8x4(positions) => read(positions, f32::from),
+        VertexAttributeValues::Uint8x4(positions) => read(positions, f32::from),
+        _ => return None,
+    })
+}
+
+/// Read the joint weights of `mesh` as `f32`s, whichever format they are stored in, like
+/// [`mesh_positions`].
+/// Normalized `u8` and `u16` weights, as written by `KHR_mesh_quantization`, are divided by 255
+/// and 65535. Returns `None` if the attribute is missing or isn't `Float32x4`, `Unorm8x4` or
+/// `Unorm16x4`.
+pub fn mesh_joint_weights(mesh: &Mesh) -> Option<Vec<Vec4>> {
+    Some(match mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT)? {
+        VertexAttributeValues::Float32x4(weights) => {
+            weights.iter().copied().map(Vec4::from).collect()
+        }
+        VertexAttributeValues::Unorm16x4(weights) => weights
+            .iter()
+            .map(|w| Vec4::from(w.map(f32::from)) / u16::MAX as f32)
+            .collect(),
+        VertexAttributeValues::Unorm8x4(weights) => weights
+            .iter()
+            .map(|w| Vec4::from(w.map(f32::from)) / u8::MAX as f32)
+            .collect(),
+        _ => return None,
+    })
+}
+
+/// Warn once per entity when its mesh is missing a skinning attribute, or has one that
+/// [`mesh_positions`], [`mesh_joint_indices`] or [`mesh_joint_weights`] can't read, naming the
+/// attribute and its format.
+pub(crate) fn warn_unreadable_attributes(
+    entity: Entity,
+    mesh: &Mesh,
+    warned: &mut HashSet<Entity>,
+) {
+    if warned.contains(&entity) {
+        return;
+    }
+    let attributes = [
+        (Mesh::ATTRIBUTE_POSITION, mesh_positions(mesh).is_some()),
+        (
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            mesh_joint_indices(mesh).is_some(),
+        ),
+        (
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            mesh_joint_weights(mesh).is_some(),
+        ),
+    ];
+    for (attribute, readable) in attributes {
+        if readable {
+            continue;
+        }
+        warned.insert(entity);
+        match mesh.attribute(attribute.id) {
+            Some(values) => warn!(
+                "Skinned mesh {:?} has {} in unsupported format {:?}, \
+                its Aabb won't be updated",
+                entity,
+                attribute.name,
+                VertexFormat::from(values)
+            ),
+            None => warn!(
+                "Skinned mesh {:?} has no {} attribute, its Aabb won't be updated",
+                entity, attribute.name
+            ),
+        }
+        return;
+    }
+}
+
+/// Scale `weights` so they sum to 1, as exporters often quantize them to a sum slightly off.
+/// If they sum to zero the vertex is bound to joint 0 alone.
+pub fn normalize_weights(indexes: &[u32; 4], weights: Vec4) -> ([u32; 4], Vec4) {
+    let sum = weights.dot(Vec4::ONE);
+    if is_unweighted(weights) {
+        ([0; 4], Vec4::X)
+    } else {
+        (*indexes, weights / sum)
+    }
+}
+
+/// [`skin_model`] with `weights` normalized first by [`normalize_weights`].
+pub fn skin_model_normalized(joint_matrices: &[Mat4], indexes: &[u32; 4], weights: Vec4) -> Mat4 {
+    let (indexes, weights) = normalize_weights(indexes, weights);
+    skin_model(joint_matrices, &indexes, weights)
+}
+
+/// Blend the joint matrices influencing a vertex, the same way the skinning vertex shader does.
+/// The weights are used as is, see [`skin_model_normalized`] for weights that may not sum to 1.
+/// Weights that are all zero give a zero matrix, collapsing the vertex to the origin, so the
+/// bounds computed by [`ComputeSkinnedAabbPlugin`] always go through [`skin_model_normalized`]
+/// and [`SkinnedAabbConfig::zero_weights`] instead.
+///
+/// This is the four influence case of [`skin_model_influences`], unrolled.
+pub fn skin_model(joint_matrices: &[Mat4], indexes: &[u32; 4], weights: Vec4) -> Mat4 {
+    weights.x * joint_matrices[indexes[0] as usize]
+        + weights.y * joint_matrices[indexes[1] as usize]
+        + weights.z * joint_matrices[indexes[2] as usize]
+        + weights.w * joint_matrices[indexes[3] as usize]
+}
+
+/// Like [`skin_model`], blending however many joints influence the vertex, e.g. the eight of
+/// exporters writing a second set of joints and weights. `indexes` and `weights` pair up, and
+/// must have the same length.
+pub fn skin_model_influences(joint_matrices: &[Mat4], indexes: &[u32], weights: &[f32]) -> Mat4 {
+    debug_assert_eq!(indexes.len(), weights.len());
+    indexes
+        .iter()
+        .zip(weights)
+        .fold(Mat4::ZERO, |model, (index, weight)| {
+            model + *weight * joint_matrices[*index as usize]
+        })
+}
+
+/// How the joints influencing a vertex are blended, to match the skinning of the renderer.
+/// Insert it on a skinned mesh to override [`SkinnedAabbConfig::skinning`].
+///
+/// Only the bounds skinned from the cached [`SkinnedMeshCpuData`] use it. Until the cache is
+/// built, and for [`AabbQuality::PerJoint`], [`TangentThickness`], [`PointRadius::Attribute`],
+/// [`SkinnedAabbConfig::percentile`] and [`AabbCompute::Gpu`] meshes, the joints are blended
+/// linearly.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SkinningMethod {
+    /// Blend the joint matrices, as bevy's shader does, see [`skin_model`].
+    #[default]
+    LinearBlend,
+    /// Blend the joints as dual quaternions, for renderers with a dual quaternion skinning
+    /// shader, see [`skin_point_dual_quaternion`]. Unlike linear blending it doesn't collapse
+    /// the mesh around twisted joints.
+    DualQuaternion,
+}
+
+/// A skinning matrix split into a unit dual quaternion and a scale, for
+/// [`SkinningMethod::DualQuaternion`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DualQuat {
+    /// The rotation.
+    pub real: Quat,
+    /// Half the translation as a pure quaternion, times `real`.
+    pub dual: Quat,
+    /// Applied before the rotation, as it can't be blended as a dual quaternion.
+    pub scale: Vec3,
+}
+
+impl From<Mat4> for DualQuat {
+    fn from(matrix: Mat4) -> Self {
+        let (scale, real, translation) = matrix.to_scale_rotation_translation();
+        let translation = Quat::from_xyzw(translation.x, translation.y, translation.z, 0.0);
+        DualQuat {
+            real,
+            dual: translation * real * 0.5,
+            scale,
+        }
+    }
+}
+
+/// Skin `position` with `joints` blended as dual quaternions, with `weights` normalized first by
+/// [`normalize_weights`].
+///
+/// Each joint is flipped to the hemisphere of the joint with the most weight before blending,
+/// so they blend the short way around. Their scales are blended linearly and applied first,
+/// like most dual quaternion skinning shaders do.
+pub fn skin_point_dual_quaternion(
+    joints: &[DualQuat],
+    indexes: &[u32; 4],
+    weights: Vec4,
+    position: Vec3,
+) -> Vec3 {
+    let (indexes, weights) = normalize_weights(indexes, weights);
+    let weights = weights.to_array();
+    let heaviest = (0..4)
+        .max_by(|a, b| weights[*a].total_cmp(&weights[*b]))
+        .unwrap_or(0);
+    let pivot = joints[indexes[heaviest] as usize];
+    let mut real = Vec4::ZERO;
+    let mut dual = Vec4::ZERO;
+    let mut scale = Vec3::ZERO;
+    for (index, weight) in indexes.iter().zip(weights) {
+        let joint = joints[*index as usize];
+        let weight = if joint.real.dot(pivot.real) < 0.0 {
+            -weight
+        } else {
+            weight
+        };
+        real += Vec4::from(joint.real) * weight;
+        dual += Vec4::from(joint.dual) * weight;
+        scale += joint.scale * weight.abs();
+    }
+    // Joints can only cancel out to zero if they're flipped, fall back to the heaviest one.
+    let length = real.length();
+    let (real, dual) = if length > f32::EPSILON {
+        (
+            Quat::from_vec4(real / length),
+            Quat::from_vec4(dual / length),
+        )
+    } else {
+        (pivot.real, pivot.dual)
+    };
+    let translation = dual * real.conjugate() * 2.0;
+    real * (scale * position) + Vec3::new(translation.x, translation.y, translation.z)
+}
+
+/// Why [`try_compute_aabb`] couldn't bound a set of positions, or
+/// [`ComputeSkinnedAabbPlugin`] a skinned mesh.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AabbError {
+    /// There was no position to bound.
+    Empty,
+    /// The position at `index` has a NaN or infinite coordinate.
+    NonFinite { index: usize },
+    /// The joint matrix at `index` has a NaN or infinite element, which would turn every vertex
+    /// it influences into one.
+    NonFiniteJoint { index: usize },
+}
+
+impl std::fmt::Display for AabbError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AabbError::Empty => write!(f, "no position to bound"),
+            AabbError::NonFinite { index } => write!(f, "position {index} isn't finite"),
+            AabbError::NonFiniteJoint { index } => write!(f, "joint matrix {index} isn't finite"),
+        }
+    }
+}
+
+impl std::error::Error for AabbError {}
+
+/// Like [`compute_aabb`], but failing on the first position with a NaN or infinite coordinate
+/// instead of skipping it, as it usually means a broken joint matrix or mesh, so bounds around
+/// the other positions would be wrong anyway.
+///
+/// Every half extent is grown to at least `min_half_extent`, so a single position or a flat set
+/// of positions can get bounds of a usable size.
+pub fn try_compute_aabb(values: &[Vec3], min_half_extent: f32) -> Result<Aabb, AabbError> {
+    if let Some(index) = values.iter().position(|p| !p.is_finite()) {
+        return Err(AabbError::NonFinite { index });
+    }
+    let mut aabb = compute_aabb(values).ok_or(AabbError::Empty)?;
+    aabb.half_extents = aabb.half_extents.max(Vec3A::splat(min_half_extent));
+    Ok(aabb)
+}
+
+/// Compute the Axis-Aligned Bounding Box of the mesh vertices in model space
+/// from https://github.com/bevyengine/bevy/blob/main/crates/bevy_render/src/mesh/mesh/mod.rs#L375
+///
+/// Vertices with a NaN or infinite coordinate are skipped, see [`try_compute_aabb`] to catch
+/// them instead. Returns `None` if there is no finite vertex, a single vertex gives a zero sized
+/// [`Aabb`].
+pub fn compute_aabb(values: &[Vec3]) -> Option<Aabb> {
+    let mut bounds: Option<(Vec3, Vec3)> = None;
+    for p in values.iter().filter(|p| p.is_finite()) {
+        bounds = Some(match bounds {
+            Some((minimum, maximum)) => (minimum.min(*p), maximum.max(*p)),
+            None => (*p, *p),
+        });
+    }
+    bounds.map(|(minimum, maximum)| Aabb::from_min_max(minimum, maximum))
+}
+
+/// The bounds of the `percentile` fraction of the finite positions in `values` closest to their
+/// centroid, leaving the others out as outliers.
+///
+/// At least one position is kept, and a `percentile` of 1 or more keeps every position, the same
+/// as [`compute_aabb`]. Positions as far from the centroid as the farthest one kept are kept too.
+pub fn compute_aabb_percentile(values: &[Vec3], percentile: f32) -> Option<Aabb> {
+    let finite: Vec<Vec3> = values.iter().copied().filter(|p| p.is_finite()).collect();
+    if finite.is_empty() || percentile >= 1.0 {
+        return compute_aabb(&finite);
+    }
+    let centroid = finite.iter().sum::<Vec3>() / finite.len() as f32;
+    let mut distances: Vec<f32> = finite
+        .iter()
+        .map(|p| p.distance_squared(centroid))
+        .collect();
+    let keep = ((percentile.max(0.0) * finite.len() as f32).ceil() as usize).clamp(1, finite.len());
+    let (_, threshold, _) = distances.select_nth_unstable_by(keep - 1, f32::total_cmp);
+    let threshold = *threshold;
+    let kept: Vec<Vec3> = finite
+        .into_iter()
+        .filter(|p| p.distance_squared(centroid) <= threshold)
+        .collect();
+    compute_aabb(&kept)
+}
+
+/// The bounds of the finite positions in `values` as integer multiples of `1 / scale`, the
+/// minimum rounded down and the maximum rounded up so they still contain every position.
+///
+/// Integer min and max give the same result in any order on any platform, unlike float min and
+/// max around signed zeros and NaNs, so lockstep simulations can compare the bounds exactly.
+/// Positions farther than `i32::MAX / scale` from the origin are clamped. Rounding each position
+/// is the same as rounding the float bounds, as rounding down or up keeps the order of values.
+pub fn fixed_point_bounds(values: &[Vec3], scale: f32) -> Option<(IVec3, IVec3)> {
+    let mut bounds: Option<(IVec3, IVec3)> = None;
+    for p in values.iter().filter(|p| p.is_finite()) {
+        // Float to int casts saturate.
+        let minimum = (*p * scale).floor().as_ivec3();
+        let maximum = (*p * scale).ceil().as_ivec3();
+        bounds = Some(match bounds {
+            Some((bounds_min, bounds_max)) => (bounds_min.min(minimum), bounds_max.max(maximum)),
+            None => (minimum, maximum),
+        });
+    }
+    bounds
+}
+
+/// Like [`compute_aabb`], with the bounds snapped outwards to multiples of `1 / scale` by
+/// [`fixed_point_bounds`].
+pub fn compute_aabb_fixed_point(values: &[Vec3], scale: f32) -> Option<Aabb> {
+    let (minimum, maximum) = fixed_point_bounds(values, scale)?;
+    Some(fixed_point_aabb(minimum, maximum, scale))
+}
+
+/// The [`Aabb`] between the fixed point bounds `minimum` and `maximum`, in units of `1 / scale`.
+fn fixed_point_aabb(minimum: IVec3, maximum: IVec3, scale: f32) -> Aabb {
+    Aabb::from_min_max(minimum.as_vec3() / scale, maximum.as_vec3() / scale)
+}
+
+/// Snap `aabb` outwards to multiples of `1 / scale`, see [`fixed_point_bounds`].
+fn snap_aabb(aabb: &Aabb, scale: f32) -> Aabb {
+    match fixed_point_bounds(&[aabb.min().into(), aabb.max().into()], scale) {
+        Some((minimum, maximum)) => fixed_point_aabb(minimum, maximum, scale),
+        None => aabb.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::render::mesh::PrimitiveTopology;
+
+    use super::*;
+
+    #[test]
+    fn u8_joint_indices() {
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, vec![[0.0f32, 1.0, 0.0]; 2]);
+        mesh.insert_attribute(
+            MeshVertexAttribute {
+                format: VertexFormat::Uint8x4,
+                ..Mesh::ATTRIBUTE_JOINT_INDEX
+            },
+            VertexAttributeValues::Uint8x4(vec![[0, 0, 0, 0], [1, 0, 0, 0]]),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            vec![[1.0f32, 0.0, 0.0, 0.0]; 2],
+        );
+        assert_eq!(
+            mesh_joint_indices(&mesh),
+            Some(vec![[0, 0, 0, 0], [1, 0, 0, 0]])
+        );
+        let joints = [
+            Mat4::IDENTITY,
+            Mat4::from_translation(Vec3::new(2.0, 0.0, 0.0)),
+        ];
+        assert_eq!(
+            skin_vertices(&mesh, &joints),
+            Some(vec![Vec3::new(0.0, 1.0, 0.0), Vec3::new(2.0, 1.0, 0.0)])
+        );
+    }
+
+    #[test]
+    fn aabb_of_finite_vertices() {
+        assert!(compute_aabb(&[]).is_none());
+        assert!(compute_aabb(&[Vec3::NAN, Vec3::new(f32::INFINITY, 0.0, 0.0)]).is_none());
+
+        let point = Vec3::new(1.0, -2.0, 3.0);
+        let aabb = compute_aabb(&[point]).unwrap();
+        assert_eq!(Vec3::from(aabb.center), point);
+        assert_eq!(Vec3::from(aabb.half_extents), Vec3::ZERO);
+
+        // Non-finite vertices are skipped, finite ones at the extremes of f32 are kept.
+        let aabb = compute_aabb(&[Vec3::NAN, Vec3::splat(f32::MAX), Vec3::ZERO]).unwrap();
+        assert_eq!(Vec3::from(aabb.max()), Vec3::splat(f32::MAX));
+        assert_eq!(Vec3::from(aabb.min()), Vec3::ZERO);
+    }
+
+    #[test]
+    fn history_covers_both_buffers() {
+        let previous = [Vec3::new(-1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)];
+        let current = [Vec3::new(2.0, 0.5, 0.0), Vec3::new(0.0, 0.0, 3.0)];
+        let aabb = history_aabb(&previous, &current).unwrap();
+        assert_eq!(Vec3::from(aabb.min()), Vec3::new(-1.0, 0.0, 0.0));
+        assert_eq!(Vec3::from(aabb.max()), Vec3::new(2.0, 1.0, 3.0));
+
+        // A buffer without any finite position leaves the other one's bounds.
+        let aabb = history_aabb(&[Vec3::NAN], &current).unwrap();
+        assert_eq!(Vec3::from(aabb.min()), Vec3::new(0.0, 0.0, 0.0));
+        assert!(history_aabb(&[], &[]).is_none());
+    }
+
+    #[test]
+    fn twisted_by_a_transform_per_vertex() {
+        // A row along X one above the axis, twisted around it by a further eighth turn per vertex,
+        // to half a turn at the end.
+        let positions: Vec<[f32; 3]> = (0..5).map(|x| [x as f32, 1.0, 0.0]).collect();
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        let transforms: Vec<Mat4> = (0..5)
+            .map(|x| Mat4::from_rotation_x(x as f32 * std::f32::consts::FRAC_PI_4))
+            .collect();
+        let deformed = deform_vertices(&mesh, &transforms).unwrap();
+        assert!(deformed[2].abs_diff_eq(Vec3::new(2.0, 0.0, 1.0), 1e-6));
+        let aabb = compute_aabb(&deformed).unwrap();
+        assert!(Vec3::from(aabb.min()).abs_diff_eq(Vec3::new(0.0, -1.0, 0.0), 1e-6));
+        assert!(Vec3::from(aabb.max()).abs_diff_eq(Vec3::new(4.0, 1.0, 1.0), 1e-6));
+
+        assert!(deform_vertices(&mesh, &transforms[1..]).is_none());
+    }
+
+    #[test]
+    fn skinned_vertices_from_slices() {
+        let positions = [
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 1.0, 0.0),
+            Vec3::new(0.0, 0.0, 1.0),
+        ];
+        let joint_indices = [[0, 0, 0, 0], [0, 1, 0, 0], [1, 0, 0, 0]];
+        let joint_weights = [Vec4::X, Vec4::new(0.5, 0.5, 0.0, 0.0), Vec4::X];
+        let joint_matrices = [
+            Mat4::from_translation(Vec3::new(0.0, 2.0, 0.0)),
+            Mat4::from_translation(Vec3::new(-4.0, 0.0, 0.0)),
+        ];
+        // Skinned to (1, 2, 0), halfway between (0, 3, 0) and (-4, 1, 0), and (-4, 0, 1).
+        let aabb =
+            skinned_vertices_aabb(&positions, &joint_indices, &joint_weights, &joint_matrices)
+                .unwrap();
+        assert_eq!(Vec3::from(aabb.min()), Vec3::new(-4.0, 0.0, 0.0));
+        assert_eq!(Vec3::from(aabb.max()), Vec3::new(1.0, 2.0, 1.0));
+
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            positions.iter().map(|p| p.to_array()).collect::<Vec<_>>(),
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            vec![[0u16, 0, 0, 0], [0, 1, 0, 0], [1, 0, 0, 0]],
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            joint_weights
+                .iter()
+                .map(|w| w.to_array())
+                .collect::<Vec<_>>(),
+        );
+        let from_mesh = skinned_mesh_aabb(&mesh, &joint_matrices).unwrap();
+        assert_eq!(from_mesh.min(), aabb.min());
+        assert_eq!(from_mesh.max(), aabb.max());
+
+        assert!(skinned_vertices_aabb(&[], &[], &[], &joint_matrices).is_none());
+        // The last vertex is bound to a joint there's no matrix for, so it's left out.
+        let joint_indices = [[0, 0, 0, 0], [0, 1, 0, 0], [2, 0, 0, 0]];
+        let aabb =
+            skinned_vertices_aabb(&positions, &joint_indices, &joint_weights, &joint_matrices)
+                .unwrap();
+        assert_eq!(Vec3::from(aabb.min()), Vec3::new(-2.0, 2.0, 0.0));
+        assert_eq!(Vec3::from(aabb.max()), Vec3::new(1.0, 2.0, 0.0));
+    }
+
+    #[test]
+    fn fixed_point_bounds_in_any_order() {
+        // Signed zeros, and a vertex left out for its NaN.
+        let values = [
+            Vec3::new(0.375, -0.0, 1.0625),
+            Vec3::new(-0.25, 0.0, f32::NAN),
+            Vec3::new(0.125, 0.75, -2.0),
+            Vec3::new(-0.0, 0.25, 0.0),
+        ];
+        let scale = 100.0;
+        let bounds = fixed_point_bounds(&values, scale).unwrap();
+        assert_eq!(bounds, (IVec3::new(0, 0, -200), IVec3::new(38, 75, 107)));
+        let mut reversed = values;
+        reversed.reverse();
+        assert_eq!(fixed_point_bounds(&reversed, scale), Some(bounds));
+        let mut rotated = values;
+        rotated.rotate_left(1);
+        assert_eq!(fixed_point_bounds(&rotated, scale), Some(bounds));
+
+        // The f32 bounds contain every finite value.
+        let aabb = compute_aabb_fixed_point(&values, scale).unwrap();
+        for p in values.iter().filter(|p| p.is_finite()) {
+            assert!(aabb.min().cmple((*p).into()).all() && aabb.max().cmpge((*p).into()).all());
+        }
+    }
+
+    #[test]
+    fn percentile_leaves_out_an_outlier() {
+        // 99 vertices up a column, and a stray one far off to the side.
+        let mut values: Vec<Vec3> = (0..99).map(|i| Vec3::Y * i as f32 * 0.01).collect();
+        values.push(Vec3::X * 100.0);
+
+        let aabb = compute_aabb_percentile(&values, 0.99).unwrap();
+        assert_eq!(Vec3::from(aabb.min()), Vec3::ZERO);
+        assert!((aabb.max().y - 0.98).abs() < 1e-6);
+        assert_eq!(aabb.max().x, 0.0);
+
+        let aabb = compute_aabb_percentile(&values, 1.0).unwrap();
+        assert_eq!(aabb.max().x, 100.0);
+    }
+
+    #[test]
+    fn dual_quaternions_keep_a_twist_out() {
+        // Halfway between no twist and half a turn around the bone along Y.
+        let joints = [Mat4::IDENTITY, Mat4::from_rotation_y(std::f32::consts::PI)];
+        let indexes = [0, 1, 0, 0];
+        let weights = Vec4::new(0.5, 0.5, 0.0, 0.0);
+        let position = Vec3::new(1.0, 0.5, 0.0);
+
+        let linear = skin_model(&joints, &indexes, weights).transform_point3(position);
+        assert!(linear.abs_diff_eq(Vec3::new(0.0, 0.5, 0.0), 1e-6));
+
+        let dual_quats: Vec<DualQuat> = joints.iter().copied().map(DualQuat::from).collect();
+        let dual = skin_point_dual_quaternion(&dual_quats, &indexes, weights, position);
+        // Turned a quarter either way, still one away from the bone.
+        assert!((dual.y - 0.5).abs() < 1e-6);
+        assert!((Vec2::new(dual.x, dual.z).length() - 1.0).abs() < 1e-6);
+        assert!(dual.x.abs() < 1e-6);
+
+        // A single joint moves the point as its matrix does, scale included.
+        let joint = Mat4::from_scale_rotation_translation(
+            Vec3::new(2.0, 1.0, 0.5),
+            Quat::from_rotation_z(0.7),
+            Vec3::new(1.0, -2.0, 3.0),
+        );
+        let moved = skin_point_dual_quaternion(&[joint.into()], &[0; 4], Vec4::X, position);
+        assert!(moved.abs_diff_eq(joint.transform_point3(position), 1e-5));
+    }
+
+    #[test]
+    fn bounds_delta_between_poses() {
+        // The bottom of a column on joint 0, its top on joint 1, both bound at the origin.
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            vec![[0.0f32, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 2.0, 0.0]],
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            vec![[0u16, 0, 0, 0], [0, 1, 0, 0], [1, 0, 0, 0]],
+        );
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            vec![
+                [1.0f32, 0.0, 0.0, 0.0],
+                [0.5, 0.5, 0.0, 0.0],
+                [1.0, 0.0, 0.0, 0.0],
+            ],
+        );
+        let inverse_bindposes = [Mat4::IDENTITY, Mat4::from_translation(-Vec3::Y * 2.0)];
+        let poses_a = [Mat4::IDENTITY, Mat4::from_translation(Vec3::Y * 2.0)];
+        // The top swung out along X and the whole column lifted.
+        let poses_b = [
+            Mat4::from_translation(Vec3::Y),
+            Mat4::from_translation(Vec3::new(3.0, 2.0, 0.0))
+                * Mat4::from_rotation_z(-std::f32::consts::FRAC_PI_2),
+        ];
+        let (a, b, delta) = bounds_delta(&mesh, &inverse_bindposes, &poses_a, &poses_b).unwrap();
+
+        // The column went from (0, 0, 0) to (0, 2, 0), up to from (0, 1, 0) to (3, 2, 0), the
+        // middle vertex halfway between (0, 2, 0) and (2, 2, 0).
+        let (a_min, a_max) = (Vec3A::ZERO, Vec3A::new(0.0, 2.0, 0.0));
+        let (b_min, b_max) = (Vec3A::new(0.0, 1.0, 0.0), Vec3A::new(3.0, 2.0, 0.0));
+        assert!(a.min().abs_diff_eq(a_min, 1e-6) && a.max().abs_diff_eq(a_max, 1e-6));
+        assert!(b.min().abs_diff_eq(b_min, 1e-6) && b.max().abs_diff_eq(b_max, 1e-6));
+        for axis in 0..3 {
+            assert!((delta.min[axis] - (b_min[axis] - a_min[axis])).abs() < 1e-6);
+            assert!((delta.max[axis] - (b_max[axis] - a_max[axis])).abs() < 1e-6);
+            let (size_a, size_b) = (a_max[axis] - a_min[axis], b_max[axis] - b_min[axis]);
+            assert!((delta.size[axis] - (size_b - size_a)).abs() < 1e-6);
+        }
+        assert!(delta.size.abs_diff_eq(Vec3A::new(3.0, -1.0, 0.0), 1e-6));
+
+        assert!(bounds_delta(&mesh, &inverse_bindposes, &poses_a, &poses_b[..1]).is_none());
+        // A vertex bound to a joint the skin doesn't have.
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_INDEX,
+            vec![[0u16, 0, 0, 0], [0, 2, 0, 0], [1, 0, 0, 0]],
+        );
+        assert!(bounds_delta(&mesh, &inverse_bindposes, &poses_a, &poses_b).is_none());
+        assert!(skinned_mesh_aabb(&mesh, &inverse_bindposes).is_none());
+    }
+
+    #[test]
+    fn aabb_errors() {
+        assert_eq!(try_compute_aabb(&[], 0.0).unwrap_err(), AabbError::Empty);
+        let values = [
+            Vec3::ZERO,
+            Vec3::new(1.0, f32::NAN, 0.0),
+            Vec3::splat(f32::INFINITY),
+        ];
+        assert_eq!(
+            try_compute_aabb(&values, 0.0).unwrap_err(),
+            AabbError::NonFinite { index: 1 }
+        );
+
+        // A single vertex is grown to the minimum half extent on every axis.
+        let point = Vec3::new(1.0, 2.0, 3.0);
+        let aabb = try_compute_aabb(&[point], 0.0).unwrap();
+        assert_eq!(Vec3::from(aabb.half_extents), Vec3::ZERO);
+        let aabb = try_compute_aabb(&[point], 0.01).unwrap();
+        assert_eq!(Vec3::from(aabb.center), point);
+        assert_eq!(Vec3::from(aabb.half_extents), Vec3::splat(0.01));
+        // Larger extents are kept.
+        let aabb = try_compute_aabb(&[Vec3::ZERO, Vec3::X], 0.01).unwrap();
+        assert_eq!(Vec3::from(aabb.half_extents), Vec3::new(0.5, 0.01, 0.01));
+    }
+}
@@ -4,27 +4,34 @@
 use std::f32::consts::PI;
 
 use bevy::{
-    pbr::{
-        wireframe::{Wireframe, WireframePlugin},
-        SkinnedMeshJoints,
-    },
+    input::mouse::{MouseMotion, MouseWheel},
     prelude::*,
-    render::{
-        mesh::{
-            skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
-            Indices, PrimitiveTopology, VertexAttributeValues,
-        },
-        primitives::Aabb,
+    render::mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Indices, PrimitiveTopology,
     },
 };
+use bevy_compute_skinned_aabb::{
+    ComputeSkinnedAabbPlugin, SkinnedAabbChanged, SkinnedAabbDebugConfig, SkinnedAabbDebugPlugin,
+};
 
 fn main() {
     App::new()
         .add_plugins(DefaultPlugins)
-        .add_plugin(WireframePlugin)
+        .add_plugin(ComputeSkinnedAabbPlugin)
+        .add_plugin(SkinnedAabbDebugPlugin)
+        .insert_resource(SkinnedAabbDebugConfig {
+            enabled: true,
+            vertex_markers: true,
+            show_aabb: true,
+            depth_test: true,
+            ..default()
+        })
         .add_startup_system(setup)
         .add_system(joint_animation)
-        .add_system(skinned_vertex_locations)
+        .add_system(orbit_camera)
+        .add_system(toggle_debug)
+        .add_system(log_aabb_changes)
         .run();
 }
 
@@ -32,8 +39,15 @@ fn main() {
 #[derive(Component)]
 struct AnimatedJoint;
 
-/// Construct a mesh and a skeleton with 2 joints for that mesh,
-///   and mark the second joint to be animated.
+/// Where the copies of the skinned mesh are placed.
+const MESH_OFFSETS: [Vec3; 3] = [
+    Vec3::new(-1.5, 0.0, 0.0),
+    Vec3::new(0.0, 0.0, 0.0),
+    Vec3::new(1.5, 0.0, -1.0),
+];
+
+/// Construct a mesh and a skeleton with 2 joints for each copy of that mesh,
+///   and mark the second joint of each to be animated.
 /// It is similar to the scene defined in `models/SimpleSkin/SimpleSkin.gltf`
 fn setup(
     mut commands: Commands,
@@ -42,10 +56,14 @@ fn setup(
     mut skinned_mesh_inverse_bindposes_assets: ResMut<Assets<SkinnedMeshInverseBindposes>>,
 ) {
     // Create a camera
-    commands.spawn_bundle(Camera3dBundle {
-        transform: Transform::from_xyz(-2.0, 2.5, 5.0).looking_at(Vec3::ZERO, Vec3::Y),
-        ..default()
-    });
+    let focus = Vec3::new(0.5, 1.0, 0.0);
+    let transform = Transform::from_xyz(-2.0, 3.0, 7.0).looking_at(focus, Vec3::Y);
+    commands
+        .spawn_bundle(Camera3dBundle {
+            transform,
+            ..default()
+        })
+        .insert(OrbitCamera::new(focus, transform.translation));
 
     // Create inverse bindpose matrices for a skeleton consists of 2 joints
     let inverse_bindposes =
@@ -122,67 +140,46 @@ fn setup(
 
     let mesh = meshes.add(mesh);
 
-    // Create joint entities
-    let joint_0 = commands
-        .spawn_bundle((Transform::default(), GlobalTransform::identity()))
-        .id();
-    let joint_1 = commands
-        .spawn_bundle((
-            AnimatedJoint,
-            Transform::identity(),
-            GlobalTransform::identity(),
-        ))
-        .id();
+    let material = materials.add(Color::rgb(0.5, 0.5, 0.5).into());
 
-    // Set joint_1 as a child of joint_0.
-    commands.entity(joint_0).push_children(&[joint_1]);
+    for offset in MESH_OFFSETS {
+        // Create joint entities
+        let joint_0 = commands
+            .spawn_bundle((
+                Transform::from_translation(offset),
+                GlobalTransform::identity(),
+            ))
+            .id();
+        let joint_1 = commands
+            .spawn_bundle((
+                AnimatedJoint,
+                Transform::identity(),
+                GlobalTransform::identity(),
+            ))
+            .id();
 
-    // Each joint in this vector corresponds to each inverse bindpose matrix in `SkinnedMeshInverseBindposes`.
-    let joint_entities = vec![joint_0, joint_1];
+        // Set joint_1 as a child of joint_0.
+        commands.entity(joint_0).push_children(&[joint_1]);
 
-    // Create skinned mesh renderer. Note that its transform doesn't affect the position of the mesh.
-    commands
-        .spawn_bundle(PbrBundle {
-            mesh,
-            material: materials.add(Color::rgb(0.5, 0.5, 0.5).into()),
-            ..default()
-        })
-        .insert(SkinnedMesh {
-            inverse_bindposes,
-            joints: joint_entities,
-        });
+        // Each joint in this vector corresponds to each inverse bindpose matrix in `SkinnedMeshInverseBindposes`.
+        let joint_entities = vec![joint_0, joint_1];
 
-    // debug cubes for each vertex
-    for _ in 0..10 {
+        // Create skinned mesh renderer. Note that its transform doesn't affect the position of the mesh,
+        // the bounds are brought into its local space for culling wherever it's placed.
         commands
             .spawn_bundle(PbrBundle {
-                mesh: meshes.add(Mesh::from(shape::Cube { size: 0.1 })),
+                mesh: mesh.clone(),
+                material: material.clone(),
+                transform: Transform::from_translation(offset),
                 ..default()
             })
-            .insert(DebugVertex);
+            .insert(SkinnedMesh {
+                inverse_bindposes: inverse_bindposes.clone(),
+                joints: joint_entities,
+            });
     }
-
-    // AABB debug cube
-    commands
-        .spawn_bundle(PbrBundle {
-            mesh: meshes.add(Mesh::from(shape::Cube { size: 1.0 })),
-            material: materials.add(Color::rgba(0.0, 0.0, 0.0, 0.0).into()),
-            transform: Transform::from_xyz(0.0, 0.5, 0.0),
-            ..default()
-        })
-        .insert(
-            // This enables wireframe drawing on this entity
-            Wireframe,
-        )
-        .insert(AABBDebugCube);
 }
 
-#[derive(Component)]
-struct DebugVertex;
-
-#[derive(Component)]
-struct AABBDebugCube;
-
 /// Animate the joint marked with [`AnimatedJoint`] component.
 fn joint_animation(time: Res<Time>, mut query: Query<&mut Transform, With<AnimatedJoint>>) {
     for mut transform in &mut query {
@@ -193,104 +190,83 @@ fn joint_animation(time: Res<Time>, mut query: Query<&mut Transform, With<Animat
     }
 }
 
-fn skinned_vertex_locations(
-    query: Query<(&Handle<Mesh>, &SkinnedMesh)>,
-    meshes: Res<Assets<Mesh>>,
-    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
-    joint_query: Query<&GlobalTransform>,
-    mut debug_vertex_cubes: Query<&mut Transform, (With<DebugVertex>, Without<AABBDebugCube>)>,
-    mut aabb_debug_cube: Query<&mut Transform, (With<AABBDebugCube>, Without<DebugVertex>)>,
-) {
-    for (mesh_h, skinned_mesh) in query.iter() {
-        if let Some(mesh) = meshes.get(mesh_h) {
-            let ws_positions =
-                get_skinned_vertex_locations(mesh, skinned_mesh, &joint_query, &inverse_bindposes);
-            if let Some(ws_positions) = ws_positions {
-                // update debug cube positions to match world space vertices
-                for (mut trans, ws_pos) in debug_vertex_cubes.iter_mut().zip(&ws_positions) {
-                    trans.translation = *ws_pos;
-                }
-
-                //compute world space aabb
-                let ws_aabb = compute_aabb(&ws_positions).unwrap();
+/// A camera circling `focus`, to look at the bounds from every side.
+#[derive(Component)]
+struct OrbitCamera {
+    focus: Vec3,
+    radius: f32,
+    yaw: f32,
+    pitch: f32,
+}
 
-                //update aabb debug cube
-                if let Some(mut trans) = aabb_debug_cube.iter_mut().next() {
-                    trans.translation = ws_aabb.center.into();
-                    trans.scale = (ws_aabb.half_extents * 2.0).into();
-                }
-            }
+impl OrbitCamera {
+    /// An orbit around `focus` going through `position`.
+    fn new(focus: Vec3, position: Vec3) -> Self {
+        let offset = position - focus;
+        Self {
+            focus,
+            radius: offset.length(),
+            yaw: offset.x.atan2(offset.z),
+            pitch: (offset.y / offset.length()).asin(),
         }
     }
 }
 
-fn get_skinned_vertex_locations(
-    mesh: &Mesh,
-    skinned_mesh: &SkinnedMesh,
-    joint_query: &Query<&GlobalTransform>,
-    inverse_bindposes: &Assets<SkinnedMeshInverseBindposes>,
-) -> Option<Vec<Vec3>> {
-    // Get required vertex attributes
-    let mesh_positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
-        Some(VertexAttributeValues::Float32x3(positions)) => positions,
-        _ => return None,
-    };
-
-    let mesh_indices = match mesh.attribute(Mesh::ATTRIBUTE_JOINT_INDEX) {
-        Some(VertexAttributeValues::Uint16x4(indices)) => indices,
-        _ => return None,
-    };
-    let mesh_weights = match mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT) {
-        Some(VertexAttributeValues::Float32x4(weights)) => weights,
-        _ => return None,
-    };
-
-    // get skinned mesh joint models
-    let mut joints = Vec::new();
-    if SkinnedMeshJoints::build(skinned_mesh, inverse_bindposes, joint_query, &mut joints).is_some()
-    {
-        // Use skin model to get world space vertex positions
-        let mut ws_positions = Vec::with_capacity(mesh_positions.len());
-
-        for ((pos, indices), weights) in mesh_positions.iter().zip(mesh_indices).zip(mesh_weights) {
-            let model = skin_model(&joints, indices, Vec4::from(*weights));
-            let ws_pos = model.transform_point3(Vec3::from(*pos));
-            ws_positions.push(ws_pos);
+/// Drag with the left mouse button to orbit, with the right one to pan, and scroll to zoom.
+fn orbit_camera(
+    buttons: Res<Input<MouseButton>>,
+    mut motion_events: EventReader<MouseMotion>,
+    mut wheel_events: EventReader<MouseWheel>,
+    mut cameras: Query<(&mut OrbitCamera, &mut Transform)>,
+) {
+    let motion: Vec2 = motion_events.iter().map(|event| &event.delta).sum();
+    let scroll: f32 = wheel_events.iter().map(|event| event.y).sum();
+    for (mut orbit, mut transform) in &mut cameras {
+        if buttons.pressed(MouseButton::Left) {
+            orbit.yaw -= motion.x * 0.005;
+            orbit.pitch = (orbit.pitch + motion.y * 0.005).clamp(-1.5, 1.5);
         }
+        if buttons.pressed(MouseButton::Right) {
+            // Move the focus in the camera's plane, faster the farther away it is.
+            let pan = (transform.right() * -motion.x + transform.up() * motion.y) * 0.002;
+            let radius = orbit.radius;
+            orbit.focus += pan * radius;
+        }
+        orbit.radius = (orbit.radius * (1.0 - scroll * 0.1)).max(0.5);
 
-        return Some(ws_positions);
+        let rotation = Quat::from_rotation_y(orbit.yaw) * Quat::from_rotation_x(-orbit.pitch);
+        transform.translation = orbit.focus + rotation * Vec3::Z * orbit.radius;
+        transform.look_at(orbit.focus, Vec3::Y);
     }
-    None
-}
-
-fn skin_model(joint_matrices: &[Mat4], indexes: &[u16; 4], weights: Vec4) -> Mat4 {
-    weights.x * joint_matrices[indexes[0] as usize]
-        + weights.y * joint_matrices[indexes[1] as usize]
-        + weights.z * joint_matrices[indexes[2] as usize]
-        + weights.w * joint_matrices[indexes[3] as usize]
 }
 
-const VEC3_MIN: Vec3 = Vec3::splat(std::f32::MIN);
-const VEC3_MAX: Vec3 = Vec3::splat(std::f32::MAX);
-
-/// Compute the Axis-Aligned Bounding Box of the mesh vertices in model space
-/// from https://github.com/bevyengine/bevy/blob/main/crates/bevy_render/src/mesh/mesh/mod.rs#L375
-pub fn compute_aabb(values: &[Vec3]) -> Option<Aabb> {
-    let mut minimum = VEC3_MAX;
-    let mut maximum = VEC3_MIN;
-    for p in values {
-        minimum = minimum.min(*p);
-        maximum = maximum.max(*p);
+/// Press `D` to toggle the debug drawing, `B` to show or hide the boxes, `V` to show or hide
+/// the vertex markers and `T` to draw the lines over everything or hide them behind the meshes.
+fn toggle_debug(keys: Res<Input<KeyCode>>, mut config: ResMut<SkinnedAabbDebugConfig>) {
+    if keys.just_pressed(KeyCode::D) {
+        config.enabled = !config.enabled;
+    }
+    if keys.just_pressed(KeyCode::B) {
+        config.show_aabb = !config.show_aabb;
     }
-    if minimum.x != std::f32::MAX
-        && minimum.y != std::f32::MAX
-        && minimum.z != std::f32::MAX
-        && maximum.x != std::f32::MIN
-        && maximum.y != std::f32::MIN
-        && maximum.z != std::f32::MIN
-    {
-        return Some(Aabb::from_min_max(minimum, maximum));
+    if keys.just_pressed(KeyCode::V) {
+        config.vertex_markers = !config.vertex_markers;
     }
+    if keys.just_pressed(KeyCode::T) {
+        config.depth_test = !config.depth_test;
+    }
+}
 
-    None
+/// Log every [`SkinnedAabbChanged`] event, to see how often the bounds move past the threshold.
+fn log_aabb_changes(mut events: EventReader<SkinnedAabbChanged>) {
+    for event in events.iter() {
+        info!(
+            "{:?} bounds moved: center {} -> {}, half extents {} -> {}",
+            event.entity,
+            event.previous.center,
+            event.new.center,
+            event.previous.half_extents,
+            event.new.half_extents
+        );
+    }
 }
@@ -0,0 +1,76 @@
+//! How much larger the bounds of a skinned mesh are than its skinned vertices.
+
+use bevy::{
+    math::Vec3A,
+    prelude::*,
+    render::primitives::{Aabb, Sphere},
+};
+
+use crate::compute_aabb;
+
+/// The bounding volume measured by [`SkinnedAabbMetrics`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VolumeKind {
+    /// The [`Aabb`] written by the plugin, larger than the vertices' own bounds with
+    /// approximate qualities or margins.
+    Aabb,
+    /// A sphere around the skinned vertices, centered on their bounds.
+    Sphere,
+}
+
+/// Written when [`SkinnedAabbConfig::metrics`](crate::SkinnedAabbConfig::metrics) is set.
+#[derive(Component, Clone, Copy, Debug, PartialEq)]
+pub struct SkinnedAabbMetrics {
+    /// Volume of the measured bounding volume divided by the volume of the exact [`Aabb`]
+    /// of the skinned vertices, 1 for a perfect fit.
+    pub tightness: f32,
+    /// The number of vertices with joint indices past the joints of the skeleton, see
+    /// [`out_of_range_joint_indices`](crate::out_of_range_joint_indices). Set while they keep the
+    /// mesh from being bounded, the rest of the metrics are then those of its last bounds.
+    pub clamped_index_count: usize,
+}
+
+/// The volume of `aabb`, with each axis at least a thousandth of the largest one
+/// so flat meshes still have a volume to compare against.
+fn aabb_volume(aabb: &Aabb) -> f32 {
+    let half_extents = Vec3::from(aabb.half_extents);
+    let min_half_extent = half_extents.max_element() * 1e-3;
+    let size = half_extents.max(Vec3::splat(min_half_extent)) * 2.0;
+    size.x * size.y * size.z
+}
+
+/// A sphere centered on the bounds of `positions`, reaching the farthest one.
+pub fn centered_bounding_sphere(positions: &[Vec3]) -> Option<Sphere> {
+    let aabb = compute_aabb(positions)?;
+    let radius = positions
+        .iter()
+        .filter(|p| p.is_finite())
+        .map(|p| Vec3A::from(*p).distance(aabb.center))
+        .fold(0.0, f32::max);
+    Some(Sphere {
+        center: aabb.center,
+        radius,
+    })
+}
+
+/// The tightness of a bounding volume of `kind` around the skinned vertices `positions`,
+/// see [`SkinnedAabbMetrics::tightness`]. `bounds` is the [`Aabb`] written for them.
+pub fn bounding_volume_tightness(
+    kind: VolumeKind,
+    positions: &[Vec3],
+    bounds: &Aabb,
+) -> Option<f32> {
+    let exact = compute_aabb(positions)?;
+    let exact_volume = aabb_volume(&exact);
+    if exact_volume <= 0.0 {
+        return None;
+    }
+    let volume = match kind {
+        VolumeKind::Aabb => aabb_volume(bounds),
+        VolumeKind::Sphere => {
+            let sphere = centered_bounding_sphere(positions)?;
+            4.0 / 3.0 * std::f32::consts::PI * sphere.radius.powi(3)
+        }
+    };
+    Some(volume / exact_volume)
+}
@@ -0,0 +1,134 @@
+//! Blend shapes applied on the CPU, as bevy 0.8 doesn't load or render morph targets: the
+//! weighted displacements of each target are added to the base positions of the mesh asset, which
+//! is then drawn and bounded in that shape.
+
+use bevy::{asset::HandleId, prelude::*};
+
+use crate::{mesh_positions, SkinnedAabbSystems};
+
+/// The blend shapes of a skinned mesh, each a displacement per vertex, blended by its
+/// [`MorphWeights`] into the positions of its mesh asset before the mesh is skinned and bounded.
+///
+/// The positions the mesh has the first time it's blended are kept as its base shape, and the
+/// mesh asset is rewritten in place whenever the weights change, so every entity sharing the
+/// asset takes the same shape: give each morphed entity its own mesh. A mesh replaced through
+/// its handle is read again, one reloaded under the same handle keeps the old base shape.
+#[derive(Component, Clone, Debug)]
+pub struct MorphTargets {
+    /// The displacement of every vertex for each target, in the order of the mesh's positions.
+    /// Vertices past the end of a target aren't displaced by it.
+    pub targets: Vec<Vec<Vec3>>,
+    /// How much of the way from the applied weights to the [`MorphWeights`] is blended each
+    /// frame, from 0 to 1. At 1, the default, the shape follows the weights right away; below
+    /// it eases in and out over a few frames. The bounds follow the blended shape every frame, so
+    /// they grow and shrink with it while still containing it.
+    pub smoothing: f32,
+    /// The weights last blended into the mesh.
+    applied: Vec<f32>,
+    /// The positions of the mesh without any target, and the mesh they were read from.
+    base: Option<(HandleId, Vec<Vec3>)>,
+}
+
+impl MorphTargets {
+    pub fn new(targets: Vec<Vec<Vec3>>) -> Self {
+        Self {
+            targets,
+            smoothing: 1.0,
+            applied: Vec::new(),
+            base: None,
+        }
+    }
+
+    /// Ease the shape towards the weights by `smoothing` of the way each frame.
+    pub fn with_smoothing(mut self, smoothing: f32) -> Self {
+        self.smoothing = smoothing;
+        self
+    }
+
+    /// The weights of each target the mesh asset currently has.
+    pub fn applied_weights(&self) -> &[f32] {
+        &self.applied
+    }
+}
+
+impl Default for MorphTargets {
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+/// Smoothed weights closer than this to their [`MorphWeights`] snap to them.
+const SNAP_WEIGHT: f32 = 1e-3;
+
+/// The weight of each of the [`MorphTargets`] of a mesh, 0 leaving the base shape. Targets
+/// without a weight aren't blended in.
+#[derive(Component, Clone, Debug, Default, PartialEq)]
+pub struct MorphWeights(pub Vec<f32>);
+
+pub(crate) struct MorphTargetsPlugin;
+
+impl Plugin for MorphTargetsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            apply_morph_targets.before(SkinnedAabbSystems::UpdateAabbs),
+        );
+    }
+}
+
+/// Blend the [`MorphTargets`] of each mesh into its mesh asset when its [`MorphWeights`], its
+/// targets or its mesh changed, and every frame while its smoothed weights catch up.
+fn apply_morph_targets(
+    mut query: Query<(&Handle<Mesh>, &MorphWeights, &mut MorphTargets)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    for (mesh_h, weights, mut targets) in &mut query {
+        let smoothing = targets.smoothing.clamp(0.0, 1.0);
+        let weights: Vec<f32> = (0..targets.targets.len())
+            .map(|i| {
+                let weight = weights.0.get(i).copied().unwrap_or(0.0);
+                let applied = targets.applied.get(i).copied().unwrap_or(0.0);
+                let smoothed = applied + (weight - applied) * smoothing;
+                if (weight - smoothed).abs() < SNAP_WEIGHT {
+                    weight
+                } else {
+                    smoothed
+                }
+            })
+            .collect();
+        let rebase = !matches!(&targets.base, Some((id, _)) if *id == mesh_h.id);
+        if !rebase && !targets.is_changed() && weights == targets.applied {
+            continue;
+        }
+        let mesh = match meshes.get_mut(mesh_h) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        let targets = &mut *targets;
+        if rebase {
+            match mesh_positions(mesh) {
+                Some(positions) => targets.base = Some((mesh_h.id, positions)),
+                None => continue,
+            }
+        }
+        let mut positions = match &targets.base {
+            Some((_, base)) => base.clone(),
+            None => continue,
+        };
+        for (target, weight) in targets.targets.iter().zip(&weights) {
+            if *weight != 0.0 {
+                for (position, displacement) in positions.iter_mut().zip(target) {
+                    *position += *weight * *displacement;
+                }
+            }
+        }
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_POSITION,
+            positions
+                .iter()
+                .map(|position| position.to_array())
+                .collect::<Vec<_>>(),
+        );
+        targets.applied = weights;
+    }
+}
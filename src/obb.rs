@@ -0,0 +1,157 @@
+//! Oriented bounding boxes of skinned vertices, tighter than their [`Aabb`] around limbs posed
+//! diagonally to the mesh's axes.
+//!
+//! [`Aabb`]: bevy::render::primitives::Aabb
+
+use bevy::{math::Affine3A, prelude::*, render::primitives::Aabb};
+
+/// An oriented box in the local space of a skinned mesh, from its box space: `frame` moves the
+/// axes of the box into the mesh's local space, and the box spans `center ± half_extents` along
+/// them.
+///
+/// Insert it on a skinned mesh to have [`ComputeSkinnedAabbPlugin`](crate::ComputeSkinnedAabbPlugin)
+/// fit it to the skinned vertices along its `axes` each time the bounds are computed on the
+/// CPU, next to the [`Aabb`]. This skins every vertex again, and isn't done for
+/// [`AabbCompute::Gpu`](crate::AabbCompute::Gpu) and
+/// [`AabbCompute::Async`](crate::AabbCompute::Async) meshes.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq)]
+pub struct SkinnedObb {
+    pub frame: Affine3A,
+    /// The center of the box, in box space.
+    pub center: Vec3,
+    pub half_extents: Vec3,
+    /// How `frame` is chosen, left as is when the box is fitted again.
+    pub axes: ObbAxes,
+}
+
+/// How the axes of a [`SkinnedObb`] are chosen.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ObbAxes {
+    /// The principal axes of the skinned vertices, see [`compute_obb`].
+    #[default]
+    PrincipalAxes,
+    /// The axes of this joint, by its index in the mesh's
+    /// [`SkinnedMesh::joints`](bevy::render::mesh::skinning::SkinnedMesh::joints), without its
+    /// scale. Usually 0 for the root joint, whose box stays tight as a character lies down or
+    /// turns, and doesn't flip between axes like principal axes can.
+    Joint(usize),
+}
+
+impl SkinnedObb {
+    /// An empty box to insert on a skinned mesh, fitted along the axes of its joint `index`.
+    pub fn joint(index: usize) -> Self {
+        Self {
+            axes: ObbAxes::Joint(index),
+            ..default()
+        }
+    }
+
+    /// The 8 corners of the box in the mesh's local space, the `i`th one on the positive side of
+    /// the axes whose bit is set in `i`.
+    pub fn corners(&self) -> [Vec3; 8] {
+        [0, 1, 2, 3, 4, 5, 6, 7].map(|i| {
+            let sign = Vec3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            );
+            self.frame
+                .transform_point3(self.center + sign * self.half_extents)
+        })
+    }
+
+    /// The volume of the box.
+    pub fn volume(&self) -> f32 {
+        let size = self.half_extents * 2.0;
+        size.x * size.y * size.z
+    }
+
+    /// Whether `point`, in the mesh's local space, is inside the box or on its surface.
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        let offset = self.frame.inverse().transform_point3(point) - self.center;
+        offset.abs().cmple(self.half_extents).all()
+    }
+
+    /// The world space [`Aabb`] around the box, for a mesh with the [`GlobalTransform`]
+    /// `transform`.
+    pub fn to_world_aabb(&self, transform: &GlobalTransform) -> Aabb {
+        let corners = self.corners().map(|corner| transform.mul_vec3(corner));
+        let minimum = corners.iter().copied().reduce(Vec3::min).unwrap();
+        let maximum = corners.iter().copied().reduce(Vec3::max).unwrap();
+        Aabb::from_min_max(minimum, maximum)
+    }
+}
+
+/// An oriented box around every finite position in `positions`, along their principal axes: the
+/// eigenvectors of the covariance of the positions, which follow the length of an elongated
+/// mesh. Not the smallest such box, but usually close to it for limbs. Returns `None` if there
+/// is no finite position.
+pub fn compute_obb(positions: &[Vec3]) -> Option<SkinnedObb> {
+    let finite = || positions.iter().filter(|p| p.is_finite());
+    let count = finite().count();
+    if count == 0 {
+        return None;
+    }
+    let mean = finite().sum::<Vec3>() / count as f32;
+    let mut covariance = Mat3::ZERO;
+    for p in finite() {
+        let d = *p - mean;
+        covariance += Mat3::from_cols(d * d.x, d * d.y, d * d.z);
+    }
+    let axes = symmetric_eigenvectors(covariance * (1.0 / count as f32));
+    compute_obb_in_frame(positions, Affine3A::from_mat3(axes))
+}
+
+/// The box along the axes of `frame` around every finite position in `positions`, e.g. the
+/// transform of a joint in the mesh's local space. Returns `None` if there is no finite position.
+pub fn compute_obb_in_frame(positions: &[Vec3], frame: Affine3A) -> Option<SkinnedObb> {
+    let to_box = frame.inverse();
+    let mut minimum = Vec3::splat(f32::MAX);
+    let mut maximum = Vec3::splat(f32::MIN);
+    for p in positions.iter().filter(|p| p.is_finite()) {
+        let p = to_box.transform_point3(*p);
+        minimum = minimum.min(p);
+        maximum = maximum.max(p);
+    }
+    (minimum.x <= maximum.x).then_some(SkinnedObb {
+        frame,
+        center: (minimum + maximum) * 0.5,
+        half_extents: (maximum - minimum) * 0.5,
+        axes: ObbAxes::PrincipalAxes,
+    })
+}
+
+/// The eigenvectors of the symmetric `matrix` as the columns of a rotation, found with Jacobi
+/// rotations zeroing its off diagonal elements one after the other.
+fn symmetric_eigenvectors(mut matrix: Mat3) -> Mat3 {
+    let mut vectors = Mat3::IDENTITY;
+    for _ in 0..16 {
+        let off_diagonal = matrix.x_axis.y.abs() + matrix.x_axis.z.abs() + matrix.y_axis.z.abs();
+        let diagonal = matrix.x_axis.x.abs() + matrix.y_axis.y.abs() + matrix.z_axis.z.abs();
+        if off_diagonal <= f32::EPSILON * diagonal {
+            break;
+        }
+        for (p, q) in [(0, 1), (0, 2), (1, 2)] {
+            let apq = matrix.col(q)[p];
+            if apq.abs() <= f32::MIN_POSITIVE {
+                continue;
+            }
+            let theta = (matrix.col(q)[q] - matrix.col(p)[p]) / (2.0 * apq);
+            let t = theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt());
+            let c = 1.0 / (t * t + 1.0).sqrt();
+            let s = t * c;
+            let mut rotation = Mat3::IDENTITY;
+            rotation.col_mut(p)[p] = c;
+            rotation.col_mut(q)[q] = c;
+            rotation.col_mut(q)[p] = s;
+            rotation.col_mut(p)[q] = -s;
+            matrix = rotation.transpose() * matrix * rotation;
+            vectors *= rotation;
+        }
+    }
+    // Keep a right handed basis so the frame is a rotation.
+    if vectors.determinant() < 0.0 {
+        vectors.z_axis = -vectors.z_axis;
+    }
+    vectors
+}
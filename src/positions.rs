@@ -0,0 +1,116 @@
+//! The skinned vertices of meshes kept around in world space, for raycasts and colliders that
+//! follow the animated surface.
+
+use bevy::{
+    math::Vec3A,
+    prelude::*,
+    render::mesh::{
+        skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+        Indices,
+    },
+    utils::HashSet,
+};
+
+use crate::{
+    build_joint_matrices, joint_indices_in_range, mesh_joint_indices, mesh_joint_weights,
+    mesh_positions, referenced_joint_count, skin_model_normalized, skin_point_dual_quaternion,
+    DualQuat, NoSkinnedAabb, SkinnedAabbConfig, SkinningMethod,
+};
+
+/// Keep the skinned vertices of this skinned mesh in a [`SkinnedVertexPositions`], updated every
+/// frame along with the bounds.
+#[derive(Component, Clone, Copy, Debug, Default)]
+pub struct StoreSkinnedPositions;
+
+/// The world space position of every vertex of a mesh with [`StoreSkinnedPositions`], in the
+/// order of its [`Mesh::ATTRIBUTE_POSITION`], so the mesh's indices still apply. Vertices with
+/// only zero weights are bound to the first joint, as bevy's shader does.
+///
+/// Inserted by [`ComputeSkinnedAabbPlugin`](crate::ComputeSkinnedAabbPlugin) once the mesh is
+/// loaded, and skinned again in place every frame without reallocating.
+#[derive(Component, Clone, Debug, Default)]
+pub struct SkinnedVertexPositions(pub Vec<Vec3A>);
+
+impl SkinnedVertexPositions {
+    /// The skinned triangles of the triangle list `indices` of the mesh.
+    /// Triangles with an out of range index are skipped.
+    pub fn triangles<'a>(&'a self, indices: &'a Indices) -> impl Iterator<Item = [Vec3A; 3]> + 'a {
+        let mut indices = indices.iter();
+        std::iter::from_fn(move || Some([indices.next()?, indices.next()?, indices.next()?]))
+            .filter_map(|triangle| {
+                let [a, b, c] = triangle.map(|i| self.0.get(i).copied());
+                Some([a?, b?, c?])
+            })
+    }
+}
+
+/// Skin every vertex of the meshes with [`StoreSkinnedPositions`] into their
+/// [`SkinnedVertexPositions`].
+#[allow(clippy::type_complexity, clippy::too_many_arguments)]
+pub(crate) fn store_skinned_positions(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &Handle<Mesh>,
+            &SkinnedMesh,
+            Option<&SkinningMethod>,
+            Option<&mut SkinnedVertexPositions>,
+        ),
+        (With<StoreSkinnedPositions>, Without<NoSkinnedAabb>),
+    >,
+    meshes: Res<Assets<Mesh>>,
+    inverse_bindposes: Res<Assets<SkinnedMeshInverseBindposes>>,
+    joint_query: Query<&GlobalTransform>,
+    config: Res<SkinnedAabbConfig>,
+    mut joints: Local<Vec<Mat4>>,
+    mut warned: Local<HashSet<Entity>>,
+) {
+    for (entity, mesh_h, skinned_mesh, skinning, positions) in &mut query {
+        let mesh = match meshes.get(mesh_h) {
+            Some(mesh) => mesh,
+            None => continue,
+        };
+        if build_joint_matrices(skinned_mesh, &inverse_bindposes, &joint_query, &mut joints)
+            .is_none()
+        {
+            continue;
+        }
+        let (mesh_positions, indices, weights) = match (
+            mesh_positions(mesh),
+            mesh_joint_indices(mesh),
+            mesh_joint_weights(mesh),
+        ) {
+            (Some(positions), Some(indices), Some(weights)) => (positions, indices, weights),
+            _ => continue,
+        };
+        let joint_count = referenced_joint_count(&indices);
+        if !joint_indices_in_range(entity, mesh, &joints, joint_count, &mut warned) {
+            continue;
+        }
+        let dual_quats: Vec<DualQuat> = match skinning.copied().unwrap_or(config.skinning) {
+            SkinningMethod::LinearBlend => Vec::new(),
+            SkinningMethod::DualQuaternion => joints.iter().copied().map(DualQuat::from).collect(),
+        };
+        let skinned = mesh_positions.iter().zip(&indices).zip(&weights).map(
+            |((position, indices), weights)| {
+                Vec3A::from(if dual_quats.is_empty() {
+                    skin_model_normalized(&joints, indices, *weights).transform_point3(*position)
+                } else {
+                    skin_point_dual_quaternion(&dual_quats, indices, *weights, *position)
+                })
+            },
+        );
+        match positions {
+            Some(mut positions) => {
+                positions.0.clear();
+                positions.0.extend(skinned);
+            }
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(SkinnedVertexPositions(skinned.collect()));
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+//! Decimated proxies of skinned meshes, to bound them with fewer vertices.
+
+use bevy::{
+    prelude::*,
+    render::mesh::{PrimitiveTopology, VertexAttributeValues},
+    utils::HashMap,
+};
+
+use crate::{mesh_joint_indices, normalize_weights};
+
+/// A point cloud with one vertex per cell of a `cell_size` grid holding vertices of `mesh`,
+/// skinned like the vertices it replaces, to bound the mesh with fewer vertices.
+///
+/// Each proxy vertex sits at the average bind pose position of its cell, and is bound to the
+/// (up to) four joints with the largest summed weight over the cell, normalized to sum to 1.
+/// This is only an approximation: the proxy can be smaller than the mesh by up to
+/// `cell_size` on each side, and more where vertices of a cell follow different joints,
+/// so pad its bounds accordingly. Build it once when the mesh is loaded, then bound it with
+/// [`skin_vertices`](crate::skin_vertices) and [`compute_aabb`](crate::compute_aabb).
+///
+/// The joint indices are written as `Uint16x4`. Returns `None` if `mesh` is missing
+/// skinning attributes or `cell_size` isn't positive.
+pub fn generate_skin_proxy(mesh: &Mesh, cell_size: f32) -> Option<Mesh> {
+    if cell_size.is_nan() || cell_size <= 0.0 {
+        return None;
+    }
+    let mesh_positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions,
+        _ => return None,
+    };
+    let mesh_indices = mesh_joint_indices(mesh)?;
+    let mesh_weights = match mesh.attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT) {
+        Some(VertexAttributeValues::Float32x4(weights)) => weights,
+        _ => return None,
+    };
+
+    /// The vertices of a grid cell, summed.
+    #[derive(Default)]
+    struct Cell {
+        position: Vec3,
+        count: usize,
+        weights: HashMap<u32, f32>,
+    }
+
+    // Cells in the order they were first hit, so the proxy doesn't depend on hashing.
+    let mut cell_indices = HashMap::default();
+    let mut cells: Vec<Cell> = Vec::new();
+    for ((position, indices), weights) in mesh_positions.iter().zip(&mesh_indices).zip(mesh_weights)
+    {
+        let position = Vec3::from(*position);
+        if !position.is_finite() {
+            continue;
+        }
+        let key = (position / cell_size).floor().as_ivec3().to_array();
+        let cell = *cell_indices.entry(key).or_insert_with(|| {
+            cells.push(Cell::default());
+            cells.len() - 1
+        });
+        let cell = &mut cells[cell];
+        cell.position += position;
+        cell.count += 1;
+        let (indices, weights) = normalize_weights(indices, Vec4::from(*weights));
+        for (joint, weight) in indices.into_iter().zip(weights.to_array()) {
+            if weight > 0.0 {
+                *cell.weights.entry(joint).or_default() += weight;
+            }
+        }
+    }
+
+    let mut positions = Vec::with_capacity(cells.len());
+    let mut joint_indices = Vec::with_capacity(cells.len());
+    let mut joint_weights = Vec::with_capacity(cells.len());
+    for cell in cells {
+        let mut influences: Vec<_> = cell.weights.into_iter().collect();
+        influences.sort_unstable_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+        let mut indices = [0; 4];
+        let mut weights = [0.0; 4];
+        for (slot, (joint, weight)) in influences.into_iter().take(4).enumerate() {
+            indices[slot] = joint as u16;
+            weights[slot] = weight;
+        }
+        let weights = Vec4::from(weights);
+
+        positions.push((cell.position / cell.count as f32).to_array());
+        joint_indices.push(indices);
+        joint_weights.push((weights / weights.dot(Vec4::ONE)).to_array());
+    }
+
+    let mut proxy = Mesh::new(PrimitiveTopology::PointList);
+    proxy.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    proxy.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, joint_indices);
+    proxy.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, joint_weights);
+    Some(proxy)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{compute_aabb, skin_vertices};
+
+    #[test]
+    fn proxy_bounds_approximate_the_mesh() {
+        // A 10 by 10 grid of points 0.1 apart, the left half on joint 0, the right half on joint 1.
+        let mut positions = Vec::new();
+        let mut indices = Vec::new();
+        for x in 0..10 {
+            for y in 0..10 {
+                positions.push([x as f32 * 0.1, y as f32 * 0.1, 0.0]);
+                indices.push([(x >= 5) as u16, 0, 0, 0]);
+            }
+        }
+        let vertices = positions.len();
+        let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, indices);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            vec![[1.0f32, 0.0, 0.0, 0.0]; vertices],
+        );
+
+        let cell_size = 0.5;
+        let proxy = generate_skin_proxy(&mesh, cell_size).unwrap();
+        assert_eq!(proxy.count_vertices(), 4);
+
+        let joints = [
+            Mat4::IDENTITY,
+            Mat4::from_translation(Vec3::new(0.0, 2.0, 0.0)),
+        ];
+        let full = compute_aabb(&skin_vertices(&mesh, &joints).unwrap()).unwrap();
+        let approximate = compute_aabb(&skin_vertices(&proxy, &joints).unwrap()).unwrap();
+        // The proxy follows the joint of its cell, and stays within a cell of the mesh's bounds.
+        assert!(approximate.max().y > 2.0);
+        assert!((Vec3::from(full.min()) - Vec3::from(approximate.min())).max_element() <= 0.0);
+        assert!((Vec3::from(approximate.max()) - Vec3::from(full.max())).max_element() <= 0.0);
+        assert!(
+            (Vec3::from(full.half_extents) - Vec3::from(approximate.half_extents)).max_element()
+                <= cell_size
+        );
+
+        assert!(generate_skin_proxy(&mesh, 0.0).is_none());
+    }
+}
@@ -0,0 +1,129 @@
+//! Detect triangles of a skinned mesh passing through each other, for animation QA.
+
+use bevy::{
+    prelude::*,
+    render::{mesh::Indices, primitives::Aabb},
+};
+
+/// Whether any two triangles of the mesh intersect in its current pose,
+/// written when [`SkinnedAabbConfig::detect_self_intersection`](crate::SkinnedAabbConfig::detect_self_intersection)
+/// is enabled.
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SelfIntersecting(pub bool);
+
+/// Whether any two triangles of a triangle list cross each other, given the skinned `positions`
+/// of its vertices. Triangles sharing a vertex are never tested against each other, and
+/// coplanar triangles are not reported.
+///
+/// Every pair of triangles whose bounds overlap is tested, so it is quadratic in the worst case.
+pub fn mesh_self_intersects(positions: &[Vec3], indices: &Indices) -> bool {
+    let indices: Vec<usize> = indices.iter().collect();
+    let triangles: Vec<[usize; 3]> = indices
+        .chunks_exact(3)
+        .filter(|triangle| triangle.iter().all(|i| *i < positions.len()))
+        .map(|triangle| [triangle[0], triangle[1], triangle[2]])
+        .collect();
+    let bounds: Vec<Aabb> = triangles
+        .iter()
+        .map(|triangle| {
+            let [a, b, c] = triangle.map(|i| positions[i]);
+            Aabb::from_min_max(a.min(b).min(c), a.max(b).max(c))
+        })
+        .collect();
+
+    for (i, a) in triangles.iter().enumerate() {
+        for (j, b) in triangles.iter().enumerate().skip(i + 1) {
+            if a.iter().any(|index| b.contains(index)) || !aabbs_overlap(&bounds[i], &bounds[j]) {
+                continue;
+            }
+            let a = a.map(|i| positions[i]);
+            let b = b.map(|i| positions[i]);
+            if triangles_intersect(&a, &b) {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+fn aabbs_overlap(a: &Aabb, b: &Aabb) -> bool {
+    a.min().cmple(b.max()).all() && b.min().cmple(a.max()).all()
+}
+
+/// Whether an edge of either triangle passes through the inside of the other.
+fn triangles_intersect(a: &[Vec3; 3], b: &[Vec3; 3]) -> bool {
+    let edges = |t: &[Vec3; 3]| [(t[0], t[1]), (t[1], t[2]), (t[2], t[0])];
+    edges(a)
+        .iter()
+        .any(|(start, end)| segment_crosses_triangle(*start, *end, b))
+        || edges(b)
+            .iter()
+            .any(|(start, end)| segment_crosses_triangle(*start, *end, a))
+}
+
+/// Möller–Trumbore, only counting hits strictly inside both the segment and the triangle,
+/// so triangles that merely touch along a seam don't count.
+fn segment_crosses_triangle(start: Vec3, end: Vec3, triangle: &[Vec3; 3]) -> bool {
+    const EPSILON: f32 = 1e-6;
+    let direction = end - start;
+    let edge1 = triangle[1] - triangle[0];
+    let edge2 = triangle[2] - triangle[0];
+    let p = direction.cross(edge2);
+    let determinant = edge1.dot(p);
+    if determinant.abs() < EPSILON {
+        return false;
+    }
+    let inverse = 1.0 / determinant;
+    let s = start - triangle[0];
+    let u = s.dot(p) * inverse;
+    if u <= EPSILON || u >= 1.0 - EPSILON {
+        return false;
+    }
+    let q = s.cross(edge1);
+    let v = direction.dot(q) * inverse;
+    if v <= EPSILON || u + v >= 1.0 - EPSILON {
+        return false;
+    }
+    let t = edge2.dot(q) * inverse;
+    t > EPSILON && t < 1.0 - EPSILON
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A triangle in the XY plane and one in the XZ plane at height `y`, 1 apart along Z.
+    fn crossed_triangles(y: f32) -> [Vec3; 6] {
+        [
+            Vec3::new(-1.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.0, 2.0, 0.0),
+            Vec3::new(-0.5, y, -1.0),
+            Vec3::new(0.5, y, -1.0),
+            Vec3::new(0.0, y, 1.0),
+        ]
+    }
+
+    #[test]
+    fn crossing_triangles_intersect() {
+        let indices = Indices::U16(vec![0, 1, 2, 3, 4, 5]);
+        assert!(mesh_self_intersects(&crossed_triangles(1.0), &indices));
+        assert!(!mesh_self_intersects(&crossed_triangles(3.0), &indices));
+    }
+
+    #[test]
+    fn neighbours_and_coplanar_triangles_dont() {
+        // Two triangles of a quad share an edge, and a third overlaps them in the same plane.
+        let positions = [
+            Vec3::ZERO,
+            Vec3::X,
+            Vec3::Y,
+            Vec3::new(1.0, 1.0, 0.0),
+            Vec3::new(0.25, 0.25, 0.0),
+            Vec3::new(2.0, 0.25, 0.0),
+            Vec3::new(0.25, 2.0, 0.0),
+        ];
+        let indices = Indices::U16(vec![0, 1, 2, 1, 3, 2, 4, 5, 6]);
+        assert!(!mesh_self_intersects(&positions, &indices));
+    }
+}
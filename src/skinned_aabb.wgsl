@@ -0,0 +1,74 @@
+// Skins every vertex of a mesh and reduces the skinned positions to their min/max.
+// Each workgroup reduces its vertices in shared memory down to `ATOMIC_LANES` values,
+// then merges them into `bounds` with atomics. Floats are mapped to uints that sort
+// the same way, since atomics only work on integers.
+// The `{{...}}` values are filled in by `gpu.rs` before the shader is compiled.
+
+struct SkinVertex {
+    position: vec4<f32>,
+    weights: vec4<f32>,
+    joints: vec4<u32>,
+};
+
+@group(0) @binding(0)
+var<storage, read> vertices: array<SkinVertex>;
+
+@group(0) @binding(1)
+var<storage, read> joints: array<mat4x4<f32>>;
+
+// min x, y, z followed by max x, y, z
+@group(0) @binding(2)
+var<storage, read_write> bounds: array<atomic<u32>, 6>;
+
+let WORKGROUP_SIZE: u32 = {{WORKGROUP_SIZE}}u;
+let ATOMIC_LANES: u32 = {{ATOMIC_LANES}}u;
+let F32_MAX: f32 = 3.40282347e38;
+
+var<workgroup> local_min: array<vec3<f32>, {{WORKGROUP_SIZE}}>;
+var<workgroup> local_max: array<vec3<f32>, {{WORKGROUP_SIZE}}>;
+
+fn to_ordered(value: f32) -> u32 {
+    let bits = bitcast<u32>(value);
+    return select(bits | 0x80000000u, ~bits, (bits & 0x80000000u) != 0u);
+}
+
+@compute @workgroup_size({{WORKGROUP_SIZE}})
+fn main(
+    @builtin(global_invocation_id) global_id: vec3<u32>,
+    @builtin(local_invocation_index) local_index: u32,
+) {
+    var position_min = vec3<f32>(F32_MAX);
+    var position_max = vec3<f32>(-F32_MAX);
+    if (global_id.x < arrayLength(&vertices)) {
+        let vertex = vertices[global_id.x];
+        let model = vertex.weights.x * joints[vertex.joints.x]
+            + vertex.weights.y * joints[vertex.joints.y]
+            + vertex.weights.z * joints[vertex.joints.z]
+            + vertex.weights.w * joints[vertex.joints.w];
+        let position = (model * vec4<f32>(vertex.position.xyz, 1.0)).xyz;
+        position_min = position;
+        position_max = position;
+    }
+    local_min[local_index] = position_min;
+    local_max[local_index] = position_max;
+    workgroupBarrier();
+
+    for (var stride = WORKGROUP_SIZE / 2u; stride >= ATOMIC_LANES; stride = stride / 2u) {
+        if (local_index < stride) {
+            local_min[local_index] = min(local_min[local_index], local_min[local_index + stride]);
+            local_max[local_index] = max(local_max[local_index], local_max[local_index + stride]);
+        }
+        workgroupBarrier();
+    }
+
+    if (local_index < ATOMIC_LANES) {
+        let workgroup_min = local_min[local_index];
+        let workgroup_max = local_max[local_index];
+        atomicMin(&bounds[0], to_ordered(workgroup_min.x));
+        atomicMin(&bounds[1], to_ordered(workgroup_min.y));
+        atomicMin(&bounds[2], to_ordered(workgroup_min.z));
+        atomicMax(&bounds[3], to_ordered(workgroup_max.x));
+        atomicMax(&bounds[4], to_ordered(workgroup_max.y));
+        atomicMax(&bounds[5], to_ordered(workgroup_max.z));
+    }
+}
@@ -0,0 +1,46 @@
+//! Bounding spheres of skinned vertices, for culling and LOD systems that prefer them to boxes.
+
+use bevy::{math::Vec3A, prelude::*, render::primitives::Sphere};
+
+/// A sphere containing every finite position in `positions`, found with Ritter's algorithm:
+/// start from the two far apart points found by two walks to the farthest point, then grow the
+/// sphere around each point left outside it. Usually within a few percent of the smallest
+/// enclosing sphere, and tighter than the sphere around the [`Aabb`](bevy::render::primitives::Aabb)
+/// of elongated meshes. Returns `None` if there is no finite position.
+pub fn compute_bounding_sphere(positions: &[Vec3]) -> Option<Sphere> {
+    let first = Vec3A::from(*positions.iter().find(|p| p.is_finite())?);
+    let farthest_from = |from: Vec3A| {
+        positions
+            .iter()
+            .filter(|p| p.is_finite())
+            .map(|p| Vec3A::from(*p))
+            .fold(from, |farthest, p| {
+                if p.distance_squared(from) > farthest.distance_squared(from) {
+                    p
+                } else {
+                    farthest
+                }
+            })
+    };
+    let a = farthest_from(first);
+    let b = farthest_from(a);
+
+    let mut center = (a + b) * 0.5;
+    let mut radius = a.distance(b) * 0.5;
+    for p in positions.iter().filter(|p| p.is_finite()) {
+        let p = Vec3A::from(*p);
+        let distance = p.distance(center);
+        if distance > radius {
+            // Move the center towards `p` just enough for the sphere to reach it,
+            // while still containing the side of the old sphere opposite to it.
+            let new_radius = (radius + distance) * 0.5;
+            center += (p - center) * ((new_radius - radius) / distance);
+            radius = new_radius;
+        }
+    }
+    // Growing the sphere rounds, so the points it was grown to can end up just outside it.
+    Some(Sphere {
+        center,
+        radius: radius * (1.0 + 4.0 * f32::EPSILON),
+    })
+}
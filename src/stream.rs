@@ -0,0 +1,200 @@
+//! The bounds of every skinned mesh sent out of the app each frame, for tools running on another
+//! thread or in another process.
+
+use std::sync::mpsc::{sync_channel, Receiver, SyncSender};
+
+use bevy::{math::Vec3A, prelude::*, render::primitives::Aabb};
+
+use crate::{SkinnedAabb, SkinnedAabbFrameCount, SkinnedAabbSystems};
+
+/// Sends a [`SkinnedAabbFrame`] to the [`SkinnedAabbStream`] resource every frame, once the
+/// bounds are updated. Nothing is sent until the resource is inserted.
+pub struct SkinnedAabbStreamPlugin;
+
+impl Plugin for SkinnedAabbStreamPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system_to_stage(
+            CoreStage::PostUpdate,
+            stream_skinned_aabbs.after(SkinnedAabbSystems::UpdateAabbs),
+        );
+    }
+}
+
+/// The [`SkinnedAabb`] of every skinned mesh on one frame.
+#[derive(Clone, Debug, Default)]
+pub struct SkinnedAabbFrame {
+    /// The [`SkinnedAabbFrameCount`] the frame was sent on.
+    pub frame: u32,
+    pub bounds: Vec<StreamedAabb>,
+}
+
+/// The bounds of one skinned mesh in a [`SkinnedAabbFrame`].
+#[derive(Clone, Debug)]
+pub struct StreamedAabb {
+    pub entity: Entity,
+    pub local: Aabb,
+    pub world: Aabb,
+}
+
+impl SkinnedAabbFrame {
+    /// The size of one [`StreamedAabb`] in [`SkinnedAabbFrame::to_bytes`].
+    const STREAMED_AABB_SIZE: usize = 8 + 12 * 4;
+
+    /// Serialize the frame for another process, in little endian: the frame and the number of
+    /// bounds as `u32`s, then for each the entity's [`Entity::to_bits`] as a `u64` followed by
+    /// the center and half extents of its local then world bounds as `f32`s.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(8 + self.bounds.len() * Self::STREAMED_AABB_SIZE);
+        bytes.extend(self.frame.to_le_bytes());
+        bytes.extend((self.bounds.len() as u32).to_le_bytes());
+        for bounds in &self.bounds {
+            bytes.extend(bounds.entity.to_bits().to_le_bytes());
+            for aabb in [&bounds.local, &bounds.world] {
+                for value in aabb
+                    .center
+                    .to_array()
+                    .into_iter()
+                    .chain(aabb.half_extents.to_array())
+                {
+                    bytes.extend(value.to_le_bytes());
+                }
+            }
+        }
+        bytes
+    }
+
+    /// Read back a frame written by [`SkinnedAabbFrame::to_bytes`].
+    /// Returns `None` if `bytes` is too short or has bytes left over.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let u32_at = |at: usize| Some(u32::from_le_bytes(bytes.get(at..at + 4)?.try_into().ok()?));
+        let frame = u32_at(0)?;
+        let len = u32_at(4)? as usize;
+        if bytes.len() != 8 + len.checked_mul(Self::STREAMED_AABB_SIZE)? {
+            return None;
+        }
+        let f32_at = |at: usize| u32_at(at).map(f32::from_bits);
+        let vec3a_at = |at: usize| Some(Vec3A::new(f32_at(at)?, f32_at(at + 4)?, f32_at(at + 8)?));
+        let aabb_at = |at: usize| {
+            Some(Aabb {
+                center: vec3a_at(at)?,
+                half_extents: vec3a_at(at + 12)?,
+            })
+        };
+        let bounds = (0..len)
+            .map(|i| {
+                let at = 8 + i * Self::STREAMED_AABB_SIZE;
+                let entity = u64::from_le_bytes(bytes.get(at..at + 8)?.try_into().ok()?);
+                Some(StreamedAabb {
+                    entity: Entity::from_bits(entity),
+                    local: aabb_at(at + 8)?,
+                    world: aabb_at(at + 32)?,
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(SkinnedAabbFrame { frame, bounds })
+    }
+}
+
+/// Where a [`SkinnedAabbStream`] sends its frames, e.g. a channel or a socket to another process.
+pub trait SkinnedAabbSink: Send + Sync + 'static {
+    /// Take one frame without blocking the app. Returns `false` if the sink can't keep up or is
+    /// closed, the frame is dropped then.
+    fn send(&mut self, frame: SkinnedAabbFrame) -> bool;
+}
+
+/// Frames are dropped while the channel is full, so a slow receiver never stalls the app.
+impl SkinnedAabbSink for SyncSender<SkinnedAabbFrame> {
+    fn send(&mut self, frame: SkinnedAabbFrame) -> bool {
+        self.try_send(frame).is_ok()
+    }
+}
+
+/// Where [`SkinnedAabbStreamPlugin`] sends the bounds. Insert it as a resource to start
+/// streaming, and remove it to stop.
+pub struct SkinnedAabbStream {
+    sink: Box<dyn SkinnedAabbSink>,
+    /// The frames the sink didn't take.
+    pub dropped: u64,
+}
+
+impl SkinnedAabbStream {
+    pub fn new(sink: impl SkinnedAabbSink) -> Self {
+        Self {
+            sink: Box::new(sink),
+            dropped: 0,
+        }
+    }
+
+    /// Stream to a channel holding up to `capacity` frames the receiver hasn't read yet,
+    /// later frames are dropped until it catches up.
+    pub fn channel(capacity: usize) -> (Self, Receiver<SkinnedAabbFrame>) {
+        let (sender, receiver) = sync_channel(capacity);
+        (Self::new(sender), receiver)
+    }
+}
+
+/// Send the bounds of every skinned mesh to the [`SkinnedAabbStream`].
+fn stream_skinned_aabbs(
+    stream: Option<ResMut<SkinnedAabbStream>>,
+    frame_count: Res<SkinnedAabbFrameCount>,
+    query: Query<(Entity, &SkinnedAabb)>,
+) {
+    let mut stream = match stream {
+        Some(stream) => stream,
+        None => return,
+    };
+    let frame = SkinnedAabbFrame {
+        frame: frame_count.0,
+        bounds: query
+            .iter()
+            .map(|(entity, skinned_aabb)| StreamedAabb {
+                entity,
+                local: skinned_aabb.local.clone(),
+                world: skinned_aabb.world.clone(),
+            })
+            .collect(),
+    };
+    if !stream.sink.send(frame) {
+        stream.dropped += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_round_trip() {
+        let frame = SkinnedAabbFrame {
+            frame: 7,
+            bounds: vec![
+                StreamedAabb {
+                    entity: Entity::from_raw(3),
+                    local: Aabb::from_min_max(Vec3::ZERO, Vec3::ONE),
+                    world: Aabb::from_min_max(Vec3::new(-1.0, 2.0, -3.5), Vec3::splat(4.0)),
+                },
+                StreamedAabb {
+                    entity: Entity::from_bits(1 << 32 | 9),
+                    local: Aabb::from_min_max(-Vec3::ONE, Vec3::ONE),
+                    world: Aabb::from_min_max(Vec3::splat(f32::MIN), Vec3::splat(f32::MAX)),
+                },
+            ],
+        };
+        let bytes = frame.to_bytes();
+        assert_eq!(bytes.len(), 8 + 2 * SkinnedAabbFrame::STREAMED_AABB_SIZE);
+        let read = SkinnedAabbFrame::from_bytes(&bytes).unwrap();
+        assert_eq!(read.frame, frame.frame);
+        assert_eq!(read.bounds.len(), frame.bounds.len());
+        for (read, bounds) in read.bounds.iter().zip(&frame.bounds) {
+            assert_eq!(read.entity, bounds.entity);
+            for (read, aabb) in [(&read.local, &bounds.local), (&read.world, &bounds.world)] {
+                assert_eq!(read.center, aabb.center);
+                assert_eq!(read.half_extents, aabb.half_extents);
+            }
+        }
+
+        assert!(SkinnedAabbFrame::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+        assert!(SkinnedAabbFrame::from_bytes(&[bytes.as_slice(), &[0]].concat()).is_none());
+        assert!(SkinnedAabbFrame::from_bytes(&[]).is_none());
+    }
+}
@@ -0,0 +1,169 @@
+//! Bounds computed in the background, the exact bounds of [`AabbQuality::Progressive`] meshes and
+//! every update of [`AabbCompute::Async`] meshes.
+
+use std::sync::{Arc, Mutex};
+
+use bevy::{
+    asset::HandleId,
+    math::Vec3A,
+    prelude::*,
+    render::primitives::Aabb,
+    tasks::AsyncComputeTaskPool,
+    utils::{HashMap, HashSet},
+};
+
+use crate::{
+    write_skinned_aabb, AabbCompute, AabbQuality, NoSkinnedAabb, SkinnedAabbConfig,
+    SkinnedAabbOutput, SkinnedMeshCpuData,
+};
+
+/// What a background task bounds, and what its result belongs to.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct AabbJob {
+    pub(crate) entity: Entity,
+    /// The mesh the vertices were cached from, the result is dropped if the entity's mesh
+    /// changed since.
+    pub(crate) mesh: HandleId,
+    /// The [`SkinnedAabbFrameCount`](crate::SkinnedAabbFrameCount) of the pose being bounded.
+    pub(crate) frame: u32,
+    /// Skin every `stride`th vertex, padding the bounds as [`AabbQuality::Sampled`] does.
+    pub(crate) stride: usize,
+    pub(crate) padding: f32,
+    /// Added to the half extents of the result, for the margins computed from the pose.
+    pub(crate) extra_half_extents: Vec3A,
+}
+
+/// A finished task: its job, its bounds or `None` if there was no vertex to bound or one wasn't
+/// finite, and the joints it skinned the vertices with.
+type AabbTaskResult = (AabbJob, Option<Aabb>, Vec<Mat4>);
+
+/// The bounds being computed on the [`AsyncComputeTaskPool`].
+#[derive(Default)]
+pub(crate) struct AabbTasks {
+    /// Tasks that finished, with the bounds waiting to be written to their entities.
+    results: Arc<Mutex<Vec<AabbTaskResult>>>,
+    /// Meshes with a task still running, which don't get another one until it finishes.
+    pending: HashSet<Entity>,
+    /// The joints of the exact bounds written to each [`AabbQuality::Progressive`] mesh, which
+    /// stand until it moves.
+    exact: HashMap<Entity, Vec<Mat4>>,
+}
+
+impl AabbTasks {
+    /// Bound the vertices of `cpu_data` skinned with the local space `joints` in the background,
+    /// unless the entity of `job` is still waiting on an earlier task.
+    /// Returns whether a task was spawned, the pose isn't bounded otherwise.
+    pub(crate) fn spawn(
+        &mut self,
+        job: AabbJob,
+        cpu_data: &SkinnedMeshCpuData,
+        joints: &[Mat4],
+    ) -> bool {
+        if !self.pending.insert(job.entity) {
+            return false;
+        }
+        let results = self.results.clone();
+        // Cheap, the vertices are shared rather than copied.
+        let cpu_data = cpu_data.clone();
+        let joints = joints.to_vec();
+        AsyncComputeTaskPool::get()
+            .spawn(async move {
+                // Already off the main thread, so the vertices aren't split any further.
+                let aabb = cpu_data
+                    .try_sampled_skinned_aabb(&joints, job.stride, usize::MAX)
+                    .ok()
+                    .map(|mut aabb| {
+                        if job.stride > 1 {
+                            aabb.half_extents +=
+                                job.padding.max(0.0) * aabb.half_extents.max_element();
+                        }
+                        aabb.half_extents += job.extra_half_extents;
+                        aabb
+                    });
+                results.lock().unwrap().push((job, aabb, joints));
+            })
+            .detach();
+        true
+    }
+
+    /// Whether the exact bounds of a [`AabbQuality::Progressive`] `entity` were written for the
+    /// local space `joints`, within `epsilon`. They're forgotten otherwise, as the sampled bounds
+    /// of the new pose replace them.
+    pub(crate) fn has_exact(&mut self, entity: Entity, joints: &[Mat4], epsilon: f32) -> bool {
+        let same_pose = self.exact.get(&entity).is_some_and(|exact| {
+            exact.len() == joints.len()
+                && exact
+                    .iter()
+                    .zip(joints)
+                    .all(|(exact, joint)| exact.abs_diff_eq(*joint, epsilon))
+        });
+        if !same_pose {
+            self.exact.remove(&entity);
+        }
+        same_pose
+    }
+}
+
+/// Write the bounds that landed to their meshes, unless newer bounds were written since the
+/// pose they were computed from. Results for meshes that were despawned, changed mesh, or no
+/// longer compute their bounds in the background are dropped.
+#[allow(clippy::type_complexity)]
+pub(crate) fn apply_aabb_tasks(
+    mut commands: Commands,
+    mut tasks: ResMut<AabbTasks>,
+    config: Res<SkinnedAabbConfig>,
+    time: Res<Time>,
+    mut query: Query<
+        (
+            SkinnedAabbOutput,
+            &Handle<Mesh>,
+            Option<&AabbQuality>,
+            Option<&AabbCompute>,
+        ),
+        Without<NoSkinnedAabb>,
+    >,
+) {
+    let results: Vec<_> = tasks.results.lock().unwrap().drain(..).collect();
+    // Tasks of despawned meshes still finish, but nothing waits on them anymore.
+    tasks.pending.retain(|entity| query.contains(*entity));
+    tasks.exact.retain(|entity, _| query.contains(*entity));
+    for (job, new_aabb, joints) in results {
+        tasks.pending.remove(&job.entity);
+        let new_aabb = match new_aabb {
+            Some(new_aabb) => new_aabb,
+            None => continue,
+        };
+        if let Ok((output, mesh_h, quality, compute)) = query.get_mut(job.entity) {
+            let progressive = matches!(
+                quality.unwrap_or(&config.quality),
+                AabbQuality::Progressive { .. }
+            );
+            let background =
+                progressive || compute.unwrap_or(&config.compute) == &AabbCompute::Async;
+            let newer = output
+                .skinned_aabb
+                .as_ref()
+                .is_some_and(|skinned_aabb| skinned_aabb.frame > job.frame);
+            if background && mesh_h.id == job.mesh && !newer {
+                // Keep the sphere of the last synchronous update, tasks don't compute one.
+                let sphere = output
+                    .skinned_aabb
+                    .as_ref()
+                    .and_then(|skinned_aabb| skinned_aabb.sphere.clone());
+                if progressive && job.stride == 1 {
+                    tasks.exact.insert(job.entity, joints);
+                }
+                write_skinned_aabb(
+                    &mut commands,
+                    job.entity,
+                    output,
+                    new_aabb,
+                    sphere,
+                    job.frame,
+                    &config,
+                    &time,
+                );
+            }
+        }
+    }
+}
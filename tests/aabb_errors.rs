@@ -0,0 +1,70 @@
+//! A mesh whose joint matrix or skinned vertices aren't finite keeps its previous bounds instead of
+//! NaN ones, and [`SkinnedAabbConfig::min_half_extent`] gives a single vertex bounds of a usable
+//! size.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{SkinnedAabb, SkinnedAabbConfig};
+use common::{assert_bounds, column};
+
+#[test]
+fn non_finite_joint_keeps_the_previous_bounds() {
+    let mut app = common::app();
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    );
+    common::update(&mut app, 2);
+    app.world.get_mut::<Transform>(spawned.joint).unwrap().scale = Vec3::new(1.0, f32::NAN, 1.0);
+    common::update(&mut app, 2);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(spawned.entity).unwrap();
+    assert_bounds(&skinned_aabb.local, Vec3::ZERO, Vec3::Y * 2.0);
+
+    // Fixed, it's bounded again.
+    app.world.get_mut::<Transform>(spawned.joint).unwrap().scale = Vec3::splat(2.0);
+    common::update(&mut app, 1);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(spawned.entity).unwrap();
+    assert_bounds(&skinned_aabb.local, Vec3::ZERO, Vec3::Y * 4.0);
+}
+
+#[test]
+fn non_finite_cached_vertex_keeps_the_previous_bounds() {
+    let mut app = common::app();
+    let tip = Vec3::Y * 1e38;
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        common::one_joint_mesh(vec![[0.0; 3], tip.to_array()]),
+        Transform::default(),
+        Transform::default(),
+    );
+    // Bounded from the cached vertices once they're cached.
+    common::update(&mut app, 3);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(spawned.entity).unwrap();
+    assert_bounds(&skinned_aabb.local, Vec3::ZERO, tip);
+
+    // A finite joint that overflows the tip, instead of bounds around the other vertex.
+    app.world.get_mut::<Transform>(spawned.joint).unwrap().scale = Vec3::splat(4.0);
+    common::update(&mut app, 2);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(spawned.entity).unwrap();
+    assert_bounds(&skinned_aabb.local, Vec3::ZERO, tip);
+}
+
+#[test]
+fn single_vertex_gets_the_minimum_half_extent() {
+    let mut app = common::app();
+    app.world
+        .resource_mut::<SkinnedAabbConfig>()
+        .min_half_extent = Some(0.1);
+    let joint = Transform::from_xyz(1.0, 0.0, 0.0);
+    let entity = common::spawn_one_joint(&mut app, column(1), Transform::default(), joint).entity;
+    common::update(&mut app, 2);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(entity).unwrap();
+    assert_bounds(
+        &skinned_aabb.local,
+        Vec3::new(0.9, -0.1, -0.1),
+        Vec3::new(1.1, 0.1, 0.1),
+    );
+}
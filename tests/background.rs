@@ -0,0 +1,143 @@
+//! Bounds computed on the `AsyncComputeTaskPool`: meshes despawned or moved while their task
+//! runs, and the exact bounds of `AabbQuality::Progressive` replacing the sampled ones.
+
+mod common;
+
+use std::{thread, time::Duration};
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{
+    AabbCompute, AabbQuality, AabbUpdateMode, SkinnedAabb, SkinnedAabbConfig,
+};
+use common::{assert_bounds, column};
+
+/// Update `app` until `done`, giving the background tasks time to finish, or panic.
+fn update_until(app: &mut App, mut done: impl FnMut(&App) -> bool) {
+    for _ in 0..500 {
+        app.update();
+        if done(app) {
+            return;
+        }
+        thread::sleep(Duration::from_millis(2));
+    }
+    panic!("the background bounds never landed");
+}
+
+#[test]
+fn despawned_while_bounded_in_the_background() {
+    let mut app = common::app();
+    app.world.resource_mut::<SkinnedAabbConfig>().compute = AabbCompute::Async;
+    let first = common::spawn_one_joint(
+        &mut app,
+        column(200_000),
+        Transform::default(),
+        Transform::default(),
+    );
+    common::update(&mut app, 2);
+    app.world.despawn(first.entity);
+    app.world.despawn(first.joint);
+    common::update(&mut app, 5);
+
+    // A mesh spawned afterwards still gets its bounds.
+    let second = common::spawn_one_joint(
+        &mut app,
+        column(10),
+        Transform::default(),
+        Transform::default(),
+    );
+    update_until(&mut app, |app| {
+        app.world.get::<SkinnedAabb>(second.entity).is_some()
+    });
+    assert_eq!(
+        app.world
+            .get::<SkinnedAabb>(second.entity)
+            .unwrap()
+            .local
+            .max()
+            .y,
+        9.0
+    );
+}
+
+#[test]
+fn last_pose_is_bounded_once_the_mesh_stops() {
+    // Bounded only when a joint moves, the default.
+    let mut app = common::app();
+    app.world.resource_mut::<SkinnedAabbConfig>().compute = AabbCompute::Async;
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        column(200_000),
+        Transform::default(),
+        Transform::default(),
+    );
+    // Moving every frame, faster than the tasks finish.
+    for x in 1..=5 {
+        app.world
+            .get_mut::<Transform>(spawned.joint)
+            .unwrap()
+            .translation
+            .x = x as f32;
+        app.update();
+    }
+    update_until(&mut app, |app| {
+        app.world
+            .get::<SkinnedAabb>(spawned.entity)
+            .is_some_and(|skinned_aabb| skinned_aabb.local.center.x == 5.0)
+    });
+}
+
+#[test]
+fn exact_bounds_replace_the_sampled_ones() {
+    let mut app = common::app();
+    let mut config = app.world.resource_mut::<SkinnedAabbConfig>();
+    config.quality = AabbQuality::Progressive {
+        stride: 3,
+        padding: 0.1,
+    };
+    // The sampled bounds are computed every frame, but mustn't overwrite the exact ones.
+    config.update_mode = AabbUpdateMode::EveryFrame;
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        column(200_000),
+        Transform::default(),
+        Transform::default(),
+    );
+    let local = |app: &App| {
+        app.world
+            .get::<SkinnedAabb>(spawned.entity)
+            .map(|skinned_aabb| skinned_aabb.local.clone())
+    };
+    // Moved while the first tasks run, so they finish for poses that were already left.
+    for x in 0..5 {
+        app.world
+            .get_mut::<Transform>(spawned.joint)
+            .unwrap()
+            .translation
+            .x = x as f32;
+        app.update();
+    }
+    let sampled = local(&app).unwrap();
+    // The last vertex isn't sampled, and the column is padded sideways.
+    assert_eq!(sampled.center.x, 4.0);
+    assert!(sampled.half_extents.x > 0.0);
+
+    update_until(&mut app, |app| local(app).unwrap().half_extents.x == 0.0);
+    let exact = local(&app).unwrap();
+    assert_bounds(
+        &exact,
+        Vec3::new(4.0, 0.0, 0.0),
+        Vec3::new(4.0, 199_999.0, 0.0),
+    );
+    assert!(
+        sampled.min().cmple(exact.min()).all() && sampled.max().cmpge(exact.max()).all(),
+        "the exact bounds {:?} should be inside the sampled ones {:?}",
+        exact,
+        sampled
+    );
+    common::update(&mut app, 5);
+    assert_bounds(
+        &local(&app).unwrap(),
+        exact.min().into(),
+        exact.max().into(),
+    );
+}
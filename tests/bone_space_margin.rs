@@ -0,0 +1,38 @@
+//! [`BoneSpaceMargin`] pads a limb along its joint, tighter than a [`SkinnedAabbMargin`] giving
+//! it the same thickness whichever way it points.
+
+mod common;
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{BoneSpaceMargin, SkinnedAabb, SkinnedAabbMargin};
+use common::assert_bounds;
+
+/// A limb from (0, 0) to (0, 2) on a joint turned a quarter around Z, lying along -X.
+fn run(margin: impl Component) -> SkinnedAabb {
+    let mut app = common::app();
+    let joint = Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2));
+    let entity =
+        common::spawn_one_joint(&mut app, common::column(3), Transform::default(), joint).entity;
+    app.world.entity_mut(entity).insert(margin);
+    common::update(&mut app, 3);
+    app.world.get::<SkinnedAabb>(entity).unwrap().clone()
+}
+
+#[test]
+fn tighter_than_a_world_space_margin() {
+    // Thickness around the limb, across its joint's Y axis.
+    let bone = run(BoneSpaceMargin(Vec3::new(0.1, 0.0, 0.1)));
+    assert_bounds(
+        &bone.local,
+        Vec3::new(-2.0, -0.1, -0.1),
+        Vec3::new(0.0, 0.1, 0.1),
+    );
+    let world = run(SkinnedAabbMargin::uniform(0.1));
+    assert_bounds(
+        &world.local,
+        Vec3::new(-2.1, -0.1, -0.1),
+        Vec3::new(0.1, 0.1, 0.1),
+    );
+}
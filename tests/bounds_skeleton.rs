@@ -0,0 +1,49 @@
+//! A [`BoundsSkeleton`] bounds a column with its own joint, ignoring the two joints of the render
+//! skeleton.
+
+mod common;
+
+use bevy::{prelude::*, render::mesh::skinning::SkinnedMeshInverseBindposes};
+use bevy_compute_skinned_aabb::{BoundsSkeleton, SkinnedAabb};
+use common::{assert_bounds, column};
+
+#[test]
+fn bounded_with_the_simpler_rig() {
+    let mut app = common::app();
+    // The top of the column is on the second render joint, moved far along Z.
+    let mut mesh = column(3);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_JOINT_INDEX,
+        vec![[0u16, 0, 0, 0], [0, 0, 0, 0], [1, 0, 0, 0]],
+    );
+    let render_joints = [Transform::default(), Transform::from_xyz(0.0, 0.0, 10.0)];
+    let (entity, _) = common::spawn_joints(&mut app, mesh, Transform::default(), &render_joints);
+
+    let bindposes = app
+        .world
+        .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+        .add(SkinnedMeshInverseBindposes::from(vec![Mat4::IDENTITY]));
+    let bounds_joint = app
+        .world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(Transform::from_xyz(
+            3.0, 0.0, 0.0,
+        )))
+        .id();
+    app.world.entity_mut(entity).insert(BoundsSkeleton {
+        joints: vec![bounds_joint],
+        bindposes,
+        mesh_weights: vec![([0; 4], Vec4::X); 3],
+    });
+    common::update(&mut app, 3);
+    let aabb = |app: &App| app.world.get::<SkinnedAabb>(entity).unwrap().local.clone();
+    assert_bounds(&aabb(&app), Vec3::X * 3.0, Vec3::new(3.0, 2.0, 0.0));
+
+    // Turned a quarter around Z by its bounds joint, the column lies along X.
+    app.world
+        .get_mut::<Transform>(bounds_joint)
+        .unwrap()
+        .rotation = Quat::from_rotation_z(-std::f32::consts::FRAC_PI_2);
+    common::update(&mut app, 1);
+    assert_bounds(&aabb(&app), Vec3::X * 3.0, Vec3::new(5.0, 0.0, 0.0));
+}
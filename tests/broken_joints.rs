@@ -0,0 +1,45 @@
+//! `SkinnedAabbConfig::broken_joints_inflation` growing the last bounds of a mesh whose joint was
+//! despawned.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{AabbPadding, SkinnedAabb, SkinnedAabbConfig, SkinnedAabbMargin};
+use common::{assert_bounds, column};
+
+#[test]
+fn inflated_before_the_margin_and_padding() {
+    let mut app = common::app();
+    app.world
+        .resource_mut::<SkinnedAabbConfig>()
+        .broken_joints_inflation = Some(2.0);
+    // From (0, 0, 0) to (0, 2, 0).
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    );
+    app.world
+        .entity_mut(spawned.entity)
+        .insert(SkinnedAabbMargin(Vec3::splat(0.5)))
+        .insert(AabbPadding::new(Vec3::ZERO, Vec3::Y));
+    common::update(&mut app, 2);
+    let local = |app: &App| {
+        app.world
+            .get::<SkinnedAabb>(spawned.entity)
+            .unwrap()
+            .local
+            .clone()
+    };
+    assert_bounds(&local(&app), Vec3::splat(-0.5), Vec3::new(0.5, 3.5, 0.5));
+
+    app.world.despawn(spawned.joint);
+    common::update(&mut app, 2);
+    // The column's own half extents doubled about its center, then padded as before.
+    assert_bounds(
+        &local(&app),
+        Vec3::new(-0.5, -1.5, -0.5),
+        Vec3::new(0.5, 4.5, 0.5),
+    );
+}
@@ -0,0 +1,80 @@
+//! [`VisibleToCameras`] with cameras looking at a skinned column, away from it and turned off.
+
+mod common;
+
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::{prelude::*, render::primitives::Frustum};
+use bevy_compute_skinned_aabb::VisibleToCameras;
+
+/// A perspective camera at `transform`, with the frustum it would get from bevy's renderer.
+fn spawn_camera(app: &mut App, transform: Transform, is_active: bool) -> Entity {
+    let far = 100.0;
+    let view_projection =
+        Mat4::perspective_rh(FRAC_PI_4, 1.0, 0.1, far) * transform.compute_matrix().inverse();
+    let frustum = Frustum::from_view_projection(
+        &view_projection,
+        &transform.translation,
+        &transform.back(),
+        far,
+    );
+    app.world
+        .spawn()
+        .insert(Camera {
+            is_active,
+            ..default()
+        })
+        .insert_bundle(TransformBundle::from_transform(transform))
+        .insert(frustum)
+        .id()
+}
+
+#[test]
+fn only_cameras_facing_the_mesh() {
+    let mut app = common::app();
+    let common::OneJoint { entity, joint, .. } = common::spawn_one_joint(
+        &mut app,
+        common::column(3),
+        Transform::default(),
+        Transform::default(),
+    );
+    app.world
+        .entity_mut(entity)
+        .insert(VisibleToCameras::default());
+
+    let facing = spawn_camera(
+        &mut app,
+        Transform::from_xyz(0.0, 1.0, 10.0).looking_at(Vec3::Y, Vec3::Y),
+        true,
+    );
+    // Looking along +Z, with the column behind it.
+    spawn_camera(
+        &mut app,
+        Transform::from_xyz(0.0, 1.0, 10.0).looking_at(Vec3::new(0.0, 1.0, 20.0), Vec3::Y),
+        true,
+    );
+    // Facing the column, but turned off.
+    spawn_camera(
+        &mut app,
+        Transform::from_xyz(10.0, 1.0, 0.0).looking_at(Vec3::Y, Vec3::Y),
+        false,
+    );
+    common::update(&mut app, 3);
+    assert_eq!(
+        app.world.get::<VisibleToCameras>(entity).unwrap().0,
+        vec![facing]
+    );
+
+    // Moved in front of a camera behind the second one, the column is seen by both that camera
+    // and the first one.
+    let second = spawn_camera(
+        &mut app,
+        Transform::from_xyz(0.0, 1.0, -10.0).looking_at(Vec3::new(0.0, 1.0, -20.0), Vec3::Y),
+        true,
+    );
+    app.world.get_mut::<Transform>(joint).unwrap().translation = Vec3::new(0.0, 0.0, -15.0);
+    common::update(&mut app, 3);
+    let mut visible = app.world.get::<VisibleToCameras>(entity).unwrap().0.clone();
+    visible.sort();
+    assert_eq!(visible, vec![facing, second]);
+}
@@ -0,0 +1,152 @@
+//! Headless setup shared by the integration tests: an app with only what
+//! [`ComputeSkinnedAabbPlugin`] needs on the CPU, and meshes bound to a single joint.
+
+// Each test crate only uses part of it.
+#![allow(dead_code)]
+
+use bevy::{
+    asset::AssetPlugin,
+    prelude::*,
+    render::{
+        mesh::{
+            skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+            PrimitiveTopology,
+        },
+        primitives::Aabb,
+    },
+    transform::TransformPlugin,
+};
+use bevy_compute_skinned_aabb::ComputeSkinnedAabbPlugin;
+
+pub const EPSILON: f32 = 1e-5;
+
+/// An app with only what [`ComputeSkinnedAabbPlugin`] needs on the CPU, no window or renderer.
+pub fn app() -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(AssetPlugin)
+        .add_plugin(TransformPlugin)
+        .add_asset::<Mesh>()
+        .add_asset::<SkinnedMeshInverseBindposes>()
+        .add_plugin(ComputeSkinnedAabbPlugin);
+    app
+}
+
+/// A point list of `positions`, all fully bound to joint 0.
+pub fn one_joint_mesh(positions: Vec<[f32; 3]>) -> Mesh {
+    let vertices = positions.len();
+    let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vec![[0u16; 4]; vertices]);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_JOINT_WEIGHT,
+        vec![[1.0f32, 0.0, 0.0, 0.0]; vertices],
+    );
+    mesh
+}
+
+/// A column of `vertices` points one apart along Y from the origin, see [`one_joint_mesh`].
+pub fn column(vertices: usize) -> Mesh {
+    one_joint_mesh((0..vertices).map(|i| [0.0, i as f32, 0.0]).collect())
+}
+
+/// What [`spawn_one_joint`] spawned.
+pub struct OneJoint {
+    pub entity: Entity,
+    pub joint: Entity,
+    pub mesh: Handle<Mesh>,
+    pub inverse_bindposes: Handle<SkinnedMeshInverseBindposes>,
+}
+
+/// Spawn `mesh` on a mesh entity at `transform`, skinned by a single joint at `joint` with an
+/// identity inverse bindpose.
+pub fn spawn_one_joint(
+    app: &mut App,
+    mesh: Mesh,
+    transform: Transform,
+    joint: Transform,
+) -> OneJoint {
+    let mesh = app.world.resource_mut::<Assets<Mesh>>().add(mesh);
+    let inverse_bindposes = app
+        .world
+        .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+        .add(SkinnedMeshInverseBindposes::from(vec![Mat4::IDENTITY]));
+    let joint = app
+        .world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(joint))
+        .id();
+    let entity = app
+        .world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(transform))
+        .insert(mesh.clone())
+        .insert(SkinnedMesh {
+            inverse_bindposes: inverse_bindposes.clone(),
+            joints: vec![joint],
+        })
+        .id();
+    OneJoint {
+        entity,
+        joint,
+        mesh,
+        inverse_bindposes,
+    }
+}
+
+/// Spawn `mesh` on a mesh entity at `transform`, skinned by a joint at each of `joints` with
+/// identity inverse bindposes. Returns the mesh entity and the joints.
+pub fn spawn_joints(
+    app: &mut App,
+    mesh: Mesh,
+    transform: Transform,
+    joints: &[Transform],
+) -> (Entity, Vec<Entity>) {
+    let mesh = app.world.resource_mut::<Assets<Mesh>>().add(mesh);
+    let inverse_bindposes = app
+        .world
+        .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+        .add(SkinnedMeshInverseBindposes::from(vec![
+            Mat4::IDENTITY;
+            joints.len()
+        ]));
+    let joints: Vec<Entity> = joints
+        .iter()
+        .map(|joint| {
+            app.world
+                .spawn()
+                .insert_bundle(TransformBundle::from_transform(*joint))
+                .id()
+        })
+        .collect();
+    let entity = app
+        .world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(transform))
+        .insert(mesh)
+        .insert(SkinnedMesh {
+            inverse_bindposes,
+            joints: joints.clone(),
+        })
+        .id();
+    (entity, joints)
+}
+
+/// Run `frames` updates of `app`.
+pub fn update(app: &mut App, frames: usize) {
+    for _ in 0..frames {
+        app.update();
+    }
+}
+
+pub fn assert_bounds(aabb: &Aabb, min: Vec3, max: Vec3) {
+    assert!(
+        Vec3::from(aabb.min()).abs_diff_eq(min, EPSILON)
+            && Vec3::from(aabb.max()).abs_diff_eq(max, EPSILON),
+        "bounds {} to {}, expected {} to {}",
+        aabb.min(),
+        aabb.max(),
+        min,
+        max
+    );
+}
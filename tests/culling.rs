@@ -0,0 +1,100 @@
+//! Bevy's own frustum culling of a skinned mesh animated far from its bind pose, with the
+//! [`Aabb`](bevy::render::primitives::Aabb) written by [`ComputeSkinnedAabbPlugin`].
+
+mod common;
+
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::{
+    prelude::*,
+    render::{
+        primitives::Frustum,
+        view::{VisibilityPlugin, VisibleEntities},
+    },
+};
+
+/// A perspective camera at `transform`, with the frustum it would get from bevy's renderer.
+fn spawn_camera(app: &mut App, transform: Transform) {
+    let far = 100.0;
+    let view_projection =
+        Mat4::perspective_rh(FRAC_PI_4, 1.0, 0.1, far) * transform.compute_matrix().inverse();
+    let frustum = Frustum::from_view_projection(
+        &view_projection,
+        &transform.translation,
+        &transform.back(),
+        far,
+    );
+    app.world
+        .spawn()
+        .insert(Camera::default())
+        .insert(VisibleEntities::default())
+        .insert_bundle(TransformBundle::from_transform(transform))
+        .insert(frustum);
+}
+
+/// A column bound in place at the origin, with what bevy needs to cull it.
+fn spawn_column(app: &mut App) -> common::OneJoint {
+    app.add_plugin(VisibilityPlugin);
+    let spawned = common::spawn_one_joint(
+        app,
+        common::column(3),
+        Transform::default(),
+        Transform::default(),
+    );
+    app.world
+        .entity_mut(spawned.entity)
+        .insert_bundle(VisibilityBundle::default());
+    spawned
+}
+
+fn move_joint(app: &mut App, joint: Entity, translation: Vec3) {
+    app.world.get_mut::<Transform>(joint).unwrap().translation = translation;
+    common::update(app, 2);
+}
+
+fn is_visible(app: &App, entity: Entity) -> bool {
+    app.world
+        .get::<ComputedVisibility>(entity)
+        .unwrap()
+        .is_visible()
+}
+
+#[test]
+fn visible_again_once_back_in_the_frustum() {
+    let mut app = common::app();
+    let spawned = spawn_column(&mut app);
+    spawn_camera(
+        &mut app,
+        Transform::from_xyz(0.0, 1.0, 10.0).looking_at(Vec3::Y, Vec3::Y),
+    );
+    common::update(&mut app, 2);
+    assert!(is_visible(&app, spawned.entity));
+
+    // Far out of view, though the bind pose still is in view.
+    move_joint(&mut app, spawned.joint, Vec3::X * 1000.0);
+    assert!(!is_visible(&app, spawned.entity));
+
+    move_joint(&mut app, spawned.joint, Vec3::ZERO);
+    assert!(is_visible(&app, spawned.entity));
+}
+
+#[test]
+fn visible_where_it_is_animated_to() {
+    let mut app = common::app();
+    let spawned = spawn_column(&mut app);
+    // Looking at where the column is animated to, with its bind pose out of view.
+    spawn_camera(
+        &mut app,
+        Transform::from_xyz(50.0, 1.0, 10.0).looking_at(Vec3::new(50.0, 1.0, 0.0), Vec3::Y),
+    );
+    common::update(&mut app, 2);
+    assert!(!is_visible(&app, spawned.entity));
+
+    move_joint(&mut app, spawned.joint, Vec3::X * 50.0);
+    assert!(is_visible(&app, spawned.entity));
+
+    move_joint(&mut app, spawned.joint, Vec3::ZERO);
+    assert!(!is_visible(&app, spawned.entity));
+    move_joint(&mut app, spawned.joint, Vec3::X * 50.0);
+    assert!(is_visible(&app, spawned.entity));
+}
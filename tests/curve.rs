@@ -0,0 +1,61 @@
+//! A column skinned along a `CurveSkin`, only bounded again when the curve moves unless the
+//! update mode says otherwise.
+
+mod common;
+
+use bevy::{prelude::*, render::primitives::Aabb};
+use bevy_compute_skinned_aabb::{AabbUpdateMode, CurveSkin, SkinnedAabbConfig};
+use common::{assert_bounds, column};
+
+/// A column from (0, 0, 0) to (0, 2, 0) bound to the start of a straight curve along it.
+fn spawn_curve_column(app: &mut App) -> Entity {
+    let mesh = app.world.resource_mut::<Assets<Mesh>>().add(column(3));
+    app.world
+        .spawn()
+        .insert_bundle(TransformBundle::default())
+        .insert(mesh)
+        .insert(CurveSkin {
+            curve: vec![Vec3::ZERO, Vec3::Y * 2.0],
+            samples: 2,
+            rest_length: 2.0,
+        })
+        .id()
+}
+
+/// Overwrite the bounds of `entity`, to tell whether they are computed again.
+fn clobber(app: &mut App, entity: Entity) {
+    *app.world.get_mut::<Aabb>(entity).unwrap() = Aabb::from_min_max(-Vec3::ONE, Vec3::ONE);
+}
+
+#[test]
+fn only_bounded_again_when_the_curve_changes() {
+    let mut app = common::app();
+    let entity = spawn_curve_column(&mut app);
+    common::update(&mut app, 2);
+    let aabb = |app: &App| app.world.get::<Aabb>(entity).unwrap().clone();
+    assert_bounds(&aabb(&app), Vec3::ZERO, Vec3::Y * 2.0);
+
+    clobber(&mut app, entity);
+    common::update(&mut app, 2);
+    assert_bounds(&aabb(&app), -Vec3::ONE, Vec3::ONE);
+
+    // Shifted along X, joint 0 follows the start of the curve.
+    app.world.get_mut::<CurveSkin>(entity).unwrap().curve = vec![Vec3::X, Vec3::X + Vec3::Y * 2.0];
+    app.update();
+    assert_bounds(&aabb(&app), Vec3::X, Vec3::X + Vec3::Y * 2.0);
+}
+
+#[test]
+fn bounded_every_frame_when_asked() {
+    let mut app = common::app();
+    app.world.resource_mut::<SkinnedAabbConfig>().update_mode = AabbUpdateMode::EveryFrame;
+    let entity = spawn_curve_column(&mut app);
+    common::update(&mut app, 2);
+    clobber(&mut app, entity);
+    app.update();
+    assert_bounds(
+        app.world.get::<Aabb>(entity).unwrap(),
+        Vec3::ZERO,
+        Vec3::Y * 2.0,
+    );
+}
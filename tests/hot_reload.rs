@@ -0,0 +1,134 @@
+//! Mesh and inverse bindposes assets replaced in place, as hot reloading does, and the caches
+//! built from them following along.
+
+mod common;
+
+use bevy::{
+    prelude::*,
+    render::{mesh::skinning::SkinnedMeshInverseBindposes, primitives::Aabb},
+};
+use bevy_compute_skinned_aabb::{
+    AabbCompute, AabbQuality, JointAabbs, SkinnedAabbConfig, SkinnedMeshCpuData,
+};
+use common::column;
+
+/// An app bounding a single column of 10 vertices.
+fn app() -> (App, common::OneJoint) {
+    let mut app = common::app();
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        column(10),
+        Transform::default(),
+        Transform::default(),
+    );
+    common::update(&mut app, 3);
+    (app, spawned)
+}
+
+#[test]
+fn cache_follows_a_replaced_mesh() {
+    let (mut app, common::OneJoint { entity, mesh, .. }) = app();
+    let cached = |app: &App| {
+        app.world
+            .get::<SkinnedMeshCpuData>(entity)
+            .unwrap()
+            .positions
+            .len()
+    };
+    assert_eq!(cached(&app), 10);
+
+    *app.world
+        .resource_mut::<Assets<Mesh>>()
+        .get_mut(&mesh)
+        .unwrap() = column(4);
+    common::update(&mut app, 2);
+    assert_eq!(cached(&app), 4);
+    assert_eq!(
+        app.world.get::<Aabb>(entity).unwrap().max().y,
+        3.0,
+        "the bounds should fit the new mesh"
+    );
+}
+
+#[test]
+fn joint_aabbs_follow_replaced_bindposes() {
+    let (
+        mut app,
+        common::OneJoint {
+            entity,
+            inverse_bindposes,
+            ..
+        },
+    ) = app();
+    app.world.resource_mut::<SkinnedAabbConfig>().quality = AabbQuality::PerJoint;
+    common::update(&mut app, 2);
+    let joint_box = |app: &App| {
+        app.world.get::<JointAabbs>(entity).unwrap().aabbs[0]
+            .clone()
+            .unwrap()
+    };
+    assert_eq!(joint_box(&app).min().y, 0.0);
+
+    // The column now hangs 2 below its joint in the bind pose.
+    *app.world
+        .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+        .get_mut(&inverse_bindposes)
+        .unwrap() = SkinnedMeshInverseBindposes::from(vec![Mat4::from_translation(-Vec3::Y * 2.0)]);
+    common::update(&mut app, 2);
+    assert_eq!(joint_box(&app).min().y, -2.0);
+}
+
+#[test]
+fn gpu_fallback_follows_a_replaced_mesh() {
+    // Without a render device the GPU path falls back to the CPU, which has to pick up the
+    // replaced vertices the same way.
+    let (mut app, common::OneJoint { entity, mesh, .. }) = app();
+    app.world.resource_mut::<SkinnedAabbConfig>().compute = AabbCompute::Gpu;
+    common::update(&mut app, 2);
+    assert_eq!(app.world.get::<Aabb>(entity).unwrap().max().y, 9.0);
+
+    *app.world
+        .resource_mut::<Assets<Mesh>>()
+        .get_mut(&mesh)
+        .unwrap() = column(4);
+    common::update(&mut app, 2);
+    assert_eq!(app.world.get::<Aabb>(entity).unwrap().max().y, 3.0);
+}
+
+#[test]
+fn mismatched_mesh_keeps_its_bounds_until_fixed() {
+    let (
+        mut app,
+        common::OneJoint {
+            entity,
+            joint,
+            mesh,
+            ..
+        },
+    ) = app();
+    // Reloaded with its top bound to a second joint the skin doesn't have.
+    let mut mismatched = column(4);
+    mismatched.insert_attribute(
+        Mesh::ATTRIBUTE_JOINT_INDEX,
+        vec![[0u16, 0, 0, 0], [0, 0, 0, 0], [0, 0, 0, 0], [1, 0, 0, 0]],
+    );
+    *app.world
+        .resource_mut::<Assets<Mesh>>()
+        .get_mut(&mesh)
+        .unwrap() = mismatched;
+    common::update(&mut app, 2);
+    // Skipped once the reloaded mesh is cached, so the bounds don't follow the joint.
+    app.world.get_mut::<Transform>(joint).unwrap().translation = Vec3::X * 5.0;
+    common::update(&mut app, 2);
+    let aabb = app.world.get::<Aabb>(entity).unwrap();
+    assert_eq!((aabb.min().x, aabb.max().y), (0.0, 9.0));
+
+    // The mesh agrees with the skin again.
+    *app.world
+        .resource_mut::<Assets<Mesh>>()
+        .get_mut(&mesh)
+        .unwrap() = column(4);
+    common::update(&mut app, 2);
+    let aabb = app.world.get::<Aabb>(entity).unwrap();
+    assert_eq!((aabb.min().x, aabb.max().y), (5.0, 3.0));
+}
@@ -0,0 +1,98 @@
+//! [`SkinnedAabbConfig::hysteresis`] on a column pulsing in length: the bounds always contain the
+//! pose, and only shrink once it has been smaller for a few updates.
+
+mod common;
+
+use bevy::{prelude::*, render::primitives::Aabb};
+use bevy_compute_skinned_aabb::{AabbHysteresis, SkinnedAabbConfig};
+use common::{column, EPSILON};
+
+/// The length of the column on `frame`, pulsing between 1 and 3.
+fn pulse(frame: usize) -> f32 {
+    2.0 + (frame as f32 * 0.5).sin()
+}
+
+/// Pulse a column from (0, 0, 0) to (0, 2, 0) for `frames` frames with `hysteresis`, returning
+/// the top of its bounds and of its pose on each frame.
+fn run(hysteresis: AabbHysteresis, frames: usize) -> Vec<(f32, f32)> {
+    let mut app = common::app();
+    app.world.resource_mut::<SkinnedAabbConfig>().hysteresis = hysteresis;
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    );
+    (0..frames)
+        .map(|frame| {
+            let length = pulse(frame);
+            app.world.get_mut::<Transform>(spawned.joint).unwrap().scale =
+                Vec3::new(1.0, length * 0.5, 1.0);
+            app.update();
+            let aabb = app.world.get::<Aabb>(spawned.entity).unwrap();
+            (aabb.max().y, length)
+        })
+        .collect()
+}
+
+#[test]
+fn bounds_contain_the_pulse() {
+    for hysteresis in [
+        AabbHysteresis {
+            shrink_delay: 4,
+            shrink_rate: 0.0,
+        },
+        AabbHysteresis {
+            shrink_delay: 0,
+            shrink_rate: 0.5,
+        },
+        AabbHysteresis {
+            shrink_delay: 2,
+            shrink_rate: 100.0,
+        },
+    ] {
+        let tops = run(hysteresis, 40);
+        for (frame, (top, length)) in tops.iter().enumerate() {
+            assert!(
+                *top >= length - EPSILON,
+                "{:?}, frame {}: bounds up to {} don't contain the column up to {}",
+                hysteresis,
+                frame,
+                top,
+                length
+            );
+        }
+        // The bounds trail the column as it shrinks.
+        assert!(tops.iter().any(|(top, length)| *top > length + 0.1));
+    }
+}
+
+#[test]
+fn shrink_after_the_delay() {
+    let delay = 4;
+    let tops = run(
+        AabbHysteresis {
+            shrink_delay: delay,
+            shrink_rate: 0.0,
+        },
+        40,
+    );
+    for (frame, (top, length)) in tops.iter().enumerate() {
+        let shrinking_for = (0..frame)
+            .rev()
+            .take_while(|previous| pulse(*previous + 1) < pulse(*previous))
+            .count();
+        if shrinking_for >= delay as usize {
+            assert!((top - length).abs() < EPSILON, "frame {}", frame);
+        } else if shrinking_for > 0 {
+            assert!(*top > length + EPSILON, "frame {}", frame);
+        }
+    }
+}
+
+#[test]
+fn zero_is_exact() {
+    for (top, length) in run(AabbHysteresis::default(), 40) {
+        assert!((top - length).abs() < EPSILON);
+    }
+}
@@ -0,0 +1,60 @@
+//! [`AabbUpdateMode::OnJointChange`] meshes are only bounded again once a joint moves past
+//! [`SkinnedAabbConfig::joint_epsilon`], or their inverse bindposes are modified.
+
+mod common;
+
+use bevy::{
+    prelude::*,
+    render::{mesh::skinning::SkinnedMeshInverseBindposes, primitives::Aabb},
+};
+use bevy_compute_skinned_aabb::SkinnedAabbConfig;
+use common::{assert_bounds, column};
+
+/// Overwrite the bounds of `entity`, to tell whether they are computed again.
+fn clobber(app: &mut App, entity: Entity) {
+    *app.world.get_mut::<Aabb>(entity).unwrap() = Aabb::from_min_max(-Vec3::ONE, Vec3::ONE);
+}
+
+#[test]
+fn frozen_joints_are_bounded_once() {
+    let mut app = common::app();
+    let epsilon = app.world.resource::<SkinnedAabbConfig>().joint_epsilon;
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    );
+    common::update(&mut app, 2);
+    let aabb = |app: &App| app.world.get::<Aabb>(spawned.entity).unwrap().clone();
+    assert_bounds(&aabb(&app), Vec3::ZERO, Vec3::Y * 2.0);
+
+    // The animation is paused, but still writes the joint's transform every frame, with a
+    // little jitter well within the epsilon.
+    clobber(&mut app, spawned.entity);
+    for frame in 0..100 {
+        let jitter = if frame % 2 == 0 { 0.1 } else { -0.1 } * epsilon;
+        app.world
+            .get_mut::<Transform>(spawned.joint)
+            .unwrap()
+            .translation = Vec3::X * jitter;
+        app.update();
+    }
+    assert_bounds(&aabb(&app), -Vec3::ONE, Vec3::ONE);
+
+    app.world
+        .get_mut::<Transform>(spawned.joint)
+        .unwrap()
+        .translation = Vec3::X;
+    app.update();
+    assert_bounds(&aabb(&app), Vec3::X, Vec3::X + Vec3::Y * 2.0);
+
+    // Reloading the inverse bindposes moves the mesh without any joint moving.
+    clobber(&mut app, spawned.entity);
+    *app.world
+        .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+        .get_mut(&spawned.inverse_bindposes)
+        .unwrap() = SkinnedMeshInverseBindposes::from(vec![Mat4::IDENTITY]);
+    common::update(&mut app, 2);
+    assert_bounds(&aabb(&app), Vec3::X, Vec3::X + Vec3::Y * 2.0);
+}
@@ -0,0 +1,157 @@
+//! The systems of `ComputeSkinnedAabbPlugin` and `SkinnedAabbDebugPlugin` are skipped while there
+//! is no skinned mesh, and pick up skinned meshes spawned later, e.g. once their assets finish
+//! loading.
+
+mod common;
+
+use bevy::{
+    ecs::schedule::ShouldRun, prelude::*, render::mesh::skinning::SkinnedMeshInverseBindposes,
+};
+use bevy_compute_skinned_aabb::{
+    any_skinned_meshes, AnimationAabb, BoundsSkeleton, JointColors, NoSkinnedAabb, SkinnedAabb,
+    SkinnedAabbDebugConfig, SkinnedAabbDebugPlugin,
+};
+use common::{assert_bounds, column};
+
+/// What [`any_skinned_meshes`] says of `app` now.
+fn should_run(app: &mut App) -> ShouldRun {
+    let mut system = IntoSystem::into_system(any_skinned_meshes);
+    system.initialize(&mut app.world);
+    system.run((), &mut app.world)
+}
+
+#[test]
+fn bounded_once_spawned() {
+    let mut app = common::app();
+    common::update(&mut app, 10);
+    assert_eq!(should_run(&mut app), ShouldRun::No);
+
+    // With only opted out meshes, the systems are still skipped.
+    let opted_out = common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    )
+    .entity;
+    app.world.entity_mut(opted_out).insert(NoSkinnedAabb);
+    common::update(&mut app, 10);
+    assert_eq!(should_run(&mut app), ShouldRun::No);
+    assert!(app.world.get::<SkinnedAabb>(opted_out).is_none());
+
+    let joint = Transform::from_xyz(1.0, 0.0, 0.0);
+    let entity = common::spawn_one_joint(&mut app, column(3), Transform::default(), joint).entity;
+    assert_eq!(should_run(&mut app), ShouldRun::Yes);
+    common::update(&mut app, 2);
+    assert_bounds(
+        &app.world.get::<SkinnedAabb>(entity).unwrap().local,
+        Vec3::X,
+        Vec3::new(1.0, 2.0, 0.0),
+    );
+    assert!(app.world.get::<SkinnedAabb>(opted_out).is_none());
+
+    // Despawned and spawned again, the systems stop and start again.
+    app.world.despawn(entity);
+    common::update(&mut app, 5);
+    assert_eq!(should_run(&mut app), ShouldRun::No);
+    let entity = common::spawn_one_joint(&mut app, column(2), Transform::default(), joint).entity;
+    common::update(&mut app, 2);
+    assert_bounds(
+        &app.world.get::<SkinnedAabb>(entity).unwrap().local,
+        Vec3::X,
+        Vec3::new(1.0, 1.0, 0.0),
+    );
+}
+
+#[test]
+fn bounds_skeleton_bounded_once_spawned() {
+    let mut app = common::app();
+    common::update(&mut app, 10);
+    let entity = common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    )
+    .entity;
+    let bindposes = app
+        .world
+        .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+        .add(SkinnedMeshInverseBindposes::from(vec![Mat4::IDENTITY]));
+    let bounds_joint = app
+        .world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(Transform::from_xyz(
+            3.0, 0.0, 0.0,
+        )))
+        .id();
+    app.world.entity_mut(entity).insert(BoundsSkeleton {
+        joints: vec![bounds_joint],
+        bindposes,
+        mesh_weights: vec![([0; 4], Vec4::X); 3],
+    });
+    common::update(&mut app, 3);
+    assert_bounds(
+        &app.world.get::<SkinnedAabb>(entity).unwrap().local,
+        Vec3::X * 3.0,
+        Vec3::new(3.0, 2.0, 0.0),
+    );
+}
+
+#[test]
+fn animation_baked_once_spawned() {
+    let mut app = common::app();
+    app.add_asset::<AnimationClip>();
+    common::update(&mut app, 10);
+    let entity = common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    )
+    .entity;
+    // Without clips, the bake is the bind pose alone.
+    app.world
+        .entity_mut(entity)
+        .insert(AnimationAabb::new(Vec::new()));
+    common::update(&mut app, 5);
+    assert!(app.world.get::<NoSkinnedAabb>(entity).is_some());
+    assert_bounds(
+        &app.world.get::<SkinnedAabb>(entity).unwrap().local,
+        Vec3::ZERO,
+        Vec3::Y * 2.0,
+    );
+}
+
+#[test]
+fn joints_colored_once_spawned() {
+    let mut app = common::app();
+    app.add_plugin(SkinnedAabbDebugPlugin)
+        .insert_resource(SkinnedAabbDebugConfig {
+            enabled: true,
+            joint_colors: JointColors::Dominant,
+            ..default()
+        });
+    common::update(&mut app, 10);
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    );
+    let colored = |app: &App| {
+        app.world
+            .resource::<Assets<Mesh>>()
+            .get(&spawned.mesh)
+            .unwrap()
+            .attribute(Mesh::ATTRIBUTE_COLOR)
+            .is_some()
+    };
+    common::update(&mut app, 3);
+    assert!(colored(&app));
+
+    // Turned off, the colors are put back.
+    app.world.resource_mut::<SkinnedAabbDebugConfig>().enabled = false;
+    common::update(&mut app, 1);
+    assert!(!colored(&app));
+}
@@ -0,0 +1,46 @@
+//! [`PerLayerAabb`] bounds the part of a column on each of its render layers.
+
+mod common;
+
+use bevy::{prelude::*, render::view::RenderLayers};
+use bevy_compute_skinned_aabb::{PerLayerAabb, ATTRIBUTE_RENDER_LAYERS};
+use common::assert_bounds;
+
+#[test]
+fn distinct_bounds_per_layer() {
+    let mut app = common::app();
+    // The bottom two points of the column are on layer 0, the top two on layer 1, the one in
+    // between on both.
+    let mut mesh = common::column(5);
+    mesh.insert_attribute(
+        ATTRIBUTE_RENDER_LAYERS,
+        vec![0b01u32, 0b01, 0b11, 0b10, 0b10],
+    );
+    let joint = Transform::from_xyz(1.0, 0.0, 0.0);
+    let entity = common::spawn_one_joint(&mut app, mesh, Transform::default(), joint).entity;
+    app.world
+        .entity_mut(entity)
+        .insert(RenderLayers::from_layers(&[0, 1, 2]))
+        .insert(PerLayerAabb::default());
+    common::update(&mut app, 3);
+
+    let per_layer_aabb = &app.world.get::<PerLayerAabb>(entity).unwrap().0;
+    // Layer 2 has no vertex.
+    assert_eq!(per_layer_aabb.len(), 2);
+    assert_bounds(
+        &per_layer_aabb[&0],
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(1.0, 2.0, 0.0),
+    );
+    assert_bounds(
+        &per_layer_aabb[&1],
+        Vec3::new(1.0, 2.0, 0.0),
+        Vec3::new(1.0, 4.0, 0.0),
+    );
+
+    // Only the layers of the entity are bounded.
+    app.world.entity_mut(entity).insert(RenderLayers::layer(1));
+    common::update(&mut app, 1);
+    let per_layer_aabb = &app.world.get::<PerLayerAabb>(entity).unwrap().0;
+    assert_eq!(per_layer_aabb.keys().collect::<Vec<_>>(), vec![&1]);
+}
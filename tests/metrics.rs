@@ -0,0 +1,48 @@
+//! [`SkinnedAabbMetrics::tightness`] of the bounds of an elongated mesh, which an [`Aabb`] fits
+//! much tighter than a sphere.
+//!
+//! [`Aabb`]: bevy::render::primitives::Aabb
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{SkinnedAabbConfig, SkinnedAabbMetrics, VolumeKind};
+
+/// The tightness of a box 10 long and 1 across, the corners of its exact bounds.
+fn tightness(kind: VolumeKind) -> f32 {
+    let mut app = common::app();
+    app.world.resource_mut::<SkinnedAabbConfig>().metrics = Some(kind);
+    let corners = (0..8)
+        .map(|i| {
+            let bit = |b: u32| ((i >> b) & 1) as f32;
+            [bit(0) * 10.0, bit(1), bit(2)]
+        })
+        .collect();
+    let entity = common::spawn_one_joint(
+        &mut app,
+        common::one_joint_mesh(corners),
+        Transform::default(),
+        Transform::default(),
+    )
+    .entity;
+    common::update(&mut app, 3);
+    app.world
+        .get::<SkinnedAabbMetrics>(entity)
+        .unwrap()
+        .tightness
+}
+
+#[test]
+fn aabb_tighter_than_sphere_on_an_elongated_mesh() {
+    let aabb = tightness(VolumeKind::Aabb);
+    let sphere = tightness(VolumeKind::Sphere);
+    assert!((aabb - 1.0).abs() < 1e-4, "aabb tightness {}", aabb);
+    // A sphere reaching the corners, 5.05 from the center, around a volume of 10.
+    let expected = 4.0 / 3.0 * std::f32::consts::PI * 25.5f32.powf(1.5) / 10.0;
+    assert!(
+        (sphere - expected).abs() < 1e-2,
+        "sphere tightness {}",
+        sphere
+    );
+    assert!(aabb < sphere);
+}
@@ -0,0 +1,54 @@
+//! [`SkinnedAabbConfig::monotonic`] only grows the bounds as the mesh moves, until
+//! [`reset_bounds`] lets them fit the current pose again.
+
+mod common;
+
+use bevy::{prelude::*, render::primitives::Aabb};
+use bevy_compute_skinned_aabb::{reset_bounds, SkinnedAabb, SkinnedAabbConfig};
+use common::assert_bounds;
+
+#[test]
+fn never_shrinks_until_reset() {
+    let mut app = common::app();
+    app.world.resource_mut::<SkinnedAabbConfig>().monotonic = true;
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        common::column(2),
+        Transform::default(),
+        Transform::default(),
+    );
+    common::update(&mut app, 3);
+
+    // The column from (x, 0) to (x, 1) swept along X.
+    let mut max_x: f32 = 0.0;
+    let mut min_x: f32 = 0.0;
+    for x in [1.0, 3.0, 2.0, -1.0, 0.5] {
+        app.world
+            .get_mut::<Transform>(spawned.joint)
+            .unwrap()
+            .translation
+            .x = x;
+        common::update(&mut app, 2);
+        max_x = max_x.max(x);
+        min_x = min_x.min(x);
+        let local = &app.world.get::<SkinnedAabb>(spawned.entity).unwrap().local;
+        assert_bounds(
+            local,
+            Vec3::new(min_x, 0.0, 0.0),
+            Vec3::new(max_x, 1.0, 0.0),
+        );
+        assert_bounds(
+            app.world.get::<Aabb>(spawned.entity).unwrap(),
+            Vec3::new(min_x, 0.0, 0.0),
+            Vec3::new(max_x, 1.0, 0.0),
+        );
+    }
+
+    // The joint stays where it is, the bounds fit it again anyway.
+    let entity = spawned.entity;
+    SystemStage::single(move |mut commands: Commands| reset_bounds(&mut commands, entity))
+        .run(&mut app.world);
+    common::update(&mut app, 2);
+    let local = &app.world.get::<SkinnedAabb>(entity).unwrap().local;
+    assert_bounds(local, Vec3::new(0.5, 0.0, 0.0), Vec3::new(0.5, 1.0, 0.0));
+}
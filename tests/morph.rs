@@ -0,0 +1,120 @@
+//! [`MorphTargets`] blended into a skinned quad by its [`MorphWeights`], bounded in the blended
+//! shape on the frame the weights change.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{MorphTargets, MorphWeights, SkinnedAabb};
+use common::assert_bounds;
+
+/// A quad from (0, 0) to (1, 1) on a joint at (0, 0, 2), with a target pushing its right edge
+/// out by 1 along X and Z.
+fn spawn_quad(app: &mut App) -> Entity {
+    let quad = common::one_joint_mesh(vec![
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [1.0, 1.0, 0.0],
+    ]);
+    let joint = Transform::from_xyz(0.0, 0.0, 2.0);
+    let entity = common::spawn_one_joint(app, quad, Transform::default(), joint).entity;
+    let target = vec![
+        Vec3::ZERO,
+        Vec3::new(1.0, 0.0, 1.0),
+        Vec3::ZERO,
+        Vec3::X + Vec3::Z,
+    ];
+    app.world
+        .entity_mut(entity)
+        .insert(MorphTargets::new(vec![target]));
+    entity
+}
+
+fn set_weights(app: &mut App, entity: Entity, weights: Vec<f32>) {
+    app.world.entity_mut(entity).insert(MorphWeights(weights));
+}
+
+fn local_aabb(app: &App, entity: Entity) -> &bevy::render::primitives::Aabb {
+    &app.world.get::<SkinnedAabb>(entity).unwrap().local
+}
+
+#[test]
+fn base_shape_without_weights() {
+    let mut app = common::app();
+    let entity = spawn_quad(&mut app);
+    common::update(&mut app, 3);
+    let (min, max) = (Vec3::new(0.0, 0.0, 2.0), Vec3::new(1.0, 1.0, 2.0));
+    assert_bounds(local_aabb(&app, entity), min, max);
+
+    set_weights(&mut app, entity, vec![0.0]);
+    common::update(&mut app, 2);
+    assert_bounds(local_aabb(&app, entity), min, max);
+}
+
+#[test]
+fn bounded_in_the_blended_shape() {
+    let mut app = common::app();
+    let entity = spawn_quad(&mut app);
+    common::update(&mut app, 3);
+
+    set_weights(&mut app, entity, vec![0.5]);
+    app.update();
+    assert_bounds(
+        local_aabb(&app, entity),
+        Vec3::new(0.0, 0.0, 2.0),
+        Vec3::new(1.5, 1.0, 2.5),
+    );
+    let targets = app.world.get::<MorphTargets>(entity).unwrap();
+    assert_eq!(targets.applied_weights(), [0.5]);
+
+    // Blended from the base shape again rather than on top of the last one.
+    set_weights(&mut app, entity, vec![1.0]);
+    app.update();
+    assert_bounds(
+        local_aabb(&app, entity),
+        Vec3::new(0.0, 0.0, 2.0),
+        Vec3::new(2.0, 1.0, 3.0),
+    );
+
+    set_weights(&mut app, entity, vec![]);
+    app.update();
+    assert_bounds(
+        local_aabb(&app, entity),
+        Vec3::new(0.0, 0.0, 2.0),
+        Vec3::new(1.0, 1.0, 2.0),
+    );
+}
+
+#[test]
+fn bounds_ease_in_and_out_with_the_shape() {
+    let mut app = common::app();
+    let entity = spawn_quad(&mut app);
+    app.world.get_mut::<MorphTargets>(entity).unwrap().smoothing = 0.5;
+    common::update(&mut app, 3);
+
+    // Half of the remaining way each frame, the far corner of the quad along with it.
+    set_weights(&mut app, entity, vec![1.0]);
+    for weight in [0.5, 0.75, 0.875, 0.9375] {
+        app.update();
+        let targets = app.world.get::<MorphTargets>(entity).unwrap();
+        assert_eq!(targets.applied_weights(), [weight]);
+        assert_bounds(
+            local_aabb(&app, entity),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(1.0 + weight, 1.0, 2.0 + weight),
+        );
+    }
+    common::update(&mut app, 10);
+    let targets = app.world.get::<MorphTargets>(entity).unwrap();
+    assert_eq!(targets.applied_weights(), [1.0]);
+
+    set_weights(&mut app, entity, vec![0.0]);
+    for weight in [0.5, 0.25] {
+        app.update();
+        assert_bounds(
+            local_aabb(&app, entity),
+            Vec3::new(0.0, 0.0, 2.0),
+            Vec3::new(1.0 + weight, 1.0, 2.0 + weight),
+        );
+    }
+}
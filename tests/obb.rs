@@ -0,0 +1,80 @@
+//! A [`SkinnedObb`] along the root joint of a long box turned diagonally to the world axes,
+//! compared with its world space bounds.
+
+mod common;
+
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{SkinnedAabb, SkinnedObb};
+
+const EPSILON: f32 = 1e-4;
+/// Half extents of the box in its bind pose, long along X.
+const HALF_EXTENTS: Vec3 = Vec3::new(2.0, 0.25, 0.25);
+
+/// The corners of the box, all bound to joint 0, on a root joint turned 45° about Y.
+fn run_turned_box() -> App {
+    let mut app = common::app();
+    let positions: Vec<[f32; 3]> = (0..8)
+        .map(|i| {
+            let sign = Vec3::new(
+                if i & 1 == 0 { -1.0 } else { 1.0 },
+                if i & 2 == 0 { -1.0 } else { 1.0 },
+                if i & 4 == 0 { -1.0 } else { 1.0 },
+            );
+            (sign * HALF_EXTENTS).to_array()
+        })
+        .collect();
+    let root = Transform::from_xyz(1.0, 0.5, -2.0).with_rotation(Quat::from_rotation_y(FRAC_PI_4));
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        common::one_joint_mesh(positions),
+        Transform::default(),
+        root,
+    );
+    app.world
+        .entity_mut(spawned.entity)
+        .insert(SkinnedObb::joint(0));
+    common::update(&mut app, 3);
+    app
+}
+
+#[test]
+fn root_joint_box_is_tighter_than_world_aabb() {
+    let mut app = run_turned_box();
+    let (obb, skinned_aabb, transform) = app
+        .world
+        .query::<(&SkinnedObb, &SkinnedAabb, &GlobalTransform)>()
+        .single(&app.world);
+
+    assert!(
+        obb.half_extents.abs_diff_eq(HALF_EXTENTS, EPSILON),
+        "{}",
+        obb.half_extents
+    );
+    let size = Vec3::from(skinned_aabb.world.half_extents) * 2.0;
+    let aabb_volume = size.x * size.y * size.z;
+    assert!(
+        obb.volume() < aabb_volume * 0.5,
+        "box volume {} against {}",
+        obb.volume(),
+        aabb_volume
+    );
+
+    // The box around the box is the world space bounds.
+    let world = obb.to_world_aabb(transform);
+    assert!(Vec3::from(world.min()).abs_diff_eq(skinned_aabb.world.min().into(), EPSILON));
+    assert!(Vec3::from(world.max()).abs_diff_eq(skinned_aabb.world.max().into(), EPSILON));
+}
+
+#[test]
+fn contains_points_along_the_root_joint() {
+    let mut app = run_turned_box();
+    let obb = *app.world.query::<&SkinnedObb>().single(&app.world);
+    let root = Transform::from_xyz(1.0, 0.5, -2.0).with_rotation(Quat::from_rotation_y(FRAC_PI_4));
+    // Near the far end of the box, and just past it.
+    assert!(obb.contains_point(root * Vec3::new(1.9, 0.2, -0.2)));
+    assert!(!obb.contains_point(root * Vec3::new(2.1, 0.0, 0.0)));
+    // Inside the world bounds, but in a corner the turned box doesn't reach.
+    assert!(!obb.contains_point(root * Vec3::new(0.0, 0.0, 1.0)));
+}
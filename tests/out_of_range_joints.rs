@@ -0,0 +1,72 @@
+//! Meshes with joint indices past the joints of their skeleton are skipped by every system,
+//! keeping their last bounds, and [`SkinnedAabbConfig::metrics`] counts the vertices at fault.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{
+    PerLayerAabb, SkinnedAabb, SkinnedAabbConfig, SkinnedAabbMetrics, SkinnedVertexPositions,
+    StoreSkinnedPositions, VolumeKind,
+};
+use common::assert_bounds;
+
+/// Two vertices on a single joint at (1, 0, 0), the second with joint index 3 if `out_of_range`.
+fn mesh(out_of_range: bool) -> Mesh {
+    let mut mesh = common::one_joint_mesh(vec![[0.0, 0.0, 0.0], [0.0, 1.0, 1.0]]);
+    let second = if out_of_range { 3 } else { 0 };
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_JOINT_INDEX,
+        vec![[0u16, 0, 0, 0], [second, 0, 0, 0]],
+    );
+    mesh
+}
+
+fn spawn(app: &mut App, mesh: Mesh) -> common::OneJoint {
+    let spawned = common::spawn_one_joint(
+        app,
+        mesh,
+        Transform::default(),
+        Transform::from_xyz(1.0, 0.0, 0.0),
+    );
+    app.world
+        .entity_mut(spawned.entity)
+        .insert(StoreSkinnedPositions)
+        .insert(PerLayerAabb::default());
+    common::update(app, 3);
+    spawned
+}
+
+#[test]
+fn skipped_by_every_system() {
+    let mut app = common::app();
+    let entity = spawn(&mut app, mesh(true)).entity;
+    assert!(app.world.get::<SkinnedAabb>(entity).is_none());
+    assert!(app.world.get::<PerLayerAabb>(entity).unwrap().0.is_empty());
+    assert!(app.world.get::<SkinnedVertexPositions>(entity).is_none());
+}
+
+#[test]
+fn counted_in_the_last_metrics() {
+    let mut app = common::app();
+    app.world.resource_mut::<SkinnedAabbConfig>().metrics = Some(VolumeKind::Aabb);
+    let spawned = spawn(&mut app, mesh(false));
+    let metrics = |app: &App| *app.world.get::<SkinnedAabbMetrics>(spawned.entity).unwrap();
+    assert_eq!(metrics(&app).clamped_index_count, 0);
+
+    *app.world
+        .resource_mut::<Assets<Mesh>>()
+        .get_mut(&spawned.mesh)
+        .unwrap() = mesh(true);
+    common::update(&mut app, 2);
+    app.world
+        .get_mut::<Transform>(spawned.joint)
+        .unwrap()
+        .translation = Vec3::X * 5.0;
+    common::update(&mut app, 2);
+    assert_eq!(metrics(&app).clamped_index_count, 1);
+    assert_bounds(
+        &app.world.get::<SkinnedAabb>(spawned.entity).unwrap().local,
+        Vec3::new(1.0, 0.0, 0.0),
+        Vec3::new(1.0, 1.0, 1.0),
+    );
+}
@@ -0,0 +1,99 @@
+//! [`AabbPadding`] on a flat quad, padded on one side only, in the mesh entity's local space and on
+//! top of the padding of sampled bounds, and [`SkinnedAabbMargin`] around it.
+
+mod common;
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{
+    AabbPadding, AabbQuality, SkinnedAabb, SkinnedAabbConfig, SkinnedAabbMargin,
+};
+use common::assert_bounds;
+
+/// A quad from (0, 0) to (1, 2) with no thickness, bound to a joint at the origin, on a mesh
+/// entity at `transform`.
+fn run_quad(transform: Transform, padding: impl Component, quality: AabbQuality) -> SkinnedAabb {
+    let mut app = common::app();
+    app.world.resource_mut::<SkinnedAabbConfig>().quality = quality;
+    let quad = common::one_joint_mesh(vec![
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 2.0, 0.0],
+        [1.0, 2.0, 0.0],
+    ]);
+    let entity = common::spawn_one_joint(&mut app, quad, transform, Transform::default()).entity;
+    app.world.entity_mut(entity).insert(padding);
+    common::update(&mut app, 3);
+    app.world.get::<SkinnedAabb>(entity).unwrap().clone()
+}
+
+#[test]
+fn padded_on_one_side() {
+    let padding = AabbPadding::new(Vec3::new(0.0, 0.0, 0.25), Vec3::new(0.0, 0.5, 0.25));
+    let skinned_aabb = run_quad(Transform::default(), padding, AabbQuality::Exact);
+    let (min, max) = (Vec3::new(0.0, 0.0, -0.25), Vec3::new(1.0, 2.5, 0.25));
+    assert_bounds(&skinned_aabb.local, min, max);
+    assert_bounds(&skinned_aabb.world, min, max);
+}
+
+#[test]
+fn padded_in_local_space() {
+    // Turned a quarter around Z, the local +Y of the mesh entity is the world -X.
+    let transform = Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2));
+    let padding = AabbPadding::new(Vec3::ZERO, Vec3::Y * 0.5);
+    let skinned_aabb = run_quad(transform, padding, AabbQuality::Exact);
+    assert_bounds(
+        &skinned_aabb.local,
+        Vec3::new(0.0, -1.0, 0.0),
+        Vec3::new(2.0, 0.5, 0.0),
+    );
+    assert_bounds(
+        &skinned_aabb.world,
+        Vec3::new(-0.5, 0.0, 0.0),
+        Vec3::new(1.0, 2.0, 0.0),
+    );
+}
+
+#[test]
+fn padded_on_top_of_sampling() {
+    // Every other vertex is the left edge, from (0, 0) to (0, 2), padded by 0.1 of its largest
+    // half extent on each side, then by the padding on the top.
+    let quality = AabbQuality::Sampled {
+        stride: 2,
+        padding: 0.1,
+    };
+    let padding = AabbPadding::new(Vec3::ZERO, Vec3::Y * 0.5);
+    let skinned_aabb = run_quad(Transform::default(), padding, quality);
+    assert_bounds(
+        &skinned_aabb.local,
+        Vec3::splat(-0.1),
+        Vec3::new(0.1, 2.6, 0.1),
+    );
+}
+
+#[test]
+fn margin_widens_around_the_center() {
+    let margin = SkinnedAabbMargin(Vec3::new(0.5, 0.25, -1.0));
+    let skinned_aabb = run_quad(Transform::default(), margin, AabbQuality::Exact);
+    // The negative margin on Z leaves the quad flat.
+    assert_eq!(
+        Vec3::from(skinned_aabb.local.center),
+        Vec3::new(0.5, 1.0, 0.0)
+    );
+    assert_eq!(
+        Vec3::from(skinned_aabb.local.half_extents),
+        Vec3::new(1.0, 1.25, 0.0)
+    );
+
+    let skinned_aabb = run_quad(
+        Transform::default(),
+        SkinnedAabbMargin::uniform(0.1),
+        AabbQuality::Exact,
+    );
+    assert_bounds(
+        &skinned_aabb.world,
+        Vec3::new(-0.1, -0.1, -0.1),
+        Vec3::new(1.1, 2.1, 0.1),
+    );
+}
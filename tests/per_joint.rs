@@ -0,0 +1,41 @@
+//! [`AabbQuality::PerJoint`] moves the bind pose box of each joint instead of skinning every
+//! vertex, a superset of the exact bounds that [`AabbQuality::Exact`] goes back to.
+
+mod common;
+
+use std::f32::consts::FRAC_PI_4;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{AabbQuality, SkinnedAabb, SkinnedAabbConfig};
+use common::assert_bounds;
+
+#[test]
+fn superset_of_the_exact_bounds() {
+    let mut app = common::app();
+    app.world.resource_mut::<SkinnedAabbConfig>().quality = AabbQuality::PerJoint;
+    // A diamond with its tips on the axes, turned an eighth around Z: its tips land on the
+    // diagonals at 1/√2, while the corners of its bind pose box land on the axes at √2.
+    let diamond = common::one_joint_mesh(vec![
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+        [-1.0, 0.0, 0.0],
+        [0.0, -1.0, 0.0],
+    ]);
+    let joint = Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_4));
+    let entity = common::spawn_one_joint(&mut app, diamond, Transform::default(), joint).entity;
+    common::update(&mut app, 3);
+    let (min, max) = (Vec3::new(-1.0, -1.0, 0.0), Vec3::new(1.0, 1.0, 0.0));
+    assert_bounds(
+        &app.world.get::<SkinnedAabb>(entity).unwrap().local,
+        min * 2f32.sqrt(),
+        max * 2f32.sqrt(),
+    );
+
+    app.world.entity_mut(entity).insert(AabbQuality::Exact);
+    common::update(&mut app, 2);
+    assert_bounds(
+        &app.world.get::<SkinnedAabb>(entity).unwrap().local,
+        min / 2f32.sqrt(),
+        max / 2f32.sqrt(),
+    );
+}
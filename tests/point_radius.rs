@@ -0,0 +1,100 @@
+//! [`PointRadius`] grows the bounds of a column of points by the radius of each point, scaled
+//! with the joint, also as the joint's scale is animated.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{
+    PointRadius, SkinnedAabb, SkinnedAabbConfig, ATTRIBUTE_POINT_RADIUS,
+};
+use common::{assert_bounds, column};
+
+/// A column from (0, 0, 0) to (0, 2, 0) with `radius`, on a joint at `joint`.
+fn run(radius: PointRadius, radii: Option<Vec<f32>>, joint: Transform) -> SkinnedAabb {
+    let mut app = common::app();
+    let mut mesh = common::column(3);
+    if let Some(radii) = radii {
+        mesh.insert_attribute(ATTRIBUTE_POINT_RADIUS, radii);
+    }
+    let entity = common::spawn_one_joint(&mut app, mesh, Transform::default(), joint).entity;
+    app.world.entity_mut(entity).insert(radius);
+    common::update(&mut app, 3);
+    app.world.get::<SkinnedAabb>(entity).unwrap().clone()
+}
+
+#[test]
+fn uniform_radius() {
+    let skinned_aabb = run(PointRadius::Uniform(0.5), None, Transform::default());
+    assert_bounds(
+        &skinned_aabb.local,
+        Vec3::new(-0.5, -0.5, -0.5),
+        Vec3::new(0.5, 2.5, 0.5),
+    );
+
+    // Scaled up twice by the joint, the column and the radius double.
+    let skinned_aabb = run(
+        PointRadius::Uniform(0.5),
+        None,
+        Transform::from_scale(Vec3::splat(2.0)),
+    );
+    assert_bounds(
+        &skinned_aabb.local,
+        Vec3::new(-1.0, -1.0, -1.0),
+        Vec3::new(1.0, 5.0, 1.0),
+    );
+}
+
+#[test]
+fn radius_per_vertex() {
+    // Only the top point is large, so the bottom of the bounds stays close to the column.
+    let skinned_aabb = run(
+        PointRadius::Attribute,
+        Some(vec![0.1, 0.0, 1.0]),
+        Transform::default(),
+    );
+    assert_bounds(
+        &skinned_aabb.local,
+        Vec3::new(-1.0, -0.1, -1.0),
+        Vec3::new(1.0, 3.0, 1.0),
+    );
+}
+
+#[test]
+fn squash_and_stretch() {
+    let mut app = common::app();
+    app.world
+        .resource_mut::<SkinnedAabbConfig>()
+        .bounding_sphere = true;
+    let spawned = common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    );
+    app.world
+        .entity_mut(spawned.entity)
+        .insert(PointRadius::Uniform(0.25));
+    // Stretched from 1x to 2x along the column, and squashed to half as much across it.
+    for step in 0..=4 {
+        let scale = 1.0 + step as f32 * 0.25;
+        app.world.get_mut::<Transform>(spawned.joint).unwrap().scale =
+            Vec3::new(1.0 / scale, scale, 1.0 / scale);
+        common::update(&mut app, 2);
+        let skinned_aabb = app.world.get::<SkinnedAabb>(spawned.entity).unwrap();
+        // The radius grows with the largest axis of the joint.
+        let radius = 0.25 * scale;
+        assert_bounds(
+            &skinned_aabb.local,
+            Vec3::splat(-radius),
+            Vec3::new(radius, 2.0 * scale + radius, radius),
+        );
+        // The sphere is around the points themselves, half the column's length.
+        let sphere = skinned_aabb.sphere.as_ref().unwrap();
+        assert!(
+            (sphere.radius - scale).abs() < 1e-4,
+            "sphere radius {} at {}x",
+            sphere.radius,
+            scale
+        );
+    }
+}
@@ -0,0 +1,36 @@
+//! [`SkinnedAabbConfig::global_pose_offset`] moves every joint, and so the bounds, without
+//! moving the mesh entity.
+
+mod common;
+
+use bevy::{math::Affine3A, prelude::*};
+use bevy_compute_skinned_aabb::{SkinnedAabb, SkinnedAabbConfig};
+use common::assert_bounds;
+
+#[test]
+fn shifted_by_the_offset_translation() {
+    let mut app = common::app();
+    let column = common::column(3);
+    let joint = Transform::from_xyz(1.0, 0.0, 0.0);
+    let entity = common::spawn_one_joint(&mut app, column, Transform::default(), joint).entity;
+    common::update(&mut app, 3);
+    let (min, max) = (Vec3::new(1.0, 0.0, 0.0), Vec3::new(1.0, 2.0, 0.0));
+    assert_bounds(
+        &app.world.get::<SkinnedAabb>(entity).unwrap().local,
+        min,
+        max,
+    );
+
+    let offset = Vec3::new(-3.0, 0.5, 2.0);
+    app.world
+        .resource_mut::<SkinnedAabbConfig>()
+        .global_pose_offset = Some(Affine3A::from_translation(offset));
+    common::update(&mut app, 2);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(entity).unwrap();
+    assert_bounds(&skinned_aabb.local, min + offset, max + offset);
+    assert_bounds(&skinned_aabb.world, min + offset, max + offset);
+    assert_eq!(
+        *app.world.get::<GlobalTransform>(entity).unwrap(),
+        GlobalTransform::identity()
+    );
+}
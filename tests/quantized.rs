@@ -0,0 +1,89 @@
+//! A mesh stored as `KHR_mesh_quantization` writes it, with `Snorm16x4` positions and `Unorm16x4`
+//! weights, bounded by every CPU path.
+
+mod common;
+
+use bevy::{
+    prelude::*,
+    render::{
+        mesh::{MeshVertexAttribute, PrimitiveTopology, VertexAttributeValues},
+        primitives::Aabb,
+        render_resource::VertexFormat,
+    },
+};
+use bevy_compute_skinned_aabb::{
+    compute_aabb, skin_point_spheres, skin_vertices_along_tangents, skin_vertices_with,
+    skin_vertices_with_normals, skinned_mesh_aabb, AabbQuality, JointAabbs, SkinnedAabbConfig,
+    ZeroWeights, ATTRIBUTE_POINT_RADIUS,
+};
+use common::assert_bounds;
+
+/// Where the joint moves the two vertices, at (1, 0, -1) and (0, 1, 0) in the bind pose.
+const JOINT: Vec3 = Vec3::new(1.0, 2.0, 3.0);
+const MIN: Vec3 = Vec3::new(1.0, 2.0, 2.0);
+const MAX: Vec3 = Vec3::new(2.0, 3.0, 3.0);
+
+fn quantized_mesh() -> Mesh {
+    let mut mesh = Mesh::new(PrimitiveTopology::PointList);
+    mesh.insert_attribute(
+        MeshVertexAttribute {
+            format: VertexFormat::Snorm16x4,
+            ..Mesh::ATTRIBUTE_POSITION
+        },
+        VertexAttributeValues::Snorm16x4(vec![[i16::MAX, 0, -i16::MAX, 0], [0, i16::MAX, 0, 0]]),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vec![[0u16; 4]; 2]);
+    mesh.insert_attribute(
+        MeshVertexAttribute {
+            format: VertexFormat::Unorm16x4,
+            ..Mesh::ATTRIBUTE_JOINT_WEIGHT
+        },
+        VertexAttributeValues::Unorm16x4(vec![[u16::MAX, 0, 0, 0]; 2]),
+    );
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0f32, 0.0, 1.0]; 2]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_TANGENT, vec![[1.0f32, 0.0, 0.0, 1.0]; 2]);
+    mesh.insert_attribute(ATTRIBUTE_POINT_RADIUS, vec![0.0f32; 2]);
+    mesh
+}
+
+fn bounds(positions: &[Vec3]) -> Aabb {
+    compute_aabb(positions).unwrap()
+}
+
+#[test]
+fn read_by_every_skinning_function() {
+    let mesh = quantized_mesh();
+    let joints = [Mat4::from_translation(JOINT)];
+    assert_bounds(&skinned_mesh_aabb(&mesh, &joints).unwrap(), MIN, MAX);
+    let positions = skin_vertices_with(&mesh, &joints, ZeroWeights::Skip).unwrap();
+    assert_bounds(&bounds(&positions), MIN, MAX);
+    let (positions, normals) =
+        skin_vertices_with_normals(&mesh, &joints, ZeroWeights::Skip).unwrap();
+    assert_bounds(&bounds(&positions), MIN, MAX);
+    assert_eq!(normals, vec![Vec3::Z; 2]);
+    let positions = skin_vertices_along_tangents(&mesh, &joints, 0.0).unwrap();
+    assert_bounds(&bounds(&positions), MIN, MAX);
+    let corners = skin_point_spheres(&mesh, &joints, ZeroWeights::Skip).unwrap();
+    assert_bounds(&bounds(&corners), MIN, MAX);
+    let joint_aabbs = JointAabbs::new(&mesh, &[Mat4::IDENTITY]).unwrap();
+    assert_bounds(&bounds(&joint_aabbs.skinned_corners(&joints)), MIN, MAX);
+}
+
+#[test]
+fn bounded_by_the_plugin() {
+    for quality in [AabbQuality::Exact, AabbQuality::PerJoint] {
+        let mut app = common::app();
+        app.world.resource_mut::<SkinnedAabbConfig>().quality = quality;
+        let spawned = common::spawn_one_joint(
+            &mut app,
+            quantized_mesh(),
+            Transform::default(),
+            Transform::from_translation(JOINT),
+        );
+        // The first frame skins the mesh itself, the next ones its cached attributes.
+        for _ in 0..3 {
+            app.update();
+            assert_bounds(app.world.get::<Aabb>(spawned.entity).unwrap(), MIN, MAX);
+        }
+    }
+}
@@ -0,0 +1,72 @@
+//! [`SkinnedAabbConfig::detect_self_intersection`] flags the poses where two triangles of a
+//! skinned mesh pass through each other.
+
+mod common;
+
+use bevy::{
+    prelude::*,
+    render::mesh::{Indices, PrimitiveTopology},
+};
+use bevy_compute_skinned_aabb::{SelfIntersecting, SkinnedAabbConfig};
+
+#[test]
+fn flagged_while_clipping() {
+    let mut app = common::app();
+    app.world
+        .resource_mut::<SkinnedAabbConfig>()
+        .detect_self_intersection = true;
+    // A triangle standing in the XY plane on joint 0, and one lying in the XZ plane on joint 1,
+    // which starts 5 units away along X.
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_POSITION,
+        vec![
+            [-1.0f32, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0],
+            [-0.5, 1.0, -1.0],
+            [0.5, 1.0, -1.0],
+            [0.0, 1.0, 1.0],
+        ],
+    );
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_JOINT_INDEX,
+        [[0u16, 0, 0, 0]; 3]
+            .into_iter()
+            .chain([[1, 0, 0, 0]; 3])
+            .collect::<Vec<_>>(),
+    );
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_JOINT_WEIGHT,
+        vec![[1.0f32, 0.0, 0.0, 0.0]; 6],
+    );
+    mesh.set_indices(Some(Indices::U16(vec![0, 1, 2, 3, 4, 5])));
+    let joints = [Transform::default(), Transform::from_xyz(5.0, 0.0, 0.0)];
+    let (entity, joints) = common::spawn_joints(&mut app, mesh, Transform::default(), &joints);
+    common::update(&mut app, 3);
+    assert_eq!(
+        app.world.get::<SelfIntersecting>(entity),
+        Some(&SelfIntersecting(false))
+    );
+
+    // Moved back onto joint 0, the lying triangle runs through the middle of the standing one.
+    app.world
+        .get_mut::<Transform>(joints[1])
+        .unwrap()
+        .translation = Vec3::ZERO;
+    common::update(&mut app, 2);
+    assert_eq!(
+        app.world.get::<SelfIntersecting>(entity),
+        Some(&SelfIntersecting(true))
+    );
+
+    app.world
+        .get_mut::<Transform>(joints[1])
+        .unwrap()
+        .translation = Vec3::new(0.0, 3.0, 0.0);
+    common::update(&mut app, 2);
+    assert_eq!(
+        app.world.get::<SelfIntersecting>(entity),
+        Some(&SelfIntersecting(false))
+    );
+}
@@ -0,0 +1,365 @@
+//! The SimpleSkin scene of the main example, run headless and bounded at joint angles worked out
+//! by hand.
+
+mod common;
+
+use std::f32::consts::{FRAC_PI_2, PI, TAU};
+
+use bevy::{
+    ecs::system::SystemState,
+    prelude::*,
+    render::{
+        mesh::{
+            skinning::{SkinnedMesh, SkinnedMeshInverseBindposes},
+            Indices, PrimitiveTopology,
+        },
+        primitives::Aabb,
+    },
+};
+use bevy_compute_skinned_aabb::{
+    AabbQuality, BindPoseStats, CollapsedSkin, SkinnedAabb, SkinnedAabbConfig, SkinnedMeshCpuData,
+    SkinnedSurface, SkinnedVertexPositions, StoreSkinnedPositions, WeightPrecision,
+};
+use common::{app, assert_bounds, EPSILON};
+
+/// The joint the top of the strip follows.
+#[derive(Component)]
+struct AnimatedJoint;
+
+/// A vertical strip from (0, 0) to (1, 2), its top half blended onto a joint pivoting at (0.5, 1),
+/// with the mesh entity placed at `offset` along with the joints.
+fn spawn_simple_skin(app: &mut App, offset: Vec3) -> Entity {
+    let mut mesh = Mesh::new(PrimitiveTopology::TriangleList);
+    let positions: Vec<[f32; 3]> = (0..10)
+        .map(|i| [(i % 2) as f32, (i / 2) as f32 * 0.5, 0.0])
+        .collect();
+    let weights: Vec<[f32; 4]> = (0..10)
+        .map(|i| {
+            let weight = ((i / 2) as f32 * 0.25).clamp(0.0, 1.0);
+            [1.0 - weight, weight, 0.0, 0.0]
+        })
+        .collect();
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 0.0, 1.0]; 10]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vec![[0u16, 1, 0, 0]; 10]);
+    mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_WEIGHT, weights);
+    mesh.set_indices(Some(Indices::U16(vec![
+        0, 1, 3, 0, 3, 2, 2, 3, 5, 2, 5, 4, 4, 5, 7, 4, 7, 6, 6, 7, 9, 6, 9, 8,
+    ])));
+    let mesh = app.world.resource_mut::<Assets<Mesh>>().add(mesh);
+    let inverse_bindposes = app
+        .world
+        .resource_mut::<Assets<SkinnedMeshInverseBindposes>>()
+        .add(SkinnedMeshInverseBindposes::from(vec![
+            Mat4::from_translation(Vec3::new(-0.5, -1.0, 0.0)),
+            Mat4::from_translation(Vec3::new(-0.5, -1.0, 0.0)),
+        ]));
+
+    let joint_1 = app
+        .world
+        .spawn()
+        .insert_bundle(TransformBundle::default())
+        .insert(AnimatedJoint)
+        .id();
+    let joint_0 = app
+        .world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(
+            Transform::from_translation(offset + Vec3::new(0.5, 1.0, 0.0)),
+        ))
+        .push_children(&[joint_1])
+        .id();
+    app.world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(
+            Transform::from_translation(offset),
+        ))
+        .insert(mesh)
+        .insert(SkinnedMesh {
+            inverse_bindposes,
+            joints: vec![joint_0, joint_1],
+        })
+        .id()
+}
+
+/// Turn the animated joint to `angle` around Z and step the app a few frames.
+fn pose(app: &mut App, angle: f32) {
+    let mut joints = app
+        .world
+        .query_filtered::<&mut Transform, With<AnimatedJoint>>();
+    for mut transform in joints.iter_mut(&mut app.world) {
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+    common::update(app, 3);
+}
+
+#[test]
+fn bind_pose() {
+    let mut app = app();
+    let entity = spawn_simple_skin(&mut app, Vec3::ZERO);
+    pose(&mut app, 0.0);
+    let aabb = app.world.get::<Aabb>(entity).unwrap();
+    assert_bounds(aabb, Vec3::ZERO, Vec3::new(1.0, 2.0, 0.0));
+}
+
+#[test]
+fn quarter_turn() {
+    let mut app = app();
+    let entity = spawn_simple_skin(&mut app, Vec3::ZERO);
+    // The top row turns to x = -0.5, the rows below are blended partway: the row at y = 0.5
+    // reaches (1, 0.75) and the one at y = 1.5 reaches (0.25, 1.5).
+    pose(&mut app, FRAC_PI_2);
+    let aabb = app.world.get::<Aabb>(entity).unwrap();
+    assert_bounds(aabb, Vec3::new(-0.5, 0.0, 0.0), Vec3::new(1.0, 1.5, 0.0));
+
+    // Turning the other way mirrors the strip around x = 0.5.
+    pose(&mut app, -FRAC_PI_2);
+    let aabb = app.world.get::<Aabb>(entity).unwrap();
+    assert_bounds(aabb, Vec3::ZERO, Vec3::new(1.5, 1.5, 0.0));
+}
+
+#[test]
+fn local_and_world_space() {
+    let mut app = app();
+    let offset = Vec3::new(2.0, 0.0, -1.0);
+    let entity = spawn_simple_skin(&mut app, offset);
+    pose(&mut app, FRAC_PI_2);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(entity).unwrap();
+    let (min, max) = (Vec3::new(-0.5, 0.0, 0.0), Vec3::new(1.0, 1.5, 0.0));
+    assert_bounds(&skinned_aabb.local, min, max);
+    assert_bounds(&skinned_aabb.world, min + offset, max + offset);
+    assert_bounds(app.world.get::<Aabb>(entity).unwrap(), min, max);
+}
+
+#[test]
+fn moved_and_scaled_mesh_entity() {
+    let mut app = app();
+    let entity = spawn_simple_skin(&mut app, Vec3::ZERO);
+    // The mesh is still drawn at its joints, only its local bounds follow the entity.
+    let translation = Vec3::new(3.0, 1.0, 0.0);
+    *app.world.get_mut::<Transform>(entity).unwrap() =
+        Transform::from_translation(translation).with_scale(Vec3::splat(2.0));
+    pose(&mut app, FRAC_PI_2);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(entity).unwrap();
+    let (min, max) = (Vec3::new(-0.5, 0.0, 0.0), Vec3::new(1.0, 1.5, 0.0));
+    assert_bounds(&skinned_aabb.world, min, max);
+    assert_bounds(
+        &skinned_aabb.local,
+        (min - translation) / 2.0,
+        (max - translation) / 2.0,
+    );
+}
+
+#[test]
+fn non_uniformly_scaled_joint() {
+    let mut app = app();
+    let entity = spawn_simple_skin(&mut app, Vec3::ZERO);
+    let mut joints = app
+        .world
+        .query_filtered::<&mut Transform, With<AnimatedJoint>>();
+    for mut transform in joints.iter_mut(&mut app.world) {
+        transform.scale = Vec3::new(2.0, 0.5, 1.0);
+    }
+    // Around the pivot at (0.5, 1), the top row is stretched out to x = -0.5 and 1.5 and squashed
+    // down to y = 1.5. The row at y = 1.5, three quarters on the animated joint, only comes down
+    // to y = 1.3125, and the rows below stay inside.
+    pose(&mut app, 0.0);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(entity).unwrap();
+    let (min, max) = (Vec3::new(-0.5, 0.0, 0.0), Vec3::new(1.5, 1.5, 0.0));
+    assert_bounds(&skinned_aabb.local, min, max);
+    assert_bounds(&skinned_aabb.world, min, max);
+}
+
+#[test]
+fn mirrored_parent() {
+    let mut app = app();
+    let entity = spawn_simple_skin(&mut app, Vec3::ZERO);
+    app.world
+        .spawn()
+        .insert_bundle(TransformBundle::from_transform(Transform::from_scale(
+            Vec3::new(-1.0, 1.0, 1.0),
+        )))
+        .push_children(&[entity]);
+    // The world bounds are those of the quarter turn, mirrored around x = 0 in the local space of
+    // the mesh entity.
+    pose(&mut app, FRAC_PI_2);
+    let skinned_aabb = app.world.get::<SkinnedAabb>(entity).unwrap();
+    assert_bounds(
+        &skinned_aabb.world,
+        Vec3::new(-0.5, 0.0, 0.0),
+        Vec3::new(1.0, 1.5, 0.0),
+    );
+    assert_bounds(
+        &skinned_aabb.local,
+        Vec3::new(-1.0, 0.0, 0.0),
+        Vec3::new(0.5, 1.5, 0.0),
+    );
+    assert_bounds(
+        app.world.get::<Aabb>(entity).unwrap(),
+        Vec3::new(-1.0, 0.0, 0.0),
+        Vec3::new(0.5, 1.5, 0.0),
+    );
+}
+
+#[test]
+fn per_joint_bounds_contain_the_pose() {
+    let mut app = app();
+    let exact = spawn_simple_skin(&mut app, Vec3::ZERO);
+    let per_joint = spawn_simple_skin(&mut app, Vec3::ZERO);
+    app.world
+        .entity_mut(per_joint)
+        .insert(AabbQuality::PerJoint);
+    for step in 0..24 {
+        pose(&mut app, TAU * step as f32 / 24.0);
+        let exact = app.world.get::<Aabb>(exact).unwrap();
+        let per_joint = app.world.get::<Aabb>(per_joint).unwrap();
+        assert!(
+            (per_joint.min() - exact.min()).max_element() <= EPSILON
+                && (exact.max() - per_joint.max()).max_element() <= EPSILON,
+            "step {}: per joint bounds {} to {} don't contain {} to {}",
+            step,
+            per_joint.min(),
+            per_joint.max(),
+            exact.min(),
+            exact.max()
+        );
+    }
+}
+
+#[test]
+fn bounding_sphere_contains_the_pose() {
+    let mut app = app();
+    app.world
+        .resource_mut::<SkinnedAabbConfig>()
+        .bounding_sphere = true;
+    let entity = spawn_simple_skin(&mut app, Vec3::ZERO);
+    app.world.entity_mut(entity).insert(StoreSkinnedPositions);
+    let mut tighter = 0;
+    for step in 0..24 {
+        pose(&mut app, TAU * step as f32 / 24.0);
+        let skinned_aabb = app.world.get::<SkinnedAabb>(entity).unwrap();
+        let sphere = skinned_aabb.sphere.as_ref().unwrap();
+        // The mesh entity is at the origin, so its local space is the world space.
+        let positions = app.world.get::<SkinnedVertexPositions>(entity).unwrap();
+        for position in &positions.0 {
+            assert!(
+                position.distance(sphere.center) <= sphere.radius + EPSILON,
+                "step {}: {} is outside the sphere at {} of radius {}",
+                step,
+                position,
+                sphere.center,
+                sphere.radius
+            );
+        }
+        // The sphere is tighter than the one around the box once the strip bends, except where
+        // it folds flat and every corner of the box is a vertex again: Ritter's sphere is then up
+        // to a few percent larger than the smallest one.
+        let circumscribed = skinned_aabb.local.half_extents.length();
+        assert!(sphere.radius <= circumscribed * 1.05);
+        if sphere.radius < circumscribed - EPSILON {
+            tighter += 1;
+        }
+    }
+    assert!(tighter > 12, "only {} of 24 poses are tighter", tighter);
+}
+
+#[test]
+fn bind_pose_stats() {
+    let mut app = app();
+    let entity = spawn_simple_skin(&mut app, Vec3::new(10.0, 0.0, 0.0));
+    // The stats are of the bind pose, from the rest positions of the mesh, whatever its pose.
+    pose(&mut app, FRAC_PI_2);
+    let data = app.world.get::<SkinnedMeshCpuData>(entity).unwrap();
+    // Five rows of two vertices at x = 0 and 1, from y = 0 to 2.
+    assert_eq!(
+        data.bind_pose,
+        BindPoseStats {
+            centroid: Vec3::new(0.5, 1.0, 0.0),
+            extents: Vec3::new(0.5, 1.0, 0.0),
+        }
+    );
+}
+
+#[test]
+fn closest_point_on_the_surface() {
+    let mut app = app();
+    let entity = spawn_simple_skin(&mut app, Vec3::ZERO);
+    pose(&mut app, FRAC_PI_2);
+    let mut surface = SystemState::<SkinnedSurface>::new(&mut app.world);
+    let surface = surface.get(&app.world);
+
+    // In front of the strip's straight bottom.
+    let (closest, distance) = surface
+        .closest_point_on_skin(entity, Vec3::new(0.5, 0.25, 0.3))
+        .unwrap();
+    assert!(closest.abs_diff_eq(Vec3::new(0.5, 0.25, 0.0), EPSILON));
+    assert!((distance - 0.3).abs() < EPSILON);
+
+    // Off the end of the top, turned a quarter to the left around (0.5, 1): the top edge of the
+    // strip runs from (-0.5, 0.5) to (-0.5, 1.5).
+    let (closest, distance) = surface
+        .closest_point_on_skin(entity, Vec3::new(-1.0, 1.0, 0.0))
+        .unwrap();
+    assert!(closest.abs_diff_eq(Vec3::new(-0.5, 1.0, 0.0), EPSILON));
+    assert!((distance - 0.5).abs() < EPSILON);
+
+    let joint = app.world.get::<SkinnedMesh>(entity).unwrap().joints[0];
+    assert!(surface.closest_point_on_skin(joint, Vec3::ZERO).is_none());
+}
+
+#[test]
+fn quantized_weights() {
+    let run = |precision: WeightPrecision| {
+        let mut app = app();
+        app.world
+            .resource_mut::<SkinnedAabbConfig>()
+            .weight_precision = precision;
+        let entity = spawn_simple_skin(&mut app, Vec3::ZERO);
+        // Bent far enough for the blended vertices to reach the sides of the bounds.
+        pose(&mut app, TAU * 3.0 / 8.0);
+        let weights = app
+            .world
+            .get::<SkinnedMeshCpuData>(entity)
+            .unwrap()
+            .joint_weights
+            .clone();
+        (app.world.get::<Aabb>(entity).unwrap().clone(), weights)
+    };
+    let (full, full_weights) = run(WeightPrecision::Full);
+    let (quantized, quantized_weights) = run(WeightPrecision::Quantized8);
+
+    // The quarter weights aren't multiples of 1/255, so the cache holds the rounded ones.
+    assert_ne!(full_weights, quantized_weights);
+    for (full, quantized) in full_weights.iter().zip(quantized_weights.iter()) {
+        assert_eq!(*quantized, WeightPrecision::Quantized8.quantize(*full));
+    }
+    // Skinned with them, the bounds move, but only by a fraction of a millimeter.
+    let difference = (full.min() - quantized.min())
+        .abs()
+        .max((full.max() - quantized.max()).abs())
+        .max_element();
+    assert!(difference > 0.0 && difference < 1e-3, "{}", difference);
+}
+
+#[test]
+fn collapsed_seam_is_flagged() {
+    let mut app = app();
+    let entity = spawn_simple_skin(&mut app, Vec3::ZERO);
+    app.world
+        .entity_mut(entity)
+        .insert(CollapsedSkin::default());
+    pose(&mut app, FRAC_PI_2);
+    assert!(app
+        .world
+        .get::<CollapsedSkin>(entity)
+        .unwrap()
+        .vertices
+        .is_empty());
+
+    // Turned half around, the middle row, blended evenly between the joints, collapses onto the
+    // joint's pivot.
+    pose(&mut app, PI);
+    let collapsed = app.world.get::<CollapsedSkin>(entity).unwrap();
+    assert_eq!(collapsed.vertices, vec![4, 5]);
+    assert!(collapsed.min_determinant.abs() < EPSILON);
+}
@@ -0,0 +1,78 @@
+//! `skin_model` checked against joint blends worked out by hand.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{skin_model, skin_model_influences, skin_model_normalized};
+use common::EPSILON;
+
+/// Joint 0 moves points up by 1, joint 1 turns them a quarter turn around Z.
+fn joints() -> [Mat4; 2] {
+    [
+        Mat4::from_translation(Vec3::Y),
+        Mat4::from_rotation_z(std::f32::consts::FRAC_PI_2),
+    ]
+}
+
+fn skin(indexes: [u32; 4], weights: [f32; 4], point: Vec3) -> Vec3 {
+    skin_model(&joints(), &indexes, Vec4::from(weights)).transform_point3(point)
+}
+
+#[test]
+fn full_weight_on_one_joint() {
+    let point = Vec3::new(1.0, 0.0, 0.0);
+    let skinned = skin([0, 0, 0, 0], [1.0, 0.0, 0.0, 0.0], point);
+    assert!(skinned.abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), EPSILON));
+    let skinned = skin([1, 0, 0, 0], [1.0, 0.0, 0.0, 0.0], point);
+    assert!(skinned.abs_diff_eq(Vec3::new(0.0, 1.0, 0.0), EPSILON));
+}
+
+#[test]
+fn even_blend() {
+    // Halfway between (1, 1, 0) and (0, 1, 0).
+    let skinned = skin([0, 1, 0, 0], [0.5, 0.5, 0.0, 0.0], Vec3::X);
+    assert!(skinned.abs_diff_eq(Vec3::new(0.5, 1.0, 0.0), EPSILON));
+}
+
+#[test]
+fn quarter_blend() {
+    // The weights of the middle of the SimpleSkin example mesh.
+    let skinned = skin([0, 1, 0, 0], [0.25, 0.75, 0.0, 0.0], Vec3::X);
+    assert!(skinned.abs_diff_eq(Vec3::new(0.25, 1.0, 0.0), EPSILON));
+    let skinned = skin([0, 1, 0, 0], [0.75, 0.25, 0.0, 0.0], Vec3::X);
+    assert!(skinned.abs_diff_eq(Vec3::new(0.75, 1.0, 0.0), EPSILON));
+}
+
+#[test]
+fn zero_weight_joint_contributes_nothing() {
+    let point = Vec3::new(1.0, 2.0, 3.0);
+    let with_unweighted = skin([0, 1, 1, 1], [1.0, 0.0, 0.0, 0.0], point);
+    let alone = skin([0, 0, 0, 0], [1.0, 0.0, 0.0, 0.0], point);
+    assert!(with_unweighted.abs_diff_eq(alone, EPSILON));
+    assert!(alone.abs_diff_eq(point + Vec3::Y, EPSILON));
+}
+
+#[test]
+fn eight_influences_match_four() {
+    // The second set of influences only has zero weights.
+    let skinned = skin_model_influences(
+        &joints(),
+        &[0, 1, 0, 0, 1, 1, 1, 1],
+        &[0.25, 0.75, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0],
+    )
+    .transform_point3(Vec3::X);
+    assert!(skinned.abs_diff_eq(skin([0, 1, 0, 0], [0.25, 0.75, 0.0, 0.0], Vec3::X), EPSILON));
+}
+
+#[test]
+fn normalized_weights_match_weights_summing_to_one() {
+    let point = Vec3::X;
+    let normalized =
+        skin_model_normalized(&joints(), &[0, 1, 0, 0], Vec4::new(0.225, 0.675, 0.0, 0.0))
+            .transform_point3(point);
+    assert!(normalized.abs_diff_eq(skin([0, 1, 0, 0], [0.25, 0.75, 0.0, 0.0], point), EPSILON));
+    // Without any weight, the vertex follows joint 0.
+    let unweighted =
+        skin_model_normalized(&joints(), &[1, 1, 0, 0], Vec4::ZERO).transform_point3(point);
+    assert!(unweighted.abs_diff_eq(Vec3::new(1.0, 1.0, 0.0), EPSILON));
+}
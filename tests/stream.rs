@@ -0,0 +1,64 @@
+//! [`SkinnedAabbStreamPlugin`] sends the bounds of every skinned mesh once per frame, dropping
+//! frames the receiver hasn't made room for.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{SkinnedAabb, SkinnedAabbStream, SkinnedAabbStreamPlugin};
+use common::column;
+
+#[test]
+fn one_frame_per_update() {
+    let mut app = common::app();
+    app.add_plugin(SkinnedAabbStreamPlugin);
+    let (stream, receiver) = SkinnedAabbStream::channel(16);
+    app.insert_resource(stream);
+    let spawn = |app: &mut App, x: f32| {
+        let joint = Transform::from_xyz(x, 0.0, 0.0);
+        common::spawn_one_joint(app, column(3), Transform::default(), joint).entity
+    };
+    let entities = [spawn(&mut app, 0.0), spawn(&mut app, 5.0)];
+    // The bounds are written by commands, so the first frame has none yet.
+    common::update(&mut app, 1);
+    assert!(receiver.try_recv().unwrap().bounds.is_empty());
+
+    common::update(&mut app, 4);
+    let frames: Vec<_> = receiver.try_iter().collect();
+    assert_eq!(frames.len(), 4);
+    for (frame, next) in frames.iter().zip(&frames[1..]) {
+        assert_eq!(next.frame, frame.frame + 1);
+    }
+    for frame in &frames {
+        assert_eq!(frame.bounds.len(), 2);
+        for entity in entities {
+            let streamed = frame.bounds.iter().find(|b| b.entity == entity).unwrap();
+            let skinned_aabb = app.world.get::<SkinnedAabb>(entity).unwrap();
+            assert_eq!(streamed.local.center, skinned_aabb.local.center);
+            assert_eq!(streamed.local.half_extents, skinned_aabb.local.half_extents);
+            assert_eq!(streamed.world.center, skinned_aabb.world.center);
+        }
+    }
+}
+
+#[test]
+fn full_channel_drops_frames() {
+    let mut app = common::app();
+    app.add_plugin(SkinnedAabbStreamPlugin);
+    let (stream, receiver) = SkinnedAabbStream::channel(2);
+    app.insert_resource(stream);
+    common::spawn_one_joint(
+        &mut app,
+        column(3),
+        Transform::default(),
+        Transform::default(),
+    );
+    common::update(&mut app, 5);
+    assert_eq!(app.world.resource::<SkinnedAabbStream>().dropped, 3);
+    let frames: Vec<_> = receiver.try_iter().collect();
+    assert_eq!(frames.len(), 2);
+
+    // Once read, the next frame gets through again.
+    app.update();
+    assert_eq!(receiver.try_iter().count(), 1);
+    assert_eq!(app.world.resource::<SkinnedAabbStream>().dropped, 3);
+}
@@ -0,0 +1,34 @@
+//! [`TangentThickness`] grows the bounds along the skinned tangents of the vertices, not their
+//! normals.
+
+mod common;
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{SkinnedAabb, TangentThickness};
+use common::assert_bounds;
+
+#[test]
+fn grown_along_the_tangents() {
+    let mut app = common::app();
+    // A quad facing +Z with its tangents along +X, on a joint turned a quarter around Z, so the
+    // skinned tangents point along +Y.
+    let mut quad = common::one_joint_mesh(vec![
+        [0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 2.0, 0.0],
+        [1.0, 2.0, 0.0],
+    ]);
+    quad.insert_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0f32, 0.0, 1.0]; 4]);
+    quad.insert_attribute(Mesh::ATTRIBUTE_TANGENT, vec![[1.0f32, 0.0, 0.0, 1.0]; 4]);
+    let joint = Transform::from_rotation(Quat::from_rotation_z(FRAC_PI_2));
+    let entity = common::spawn_one_joint(&mut app, quad, Transform::default(), joint).entity;
+    app.world.entity_mut(entity).insert(TangentThickness(0.25));
+    common::update(&mut app, 3);
+    assert_bounds(
+        &app.world.get::<SkinnedAabb>(entity).unwrap().local,
+        Vec3::new(-2.0, -0.25, 0.0),
+        Vec3::new(0.0, 1.25, 0.0),
+    );
+}
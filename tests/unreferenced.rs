@@ -0,0 +1,51 @@
+//! Vertices no triangle references are left out of the bounds of indexed meshes, unless
+//! [`SkinnedAabbConfig::skip_unreferenced_vertices`] is turned off.
+
+mod common;
+
+use bevy::{prelude::*, render::mesh::Indices};
+use bevy_compute_skinned_aabb::{SkinnedAabb, SkinnedAabbConfig};
+use common::assert_bounds;
+
+/// A triangle from (0, 0, 0) to (1, 1, 0) with an orphaned vertex far away at (100, 0, 0), bounded
+/// with `skip_unreferenced_vertices` set to `skip`. `indices` are only read to tell which vertices
+/// are used, so the mesh can stay a point list.
+fn run(indices: Option<Indices>, skip: bool) -> SkinnedAabb {
+    let mut app = common::app();
+    app.world
+        .resource_mut::<SkinnedAabbConfig>()
+        .skip_unreferenced_vertices = skip;
+    let mut mesh = common::one_joint_mesh(vec![
+        [0.0, 0.0, 0.0],
+        [100.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0],
+        [0.0, 1.0, 0.0],
+    ]);
+    mesh.set_indices(indices);
+    let entity =
+        common::spawn_one_joint(&mut app, mesh, Transform::default(), Transform::default()).entity;
+    common::update(&mut app, 3);
+    app.world.get::<SkinnedAabb>(entity).unwrap().clone()
+}
+
+#[test]
+fn orphaned_vertex_left_out() {
+    for indices in [Indices::U16(vec![0, 2, 3]), Indices::U32(vec![0, 2, 3])] {
+        assert_bounds(
+            &run(Some(indices), true).local,
+            Vec3::ZERO,
+            Vec3::new(1.0, 1.0, 0.0),
+        );
+    }
+}
+
+#[test]
+fn orphaned_vertex_kept() {
+    // Turned off, or without indices to tell which vertices are used.
+    for skinned_aabb in [
+        run(Some(Indices::U16(vec![0, 2, 3])), false),
+        run(None, true),
+    ] {
+        assert_bounds(&skinned_aabb.local, Vec3::ZERO, Vec3::new(100.0, 1.0, 0.0));
+    }
+}
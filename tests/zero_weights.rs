@@ -0,0 +1,73 @@
+//! Vertices whose joint weights are all zero are left out of the bounds rather than collapsed to
+//! the origin, unless [`ZeroWeights::BindToJointZero`] bounds them with joint 0, and weights that
+//! don't sum to 1 are normalized rather than scaling their vertex.
+
+mod common;
+
+use bevy::prelude::*;
+use bevy_compute_skinned_aabb::{AabbQuality, SkinnedAabb, SkinnedAabbConfig, ZeroWeights};
+use common::assert_bounds;
+
+/// Two vertices at (0, 1, 0) and (1, 2, 0) on a joint moved to (5, 0, 0), and an unweighted one
+/// at (0, 0, 3).
+fn run(quality: AabbQuality, zero_weights: ZeroWeights) -> SkinnedAabb {
+    let mut app = common::app();
+    let mut config = app.world.resource_mut::<SkinnedAabbConfig>();
+    config.quality = quality;
+    config.zero_weights = zero_weights;
+    let mut mesh = common::one_joint_mesh(vec![[0.0, 1.0, 0.0], [1.0, 2.0, 0.0], [0.0, 0.0, 3.0]]);
+    mesh.insert_attribute(
+        Mesh::ATTRIBUTE_JOINT_WEIGHT,
+        vec![[1.0f32, 0.0, 0.0, 0.0], [1.0, 0.0, 0.0, 0.0], [0.0; 4]],
+    );
+    let joint = Transform::from_xyz(5.0, 0.0, 0.0);
+    let entity = common::spawn_one_joint(&mut app, mesh, Transform::default(), joint).entity;
+    common::update(&mut app, 3);
+    app.world.get::<SkinnedAabb>(entity).unwrap().clone()
+}
+
+#[test]
+fn not_pulled_to_the_origin() {
+    for quality in [AabbQuality::Exact, AabbQuality::PerJoint] {
+        let skinned_aabb = run(quality, ZeroWeights::Skip);
+        assert_bounds(
+            &skinned_aabb.local,
+            Vec3::new(5.0, 1.0, 0.0),
+            Vec3::new(6.0, 2.0, 0.0),
+        );
+    }
+}
+
+#[test]
+fn bound_to_joint_zero() {
+    let skinned_aabb = run(AabbQuality::Exact, ZeroWeights::BindToJointZero);
+    assert_bounds(
+        &skinned_aabb.local,
+        Vec3::new(5.0, 0.0, 0.0),
+        Vec3::new(6.0, 2.0, 3.0),
+    );
+}
+
+#[test]
+fn normalized_weights() {
+    for quality in [AabbQuality::Exact, AabbQuality::PerJoint] {
+        let mut app = common::app();
+        app.world.resource_mut::<SkinnedAabbConfig>().quality = quality;
+        // Weights rounded by an exporter to sum to 0.8 and 1.25, split over two joints at the
+        // same place.
+        let mut mesh = common::one_joint_mesh(vec![[0.0, 1.0, 0.0], [1.0, 2.0, 0.0]]);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_JOINT_INDEX, vec![[0u16, 1, 0, 0]; 2]);
+        mesh.insert_attribute(
+            Mesh::ATTRIBUTE_JOINT_WEIGHT,
+            vec![[0.5f32, 0.3, 0.0, 0.0], [0.75, 0.5, 0.0, 0.0]],
+        );
+        let joint = Transform::from_xyz(5.0, 0.0, 0.0);
+        let (entity, _) = common::spawn_joints(&mut app, mesh, Transform::default(), &[joint; 2]);
+        common::update(&mut app, 3);
+        assert_bounds(
+            &app.world.get::<SkinnedAabb>(entity).unwrap().local,
+            Vec3::new(5.0, 1.0, 0.0),
+            Vec3::new(6.0, 2.0, 0.0),
+        );
+    }
+}